@@ -0,0 +1,62 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::path::PathBuf;
+
+use isopy_lib::{PackageInfo, PackageOps, Version};
+use url::Url;
+
+use crate::binary_version::BinaryVersion;
+use crate::manifest::Asset;
+
+pub struct BinaryPackage {
+    pub(crate) asset: Asset,
+    pub(crate) version: BinaryVersion,
+    pub(crate) other_version: Version,
+    pub(crate) path: Option<PathBuf>,
+}
+
+impl BinaryPackage {
+    pub(crate) fn new(asset: Asset, version: BinaryVersion, path: Option<PathBuf>) -> Self {
+        let other_version = Version::new(version.clone());
+        Self {
+            asset,
+            version,
+            other_version,
+            path,
+        }
+    }
+
+    pub(crate) fn into_package_info(self) -> PackageInfo {
+        let version = PackageOps::version(&self).to_owned();
+        PackageInfo::new(self.asset.file_name, &self.asset.url, version, self.path)
+    }
+}
+
+impl PackageOps for BinaryPackage {
+    fn version(&self) -> &Version {
+        &self.other_version
+    }
+
+    fn url(&self) -> &Url {
+        &self.asset.url
+    }
+}
@@ -0,0 +1,419 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::collections::HashSet;
+use std::fs::{copy, create_dir_all};
+use std::path::Path;
+
+use anyhow::{Result, anyhow, bail};
+use async_trait::async_trait;
+use isopy_lib::{
+    Accept, Checksum, DownloadAssetOptionsBuilder, DownloadPackageOptions,
+    DownloadPaginatedAssetOptionsBuilder, GetPackageOptions, InstallPackageOptions,
+    ListPackagesOptions, ListTagsOptions, Package, PackageInfo, PackageManagerContext,
+    PackageManagerOps, PluginError, SourceFilter, StripComponents, TagFilter, Tags, Target,
+    UnpackOptionsBuilder, UpdateIndexOptions, Version, error_for_github_rate_limit, parse_shasums,
+};
+use log::warn;
+use serde_json::Value;
+use tokio::fs::read_to_string;
+use url::Url;
+
+use crate::binary_package::BinaryPackage;
+use crate::binary_version::{BinaryVersion, compare_versions};
+use crate::install_info::InstallInfo;
+use crate::json_path::select;
+use crate::manifest::{
+    Artifact, ChecksumSource, Manifest, VersionSource, read_manifest, read_manifests,
+};
+
+const GITHUB_API_URL: &str = "https://api.github.com/";
+
+pub struct BinaryPackageManager {
+    ctx: PackageManagerContext,
+    moniker: String,
+}
+
+impl BinaryPackageManager {
+    pub(crate) fn new(ctx: PackageManagerContext, moniker: &str) -> Self {
+        Self {
+            ctx,
+            moniker: String::from(moniker),
+        }
+    }
+
+    async fn get_versions(
+        &self,
+        manifest: &Manifest,
+        update: bool,
+        show_progress: bool,
+    ) -> Result<Vec<String>> {
+        let mut versions = match &manifest.versions {
+            VersionSource::Json { url, path, prefix } => {
+                let options = DownloadAssetOptionsBuilder::json()
+                    .update(update)
+                    .show_progress(show_progress)
+                    .build()?;
+                let response = self.ctx.download_asset(url, &options).await?;
+                let value = serde_json::from_str::<Value>(&read_to_string(response.path).await?)?;
+                strip_prefix(select(&value, path)?, prefix.as_deref())
+            }
+            VersionSource::GitHub { repo, tag_prefix } => {
+                let mut url = format!("{GITHUB_API_URL}repos/{repo}/releases").parse::<Url>()?;
+                url.query_pairs_mut().append_pair("per_page", "100");
+                let options = DownloadPaginatedAssetOptionsBuilder::default()
+                    .update(update)
+                    .show_progress(show_progress)
+                    .accept(Some(Accept::ApplicationGitHubJson))
+                    .check(Some(error_for_github_rate_limit))
+                    .build()?;
+                let response = self.ctx.download_paginated_asset(&url, &options).await?;
+                let mut pages = Vec::with_capacity(response.parts.len());
+                for part in &response.parts {
+                    pages.push(read_to_string(part).await?);
+                }
+                strip_prefix(release_tags(&pages)?, tag_prefix.as_deref())
+            }
+        };
+
+        versions.sort_by(|a, b| compare_versions(b, a));
+        versions.dedup();
+        Ok(versions)
+    }
+
+    async fn get_packages(
+        &self,
+        update: bool,
+        show_progress: bool,
+        tag_filter: &TagFilter,
+        target: Target,
+    ) -> Result<Vec<BinaryPackage>> {
+        let tags = tag_filter
+            .tags
+            .iter()
+            .map(String::from)
+            .collect::<HashSet<_>>();
+
+        let mut packages = Vec::new();
+        for (tool, manifest) in read_manifests(self.ctx.config_dir())? {
+            if !HashSet::from([tool.clone()]).is_superset(&tags) {
+                continue;
+            }
+
+            for version in self.get_versions(&manifest, update, show_progress).await? {
+                packages.push(self.make_package(
+                    &manifest,
+                    BinaryVersion::new(&tool, &version),
+                    target,
+                )?);
+            }
+        }
+        Ok(packages)
+    }
+
+    fn make_package(
+        &self,
+        manifest: &Manifest,
+        version: BinaryVersion,
+        target: Target,
+    ) -> Result<BinaryPackage> {
+        let asset = manifest.asset(&version.tool, &version.version, target)?;
+        let path = self.ctx.check_asset(&asset.url)?;
+        Ok(BinaryPackage::new(asset, version, path))
+    }
+
+    async fn get_checksum(
+        &self,
+        manifest: &Manifest,
+        package: &BinaryPackage,
+        show_progress: bool,
+    ) -> Result<Option<Checksum>> {
+        let tool = &package.version.tool;
+        let checksum_url = match (&manifest.checksum, &package.asset.checksum_url) {
+            (Some(ChecksumSource::Unverified), _) => {
+                warn!(
+                    "Checksum verification is disabled for {tool}: download will not be verified"
+                );
+                return Ok(None);
+            }
+            (Some(_), Some(checksum_url)) => checksum_url,
+            _ => bail!(
+                "No checksum is configured for {tool}: add \"checksum\" to its manifest or set \"checksum: none\" to install without verification"
+            ),
+        };
+
+        let options = DownloadAssetOptionsBuilder::default()
+            .update(false)
+            .show_progress(show_progress)
+            .build()?;
+        let response = self.ctx.download_asset(checksum_url, &options).await?;
+        let s = read_to_string(response.path).await?;
+        let checksum = if matches!(manifest.checksum, Some(ChecksumSource::Sha256Sums { .. })) {
+            parse_shasums(&s)?
+                .remove(&package.asset.file_name)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "No checksum for {} found in {checksum_url}",
+                        package.asset.file_name
+                    )
+                })?
+        } else {
+            s.split_whitespace()
+                .next()
+                .ok_or_else(|| anyhow!("No checksum found in {checksum_url}"))?
+                .parse()?
+        };
+        Ok(Some(checksum))
+    }
+}
+
+#[async_trait]
+impl PackageManagerOps for BinaryPackageManager {
+    async fn update_index(&self, options: &UpdateIndexOptions) -> Result<()> {
+        for (_, manifest) in read_manifests(self.ctx.config_dir())? {
+            self.get_versions(&manifest, true, options.show_progress)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn list_tags(&self, _options: &ListTagsOptions) -> Result<Tags> {
+        Ok(Tags {
+            platform_tags: Vec::new(),
+            other_tags: read_manifests(self.ctx.config_dir())?
+                .into_iter()
+                .map(|(tool, _)| tool)
+                .collect(),
+        })
+    }
+
+    async fn list_packages(
+        &self,
+        source_filter: SourceFilter,
+        tag_filter: &TagFilter,
+        options: &ListPackagesOptions,
+    ) -> Result<Vec<PackageInfo>> {
+        use isopy_lib::SourceFilter::{All, Local, Remote};
+
+        Ok(self
+            .get_packages(false, options.show_progress, tag_filter, options.target)
+            .await?
+            .into_iter()
+            .filter(|p| {
+                matches!(
+                    (source_filter, p.path.is_some()),
+                    (All, _) | (Local, true) | (Remote, false)
+                )
+            })
+            .map(BinaryPackage::into_package_info)
+            .collect())
+    }
+
+    async fn get_package(
+        &self,
+        version: &Version,
+        _tag_filter: &TagFilter,
+        options: &GetPackageOptions,
+    ) -> Result<Option<PackageInfo>> {
+        let version = isopy_lib::downcast_version!(version, BinaryVersion);
+        let manifest = read_manifest(self.ctx.config_dir(), &version.tool)?;
+        let package = self.make_package(&manifest, version.clone(), options.target)?;
+        Ok(Some(package.into_package_info()))
+    }
+
+    async fn download_package(
+        &self,
+        version: &Version,
+        _tag_filter: &TagFilter,
+        options: &DownloadPackageOptions,
+    ) -> Result<()> {
+        let version = isopy_lib::downcast_version!(version, BinaryVersion);
+        let manifest = read_manifest(self.ctx.config_dir(), &version.tool)?;
+        let package = self.make_package(&manifest, version.clone(), options.target)?;
+        let checksum = self
+            .get_checksum(&manifest, &package, options.show_progress)
+            .await?;
+        let options = DownloadAssetOptionsBuilder::default()
+            .update(false)
+            .checksum(checksum)
+            .show_progress(options.show_progress)
+            .build()?;
+        _ = self
+            .ctx
+            .download_asset(&package.asset.url, &options)
+            .await?;
+        Ok(())
+    }
+
+    async fn install_package(
+        &self,
+        version: &Version,
+        _tag_filter: &TagFilter,
+        dir: &Path,
+        options: &InstallPackageOptions,
+    ) -> Result<Package> {
+        let version = isopy_lib::downcast_version!(version, BinaryVersion);
+        let manifest = read_manifest(self.ctx.config_dir(), &version.tool)?;
+        let package = self.make_package(&manifest, version.clone(), options.target)?;
+
+        let Some(path) = &package.path else {
+            return Err(PluginError::PackageNotDownloaded {
+                package_id: format!("{}:{version}", self.moniker),
+            }
+            .into());
+        };
+
+        match package.asset.artifact {
+            Artifact::Archive(archive_type) => {
+                archive_type
+                    .unpack(
+                        path,
+                        dir,
+                        &UnpackOptionsBuilder::default()
+                            .show_progress(options.show_progress)
+                            .strip_components(
+                                manifest
+                                    .strip_components
+                                    .map_or(StripComponents::Auto, StripComponents::Count),
+                            )
+                            .build()?,
+                    )
+                    .await?;
+            }
+            Artifact::Binary => install_binary(path, dir, &version.tool, &package.asset.file_name)?,
+        }
+
+        InstallInfo {
+            bin_dirs: manifest.bin_dirs(package.asset.artifact),
+        }
+        .write(dir)?;
+
+        Ok(Package::new(package))
+    }
+}
+
+// Returns tags of published releases from each page of a GitHub releases
+// listing
+fn release_tags(pages: &[String]) -> Result<Vec<String>> {
+    let mut tags = Vec::new();
+    for page in pages {
+        let Value::Array(releases) = serde_json::from_str::<Value>(page)? else {
+            bail!("Unexpected response from GitHub releases API")
+        };
+        tags.extend(
+            releases
+                .iter()
+                .filter(|r| {
+                    !r["draft"].as_bool().unwrap_or_default()
+                        && !r["prerelease"].as_bool().unwrap_or_default()
+                })
+                .filter_map(|r| r["tag_name"].as_str().map(String::from)),
+        );
+    }
+    Ok(tags)
+}
+
+fn strip_prefix(values: Vec<String>, prefix: Option<&str>) -> Vec<String> {
+    match prefix {
+        Some(prefix) => values
+            .into_iter()
+            .filter_map(|s| s.strip_prefix(prefix).map(String::from))
+            .collect(),
+        None => values,
+    }
+}
+
+// Copies a bare executable into "bin" under the name of the tool
+fn install_binary(path: &Path, dir: &Path, tool: &str, file_name: &str) -> Result<()> {
+    if dir.exists() {
+        bail!("Output directory {} already exists", dir.display())
+    }
+
+    let bin_dir = dir.join("bin");
+    create_dir_all(&bin_dir)?;
+
+    let bin_path = if Path::new(file_name)
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("exe"))
+    {
+        bin_dir.join(format!("{tool}.exe"))
+    } else {
+        bin_dir.join(tool)
+    };
+    copy(path, &bin_path)?;
+
+    #[cfg(unix)]
+    {
+        use std::fs::{Permissions, set_permissions};
+        use std::os::unix::fs::PermissionsExt;
+        set_permissions(&bin_path, Permissions::from_mode(0o755))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{read_to_string, write};
+
+    use anyhow::Result;
+    use rstest::rstest;
+    use tempfile::tempdir;
+
+    use crate::binary_package_manager::{install_binary, release_tags};
+
+    #[test]
+    fn release_tags_from_all_pages() -> Result<()> {
+        let pages = [
+            String::from(
+                r#"[{"tag_name": "v1.2.0", "draft": false, "prerelease": false}, {"tag_name": "v1.3.0-rc1", "draft": false, "prerelease": true}]"#,
+            ),
+            String::from(
+                r#"[{"tag_name": "v1.1.0", "draft": false, "prerelease": false}, {"tag_name": "v1.4.0", "draft": true, "prerelease": false}]"#,
+            ),
+        ];
+        assert_eq!(vec!["v1.2.0", "v1.1.0"], release_tags(&pages)?);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("bin/jq", "jq-linux-amd64")]
+    #[case("bin/jq.exe", "jq-windows-amd64.exe")]
+    fn install(#[case] expected_path: &str, #[case] file_name: &str) -> Result<()> {
+        let temp_dir = tempdir()?;
+        let path = temp_dir.path().join(file_name);
+        write(&path, "content")?;
+        let dir = temp_dir.path().join("dir");
+
+        install_binary(&path, &dir, "jq", file_name)?;
+        assert_eq!("content", read_to_string(dir.join(expected_path))?);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = dir.join(expected_path).metadata()?.permissions().mode();
+            assert_eq!(0o755, mode & 0o777);
+        }
+
+        assert!(install_binary(&path, &dir, "jq", file_name).is_err());
+        Ok(())
+    }
+}
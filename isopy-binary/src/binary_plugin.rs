@@ -0,0 +1,122 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::Result;
+use isopy_lib::{
+    EnvInfo, MakeEnvInfoOptions, PackageManager, PackageManagerContext, Platform, Plugin,
+    PluginOps, Shell, Version,
+};
+use url::Url;
+
+use crate::binary_package_manager::BinaryPackageManager;
+use crate::binary_version::BinaryVersion;
+use crate::install_info::InstallInfo;
+
+static DOCS_URL: LazyLock<Url> = LazyLock::new(|| {
+    "https://rcook.github.io/isopy/"
+        .parse()
+        .expect("Invalid docs URL")
+});
+
+pub struct BinaryPlugin {
+    moniker: String,
+}
+
+impl BinaryPlugin {
+    pub(crate) fn new_plugin(moniker: &str) -> Plugin {
+        Plugin::new(Self {
+            moniker: String::from(moniker),
+        })
+    }
+}
+
+impl PluginOps for BinaryPlugin {
+    fn url(&self) -> &Url {
+        &DOCS_URL
+    }
+
+    fn parse_version(&self, s: &str) -> Result<Version> {
+        Ok(Version::new(s.parse::<BinaryVersion>()?))
+    }
+
+    fn make_env_info(&self, dir: &Path, _options: &MakeEnvInfoOptions) -> EnvInfo {
+        // Fall back to "bin" for installations without install information
+        let bin_dirs = InstallInfo::read(dir)
+            .map_or_else(|_| vec![PathBuf::from("bin")], |info| info.bin_dirs);
+        EnvInfo {
+            path_dirs: bin_dirs.into_iter().map(|d| dir.join(d)).collect(),
+            vars: vec![],
+            unset_vars: vec![],
+            path_vars: vec![],
+        }
+    }
+
+    fn make_script_command(
+        &self,
+        _script_path: &Path,
+        _platform: Platform,
+        _shell: Shell,
+    ) -> Result<Option<OsString>> {
+        Ok(None)
+    }
+
+    fn new_package_manager(&self, ctx: PackageManagerContext) -> PackageManager {
+        PackageManager::new(BinaryPackageManager::new(ctx, &self.moniker))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use anyhow::Result;
+    use isopy_lib::MakeEnvInfoOptions;
+    use tempfile::tempdir;
+
+    use crate::binary_plugin::BinaryPlugin;
+    use crate::install_info::InstallInfo;
+
+    #[test]
+    fn make_env_info() -> Result<()> {
+        let plugin = BinaryPlugin::new_plugin("binary");
+        let temp_dir = tempdir()?;
+        let dir = temp_dir.path();
+        let options = MakeEnvInfoOptions::default();
+
+        let env_info = plugin.make_env_info(dir, &options);
+        assert_eq!(vec![dir.join("bin")], env_info.path_dirs);
+
+        InstallInfo {
+            bin_dirs: vec![PathBuf::from("."), PathBuf::from("tools/bin")],
+        }
+        .write(dir)?;
+        let env_info = plugin.make_env_info(dir, &options);
+        assert_eq!(
+            vec![dir.join("."), dir.join("tools/bin")],
+            env_info.path_dirs
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::any::Any;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+use anyhow::{Error, bail};
+use isopy_lib::VersionOps;
+
+const SEPARATOR: char = '@';
+
+// Release of a tool described by a manifest such as terraform@1.8.0
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BinaryVersion {
+    pub(crate) tool: String,
+    pub(crate) version: String,
+}
+
+impl BinaryVersion {
+    pub(crate) fn new(tool: &str, version: &str) -> Self {
+        Self {
+            tool: String::from(tool),
+            version: String::from(version),
+        }
+    }
+}
+
+impl Display for BinaryVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}{SEPARATOR}{}", self.tool, self.version)
+    }
+}
+
+impl FromStr for BinaryVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        let Some((tool, version)) = s.split_once(SEPARATOR) else {
+            bail!("Invalid version {s}: expected tool and version such as terraform@1.8.0")
+        };
+
+        if tool.is_empty()
+            || !tool
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            bail!("Invalid tool name {tool} in version {s}")
+        }
+
+        if version.is_empty() || version.contains(char::is_whitespace) {
+            bail!("Invalid version {version} in version {s}")
+        }
+
+        Ok(Self::new(tool, version))
+    }
+}
+
+impl Ord for BinaryVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.tool
+            .cmp(&other.tool)
+            .then_with(|| compare_versions(&self.version, &other.version))
+            .then_with(|| self.version.cmp(&other.version))
+    }
+}
+
+impl PartialOrd for BinaryVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl VersionOps for BinaryVersion {
+    fn as_str(&self) -> Cow<'_, String> {
+        Cow::Owned(format!("{self}"))
+    }
+
+    fn label(&self) -> Option<Cow<'_, String>> {
+        None
+    }
+
+    fn box_clone(&self) -> Box<dyn VersionOps> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn package_name(&self) -> Option<Cow<'_, String>> {
+        Some(Cow::Borrowed(&self.tool))
+    }
+}
+
+// Orders arbitrary version strings: runs of digits compare numerically and
+// anything following "-" is a prerelease that sorts before the release
+pub(crate) fn compare_versions(a: &str, b: &str) -> Ordering {
+    fn split(s: &str) -> (&str, Option<&str>) {
+        s.split_once('-')
+            .map_or((s, None), |(release, prerelease)| {
+                (release, Some(prerelease))
+            })
+    }
+
+    let (a_release, a_prerelease) = split(a);
+    let (b_release, b_prerelease) = split(b);
+    compare_natural(a_release, b_release).then_with(|| match (a_prerelease, b_prerelease) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => compare_natural(a, b),
+    })
+}
+
+fn compare_natural(a: &str, b: &str) -> Ordering {
+    fn chunks(s: &str) -> Vec<&str> {
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        for (i, c) in s.char_indices().skip(1) {
+            let prev_is_digit = s[..i].ends_with(|c: char| c.is_ascii_digit());
+            if prev_is_digit != c.is_ascii_digit() {
+                chunks.push(&s[start..i]);
+                start = i;
+            }
+        }
+        if start < s.len() {
+            chunks.push(&s[start..]);
+        }
+        chunks
+    }
+
+    for (a, b) in chunks(a).into_iter().zip(chunks(b)) {
+        let ordering = if a.starts_with(|c: char| c.is_ascii_digit())
+            && b.starts_with(|c: char| c.is_ascii_digit())
+        {
+            let a = a.trim_start_matches('0');
+            let b = b.trim_start_matches('0');
+            a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+        } else {
+            a.cmp(b)
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    chunks(a).len().cmp(&chunks(b).len())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use anyhow::Result;
+    use rstest::rstest;
+
+    use crate::binary_version::{BinaryVersion, compare_versions};
+
+    #[rstest]
+    #[case("terraform", "1.8.0", "terraform@1.8.0")]
+    #[case("jq", "1.7.1", "jq@1.7.1")]
+    #[case("kube-linter", "v0.6.8", "kube-linter@v0.6.8")]
+    fn parse_valid(
+        #[case] expected_tool: &str,
+        #[case] expected_version: &str,
+        #[case] input: &str,
+    ) -> Result<()> {
+        let version = input.parse::<BinaryVersion>()?;
+        assert_eq!(expected_tool, version.tool);
+        assert_eq!(expected_version, version.version);
+        assert_eq!(input, version.to_string());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("terraform")]
+    #[case("terraform@")]
+    #[case("@1.8.0")]
+    #[case("terra form@1.8.0")]
+    #[case("terraform@1.8 .0")]
+    fn parse_invalid(#[case] input: &str) {
+        assert!(input.parse::<BinaryVersion>().is_err());
+    }
+
+    #[rstest]
+    #[case(Ordering::Equal, "1.8.0", "1.8.0")]
+    #[case(Ordering::Less, "1.8.0", "1.10.0")]
+    #[case(Ordering::Greater, "1.8.1", "1.8.0")]
+    #[case(Ordering::Less, "1.8", "1.8.0")]
+    #[case(Ordering::Less, "1.8.0-rc1", "1.8.0")]
+    #[case(Ordering::Less, "1.8.0-alpha2", "1.8.0-beta1")]
+    #[case(Ordering::Less, "1.8.0-rc2", "1.8.0-rc10")]
+    #[case(Ordering::Greater, "1.8.0-rc1", "1.7.5")]
+    #[case(Ordering::Less, "v0.9", "v0.10")]
+    fn compare(#[case] expected: Ordering, #[case] a: &str, #[case] b: &str) {
+        assert_eq!(expected, compare_versions(a, b));
+    }
+}
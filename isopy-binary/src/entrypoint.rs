@@ -0,0 +1,42 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::sync::Arc;
+
+use isopy_lib::{Plugin, PluginDescriptor, Stability};
+
+use crate::binary_plugin::BinaryPlugin;
+
+#[must_use]
+pub fn new_plugin(moniker: &str) -> Plugin {
+    BinaryPlugin::new_plugin(moniker)
+}
+
+#[must_use]
+pub fn descriptor() -> PluginDescriptor {
+    PluginDescriptor {
+        name: String::from("binary"),
+        description: String::from("Prebuilt tool binaries described by user manifests"),
+        stability: Stability::Experimental,
+        enable_env_var: Some(String::from("ISOPY_BINARY")),
+        new_plugin: Arc::new(new_plugin),
+    }
+}
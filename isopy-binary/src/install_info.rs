@@ -0,0 +1,51 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fs::{read_to_string, write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+// Written to the installation directory so that environments can be built
+// without access to the manifest used to install the tool
+const INSTALL_INFO_FILE_NAME: &str = ".isopy-binary.yaml";
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct InstallInfo {
+    #[serde(rename = "bin_dirs")]
+    pub(crate) bin_dirs: Vec<PathBuf>,
+}
+
+impl InstallInfo {
+    pub(crate) fn read(dir: &Path) -> Result<Self> {
+        let s = read_to_string(dir.join(INSTALL_INFO_FILE_NAME))?;
+        Ok(serde_yaml::from_str(&s)?)
+    }
+
+    pub(crate) fn write(&self, dir: &Path) -> Result<()> {
+        write(
+            dir.join(INSTALL_INFO_FILE_NAME),
+            serde_yaml::to_string(self)?,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use anyhow::{Result, bail};
+use serde_json::Value;
+
+// Selects values from JSON document using a dot-separated path in which "*"
+// matches every element of an array or every value of an object, e.g.
+// "*.version" or "releases.*.tag_name": strings and numbers are returned
+pub(crate) fn select(value: &Value, path: &str) -> Result<Vec<String>> {
+    let mut values = vec![value];
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let mut next = Vec::new();
+        for value in values {
+            match (segment, value) {
+                ("*", Value::Array(items)) => next.extend(items),
+                ("*", Value::Object(map)) => next.extend(map.values()),
+                (key, Value::Object(map)) => next.extend(map.get(key)),
+                (index, Value::Array(items)) => {
+                    let Ok(index) = index.parse::<usize>() else {
+                        bail!("Invalid array index {index} in JSON path {path}")
+                    };
+                    next.extend(items.get(index));
+                }
+                _ => {}
+            }
+        }
+        values = next;
+    }
+
+    Ok(values
+        .into_iter()
+        .filter_map(|value| match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rstest::rstest;
+    use serde_json::json;
+
+    use crate::json_path::select;
+
+    #[rstest]
+    #[case(vec!["1.8.0", "1.7.5"], "*.version")]
+    #[case(vec!["v1.8.0"], "0.tag_name")]
+    #[case(vec!["v1.8.0", "v1.7.5"], "*.tag_name")]
+    #[case(vec![], "*.missing")]
+    #[case(vec!["a", "b"], "*.assets.*.name")]
+    fn select_array(#[case] expected: Vec<&str>, #[case] path: &str) -> Result<()> {
+        let value = json!([
+            {"version": "1.8.0", "tag_name": "v1.8.0", "assets": [{"name": "a"}, {"name": "b"}]},
+            {"version": "1.7.5", "tag_name": "v1.7.5", "assets": []}
+        ]);
+        assert_eq!(expected, select(&value, path)?);
+        Ok(())
+    }
+
+    #[test]
+    fn select_object() -> Result<()> {
+        let value = json!({"releases": {"1.0.0": {"id": 1}, "1.1.0": {"id": 2}}});
+        let mut ids = select(&value, "releases.*.id")?;
+        ids.sort();
+        assert_eq!(vec!["1", "2"], ids);
+        Ok(())
+    }
+
+    #[test]
+    fn select_invalid_index() {
+        assert!(select(&json!([1, 2]), "first").is_err());
+    }
+}
@@ -0,0 +1,460 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::collections::HashMap;
+use std::fs::{read_dir, read_to_string};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+use isopy_lib::{Arch, ArchiveType, Platform, Target};
+use serde::Deserialize;
+use url::Url;
+
+pub(crate) const MANIFESTS_DIR_NAME: &str = "manifests";
+const MANIFEST_EXT: &str = "yaml";
+const BINARY_ARCHIVE: &str = "binary";
+
+// Describes how to discover, download and install releases of a single tool
+// distributed as prebuilt binaries, e.g. <config-dir>/manifests/terraform.yaml
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Manifest {
+    #[serde(rename = "versions", with = "serde_yaml::with::singleton_map")]
+    pub(crate) versions: VersionSource,
+
+    #[serde(rename = "url")]
+    pub(crate) url: String,
+
+    #[serde(rename = "os", default)]
+    pub(crate) os: HashMap<String, String>,
+
+    #[serde(rename = "arch", default)]
+    pub(crate) arch: HashMap<String, String>,
+
+    #[serde(
+        rename = "checksum",
+        default,
+        with = "serde_yaml::with::singleton_map_recursive"
+    )]
+    pub(crate) checksum: Option<ChecksumSource>,
+
+    #[serde(rename = "archive", default)]
+    pub(crate) archive: Option<ArchiveSpec>,
+
+    #[serde(rename = "strip_components", default)]
+    pub(crate) strip_components: Option<usize>,
+
+    #[serde(rename = "bin_dirs", default)]
+    pub(crate) bin_dirs: Option<Vec<PathBuf>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) enum VersionSource {
+    // Versions selected from a JSON document by a path such as "*.version"
+    #[serde(rename = "json")]
+    Json {
+        #[serde(rename = "url")]
+        url: Url,
+
+        #[serde(rename = "path")]
+        path: String,
+
+        #[serde(rename = "prefix", default)]
+        prefix: Option<String>,
+    },
+
+    // Tags of non-prerelease GitHub releases of the given repository
+    #[serde(rename = "github")]
+    GitHub {
+        #[serde(rename = "repo")]
+        repo: String,
+
+        #[serde(rename = "tag_prefix", default)]
+        tag_prefix: Option<String>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) enum ChecksumSource {
+    // File listing SHA-256 digests and file names such as SHA256SUMS
+    #[serde(rename = "sha256sums")]
+    Sha256Sums {
+        #[serde(rename = "url")]
+        url: String,
+    },
+
+    // File containing only the SHA-256 digest of the asset
+    #[serde(rename = "digest")]
+    Digest {
+        #[serde(rename = "url")]
+        url: String,
+    },
+
+    // Explicitly opts out of verifying downloads
+    #[serde(rename = "none")]
+    Unverified,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ArchiveSpec {
+    All(String),
+    PerOs(HashMap<String, String>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Artifact {
+    Archive(ArchiveType),
+    Binary,
+}
+
+impl Artifact {
+    fn parse(s: &str) -> Result<Self> {
+        if s == BINARY_ARCHIVE {
+            return Ok(Self::Binary);
+        }
+
+        match ArchiveType::strip_suffix(&format!(".{s}")) {
+            Some((archive_type, "")) if archive_type.is_supported() => {
+                Ok(Self::Archive(archive_type))
+            }
+            _ => bail!("Unsupported archive type {s}"),
+        }
+    }
+
+    pub(crate) const fn ext(&self) -> &str {
+        match self {
+            Self::Archive(archive_type) => archive_type.suffix(),
+            Self::Binary => "",
+        }
+    }
+}
+
+// Asset to download for a given version and target
+#[derive(Debug)]
+pub(crate) struct Asset {
+    pub(crate) file_name: String,
+    pub(crate) url: Url,
+    pub(crate) artifact: Artifact,
+    pub(crate) checksum_url: Option<Url>,
+}
+
+impl Manifest {
+    pub(crate) fn read(path: &Path) -> Result<Self> {
+        let s = read_to_string(path)
+            .with_context(|| format!("Failed to read manifest {}", path.display()))?;
+        serde_yaml::from_str(&s)
+            .with_context(|| format!("Failed to parse manifest {}", path.display()))
+    }
+
+    pub(crate) fn bin_dirs(&self, artifact: Artifact) -> Vec<PathBuf> {
+        match (artifact, &self.bin_dirs) {
+            (Artifact::Archive(_), Some(bin_dirs)) => bin_dirs.clone(),
+            _ => vec![PathBuf::from("bin")],
+        }
+    }
+
+    pub(crate) fn asset(&self, tool: &str, version: &str, target: Target) -> Result<Asset> {
+        let os_key = match target.platform {
+            Platform::Linux => "linux",
+            Platform::MacOS => "macos",
+            Platform::Windows => "windows",
+        };
+        let arch_key = match target.arch {
+            Arch::Aarch64 => "aarch64",
+            Arch::X86_64 => "x86_64",
+        };
+        let os = self.os.get(os_key).map_or(os_key, String::as_str);
+        let arch = self.arch.get(arch_key).map_or(arch_key, String::as_str);
+
+        let artifact = match &self.archive {
+            Some(ArchiveSpec::All(s)) => Some(Artifact::parse(s)?),
+            Some(ArchiveSpec::PerOs(m)) => m.get(os_key).map(|s| Artifact::parse(s)).transpose()?,
+            None => None,
+        };
+
+        let render = |template: &str, ext: &str| {
+            render_template(
+                template,
+                &[
+                    ("name", tool),
+                    ("version", version),
+                    ("os", os),
+                    ("arch", arch),
+                    ("ext", ext),
+                ],
+            )
+        };
+
+        let url = render(&self.url, artifact.as_ref().map_or("", Artifact::ext))?;
+
+        // Infer archive type from the URL when the manifest doesn't specify it
+        let artifact = match artifact {
+            Some(artifact) => artifact,
+            None => ArchiveType::strip_suffix(url.path())
+                .filter(|(archive_type, _)| archive_type.is_supported())
+                .map_or(Artifact::Binary, |(archive_type, _)| {
+                    Artifact::Archive(archive_type)
+                }),
+        };
+
+        let file_name = url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| anyhow!("URL {url} does not end with a file name"))?
+            .to_owned();
+
+        let checksum_url = match &self.checksum {
+            Some(ChecksumSource::Sha256Sums { url } | ChecksumSource::Digest { url }) => {
+                Some(render(url, artifact.ext())?)
+            }
+            Some(ChecksumSource::Unverified) | None => None,
+        };
+
+        Ok(Asset {
+            file_name,
+            url,
+            artifact,
+            checksum_url,
+        })
+    }
+}
+
+// Replaces "{key}" placeholders in a template with the corresponding values
+pub(crate) fn render_template(template: &str, values: &[(&str, &str)]) -> Result<Url> {
+    let mut s = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        s.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            bail!("Unterminated placeholder in template {template}")
+        };
+        let key = &rest[start + 1..start + end];
+        let Some((_, value)) = values.iter().find(|(k, _)| *k == key) else {
+            bail!("Unknown placeholder {{{key}}} in template {template}")
+        };
+        s.push_str(value);
+        rest = &rest[start + end + 1..];
+    }
+    s.push_str(rest);
+    s.parse()
+        .with_context(|| format!("Template {template} did not produce a valid URL"))
+}
+
+// Returns tool names and manifests found in the manifests directory, sorted
+// by tool name: the tool name is the file stem of the manifest
+pub(crate) fn read_manifests(config_dir: &Path) -> Result<Vec<(String, Manifest)>> {
+    let dir = config_dir.join(MANIFESTS_DIR_NAME);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut manifests = Vec::new();
+    for entry in read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(MANIFEST_EXT) {
+            continue;
+        }
+
+        let Some(tool) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+
+        manifests.push((String::from(tool), Manifest::read(&path)?));
+    }
+
+    manifests.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(manifests)
+}
+
+pub(crate) fn read_manifest(config_dir: &Path, tool: &str) -> Result<Manifest> {
+    let path = config_dir
+        .join(MANIFESTS_DIR_NAME)
+        .join(format!("{tool}.{MANIFEST_EXT}"));
+    if !path.is_file() {
+        bail!("No manifest for tool {tool} found at {}", path.display())
+    }
+    Manifest::read(&path)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use isopy_lib::{Arch, ArchiveType, Libc, Platform, Target};
+    use rstest::rstest;
+
+    use crate::manifest::{Artifact, ChecksumSource, Manifest, VersionSource, render_template};
+
+    const TERRAFORM: &str = r"
+versions:
+  json:
+    url: https://api.releases.hashicorp.com/v1/releases/terraform?limit=20
+    path: '*.version'
+url: https://releases.hashicorp.com/terraform/{version}/terraform_{version}_{os}_{arch}.zip
+os:
+  macos: darwin
+arch:
+  x86_64: amd64
+  aarch64: arm64
+checksum:
+  sha256sums:
+    url: https://releases.hashicorp.com/terraform/{version}/terraform_{version}_SHA256SUMS
+";
+
+    const DENO: &str = r"
+versions:
+  github:
+    repo: denoland/deno
+    tag_prefix: v
+url: https://github.com/denoland/deno/releases/download/v{version}/deno-{arch}-{os}{ext}
+os:
+  linux: unknown-linux-gnu
+  macos: apple-darwin
+  windows: pc-windows-msvc
+archive: zip
+strip_components: 0
+bin_dirs: ['.']
+checksum: none
+";
+
+    const JQ: &str = r"
+versions:
+  github:
+    repo: jqlang/jq
+    tag_prefix: jq-
+url: https://github.com/jqlang/jq/releases/download/jq-{version}/jq-{os}-{arch}
+arch:
+  x86_64: amd64
+  aarch64: arm64
+archive:
+  linux: binary
+";
+
+    const fn target(platform: Platform, arch: Arch) -> Target {
+        Target {
+            platform,
+            arch,
+            libc: None,
+        }
+    }
+
+    #[test]
+    fn terraform() -> Result<()> {
+        let manifest = serde_yaml::from_str::<Manifest>(TERRAFORM)?;
+        assert!(matches!(manifest.versions, VersionSource::Json { .. }));
+        let asset = manifest.asset("terraform", "1.8.0", target(Platform::MacOS, Arch::Aarch64))?;
+        assert_eq!("terraform_1.8.0_darwin_arm64.zip", asset.file_name);
+        assert_eq!(
+            "https://releases.hashicorp.com/terraform/1.8.0/terraform_1.8.0_darwin_arm64.zip",
+            asset.url.as_str()
+        );
+        assert_eq!(Artifact::Archive(ArchiveType::Zip), asset.artifact);
+        assert_eq!(
+            Some("https://releases.hashicorp.com/terraform/1.8.0/terraform_1.8.0_SHA256SUMS"),
+            asset.checksum_url.as_ref().map(url::Url::as_str)
+        );
+        assert_eq!(
+            vec![std::path::PathBuf::from("bin")],
+            manifest.bin_dirs(asset.artifact)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn deno() -> Result<()> {
+        let manifest = serde_yaml::from_str::<Manifest>(DENO)?;
+        let asset = manifest.asset(
+            "deno",
+            "1.42.1",
+            Target {
+                platform: Platform::Linux,
+                arch: Arch::X86_64,
+                libc: Some(Libc::Gnu),
+            },
+        )?;
+        assert_eq!(
+            "https://github.com/denoland/deno/releases/download/v1.42.1/deno-x86_64-unknown-linux-gnu.zip",
+            asset.url.as_str()
+        );
+        assert!(matches!(
+            manifest.checksum,
+            Some(ChecksumSource::Unverified)
+        ));
+        assert!(asset.checksum_url.is_none());
+        assert_eq!(Some(0), manifest.strip_components);
+        assert_eq!(
+            vec![std::path::PathBuf::from(".")],
+            manifest.bin_dirs(asset.artifact)
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("jq-linux-amd64", Platform::Linux)]
+    #[case("jq-macos-amd64", Platform::MacOS)]
+    fn jq(#[case] expected_file_name: &str, #[case] platform: Platform) -> Result<()> {
+        let manifest = serde_yaml::from_str::<Manifest>(JQ)?;
+        let asset = manifest.asset("jq", "1.7.1", target(platform, Arch::X86_64))?;
+        assert_eq!(expected_file_name, asset.file_name);
+        assert_eq!(Artifact::Binary, asset.artifact);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(
+        "https://example.com/1.0/tool-1.0.zip",
+        "https://example.com/{version}/{name}-{version}{ext}"
+    )]
+    #[case("https://example.com/tool", "https://example.com/{name}")]
+    fn render_valid(#[case] expected: &str, #[case] template: &str) -> Result<()> {
+        let url = render_template(
+            template,
+            &[("name", "tool"), ("version", "1.0"), ("ext", ".zip")],
+        )?;
+        assert_eq!(expected, url.as_str());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("https://example.com/{unknown}")]
+    #[case("https://example.com/{version")]
+    #[case("{version}")]
+    fn render_invalid(#[case] template: &str) {
+        assert!(render_template(template, &[("version", "1.0")]).is_err());
+    }
+
+    #[rstest]
+    #[case("archive: rar")]
+    #[case("archive: 7z")]
+    #[case("unknown: value")]
+    fn parse_invalid(#[case] extra: &str) {
+        let s = format!("{JQ}{extra}\n").replace("archive:\n  linux: binary\n", "");
+        assert!(
+            serde_yaml::from_str::<Manifest>(&s)
+                .map_err(anyhow::Error::from)
+                .and_then(|m| m.asset("jq", "1.7.1", target(Platform::Linux, Arch::X86_64)))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+// Reference external plugin providing a fictitious "hello" tool: copy this
+// executable to <config-dir>/plugins/isopy-plugin-hello to try it out
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write, stdin, stdout};
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow, bail};
+use isopy_external::protocol::{
+    AssetResult, Callback, CallbackResponse, DescribeResult, EnvInfoResult, Method,
+    PROTOCOL_VERSION, PackageResult, PluginMessage, Request, TagsResult,
+};
+use serde_json::{Value, json};
+use url::Url;
+
+const VERSIONS: [&str; 2] = ["2.0.0", "1.0.0"];
+
+fn package_url(version: &str) -> Result<Url> {
+    Ok(Url::parse(&format!(
+        "https://example.com/hello-{version}.tar.gz"
+    ))?)
+}
+
+fn make_package(version: &str) -> Result<PackageResult> {
+    Ok(PackageResult {
+        version: String::from(version),
+        url: package_url(version)?,
+    })
+}
+
+fn find_package(version: &str) -> Result<Option<PackageResult>> {
+    VERSIONS
+        .iter()
+        .find(|v| **v == version)
+        .map(|v| make_package(v))
+        .transpose()
+}
+
+fn write_message(message: &PluginMessage) -> Result<()> {
+    let mut stdout = stdout().lock();
+    serde_json::to_writer(&mut stdout, message)?;
+    stdout.write_all(b"\n")?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn call(callback: Callback) -> Result<AssetResult> {
+    write_message(&PluginMessage::Callback(callback))?;
+    let mut line = String::new();
+    stdin().lock().read_line(&mut line)?;
+    match serde_json::from_str::<CallbackResponse>(&line)? {
+        CallbackResponse::Result(value) => Ok(serde_json::from_value(value)?),
+        CallbackResponse::Error(message) => Err(anyhow!(message)),
+    }
+}
+
+fn handle(method: Method) -> Result<Value> {
+    Ok(match method {
+        Method::Describe => serde_json::to_value(DescribeResult {
+            protocol_version: PROTOCOL_VERSION,
+            description: String::from("Reference plugin"),
+            url: Url::parse("https://github.com/rcook/isopy")?,
+        })?,
+        Method::UpdateIndex => Value::Null,
+        Method::ListTags { .. } => serde_json::to_value(TagsResult {
+            platform_tags: Vec::new(),
+            other_tags: vec![String::from("stable")],
+        })?,
+        Method::ListPackages { .. } => serde_json::to_value(
+            VERSIONS
+                .iter()
+                .map(|v| make_package(v))
+                .collect::<Result<Vec<_>>>()?,
+        )?,
+        Method::GetPackage { version, .. } => serde_json::to_value(find_package(&version)?)?,
+        Method::DownloadPackage { version, .. } => {
+            let Some(package) = find_package(&version)? else {
+                bail!("no package with version {version}")
+            };
+            call(Callback::DownloadAsset {
+                url: package.url,
+                checksum: None,
+                json: false,
+                update: false,
+            })?;
+            Value::Null
+        }
+        Method::InstallPackage { version, dir, .. } => {
+            let Some(package) = find_package(&version)? else {
+                bail!("no package with version {version}")
+            };
+            call(Callback::UnpackAsset {
+                url: package.url.clone(),
+                dir,
+                strip_components: None,
+            })?;
+            serde_json::to_value(package)?
+        }
+        Method::MakeEnvInfo { dir } => serde_json::to_value(EnvInfoResult {
+            path_dirs: vec![PathBuf::from("bin")],
+            vars: BTreeMap::from([(String::from("HELLO_HOME"), dir.display().to_string())]),
+            unset_vars: Vec::new(),
+            path_vars: Vec::new(),
+        })?,
+        Method::MakeScriptCommand { script_path, .. } => {
+            if script_path.extension().is_some_and(|e| e == "hello") {
+                json!(format!("hello \"{}\"", script_path.display()))
+            } else {
+                Value::Null
+            }
+        }
+    })
+}
+
+fn run() -> Result<Value> {
+    let mut line = String::new();
+    stdin().lock().read_line(&mut line)?;
+    let request = serde_json::from_str::<Request>(&line)?;
+    if request.protocol_version != PROTOCOL_VERSION {
+        bail!("unsupported protocol version {}", request.protocol_version)
+    }
+    handle(request.method)
+}
+
+fn main() -> Result<()> {
+    let message = match run() {
+        Ok(value) => PluginMessage::Result(value),
+        Err(e) => PluginMessage::Error(e.to_string()),
+    };
+    write_message(&message)
+}
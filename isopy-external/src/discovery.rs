@@ -0,0 +1,162 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fs::read_dir;
+use std::path::{Path, PathBuf, absolute};
+use std::sync::Arc;
+
+use isopy_lib::{PluginDescriptor, Stability};
+
+use crate::entrypoint::new_plugin;
+
+const EXECUTABLE_PREFIX: &str = "isopy-plugin-";
+
+#[cfg(target_os = "windows")]
+const EXECUTABLE_SUFFIX: &str = ".exe";
+
+// Finds executables named isopy-plugin-<name> in the given directories:
+// plugins found in earlier directories take precedence and relative
+// directories are resolved against the current directory
+pub fn find_plugins<I: IntoIterator<Item = PathBuf>>(dirs: I) -> Vec<PluginDescriptor> {
+    let mut descriptors = Vec::<PluginDescriptor>::new();
+    for dir in dirs {
+        // Empty PATH entries cannot be made absolute and are skipped
+        let Ok(dir) = absolute(&dir) else {
+            continue;
+        };
+
+        let Ok(entries) = read_dir(&dir) else {
+            continue;
+        };
+
+        let mut plugins = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let path = entry.path();
+                let name = plugin_name(&path)?;
+                is_executable(&path).then_some((name, path))
+            })
+            .collect::<Vec<_>>();
+        plugins.sort();
+
+        for (name, path) in plugins {
+            if descriptors.iter().all(|d| d.name != name) {
+                descriptors.push(make_descriptor(name, path));
+            }
+        }
+    }
+    descriptors
+}
+
+fn make_descriptor(name: String, path: PathBuf) -> PluginDescriptor {
+    PluginDescriptor {
+        description: format!("External plugin {}", path.display()),
+        name,
+        stability: Stability::External,
+        enable_env_var: None,
+        new_plugin: Arc::new(move |moniker| new_plugin(moniker, &path)),
+    }
+}
+
+fn plugin_name(path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_str()?;
+    let name = file_name.strip_prefix(EXECUTABLE_PREFIX)?;
+
+    #[cfg(target_os = "windows")]
+    let name = name.strip_suffix(EXECUTABLE_SUFFIX)?;
+
+    let is_valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    is_valid.then(|| String::from(name))
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(target_os = "windows")]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{create_dir_all, write};
+    use std::path::{Path, PathBuf};
+
+    use anyhow::Result;
+    use tempfile::tempdir;
+
+    use crate::discovery::find_plugins;
+
+    fn make_executable(path: &Path) -> Result<()> {
+        write(path, "")?;
+
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        {
+            use std::fs::{Permissions, set_permissions};
+            use std::os::unix::fs::PermissionsExt;
+            set_permissions(path, Permissions::from_mode(0o755))?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn find() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let dir0 = temp_dir.path().join("dir0");
+        let dir1 = temp_dir.path().join("dir1");
+        create_dir_all(&dir0)?;
+        create_dir_all(&dir1)?;
+
+        make_executable(&dir0.join("isopy-plugin-foo"))?;
+        make_executable(&dir1.join("isopy-plugin-foo"))?;
+        make_executable(&dir1.join("isopy-plugin-bar-2"))?;
+        make_executable(&dir1.join("isopy-plugin-Invalid"))?;
+        make_executable(&dir1.join("other-program"))?;
+        write(dir1.join("isopy-plugin-not-executable"), "")?;
+
+        let descriptors = find_plugins([
+            PathBuf::new(),
+            dir0.clone(),
+            dir1,
+            temp_dir.path().join("missing"),
+        ]);
+        let names = descriptors
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["foo", "bar-2"], names);
+        assert!(
+            descriptors[0]
+                .description
+                .contains(&dir0.join("isopy-plugin-foo").display().to_string())
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::path::PathBuf;
+
+use anyhow::Result;
+use isopy_lib::{PackageInfo, PackageOps, Version};
+use url::Url;
+
+use crate::external_version::ExternalVersion;
+use crate::protocol::PackageResult;
+
+pub struct ExternalPackage {
+    pub(crate) version: Version,
+    pub(crate) url: Url,
+}
+
+impl ExternalPackage {
+    pub(crate) fn from_result(result: PackageResult) -> Result<Self> {
+        Ok(Self {
+            version: Version::new(result.version.parse::<ExternalVersion>()?),
+            url: result.url,
+        })
+    }
+
+    pub(crate) fn into_package_info(self, path: Option<PathBuf>) -> PackageInfo {
+        let name = self
+            .url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .map_or_else(|| self.url.to_string(), String::from);
+        PackageInfo::new(name, &self.url, self.version, path)
+    }
+}
+
+impl PackageOps for ExternalPackage {
+    fn version(&self) -> &Version {
+        &self.version
+    }
+
+    fn url(&self) -> &Url {
+        &self.url
+    }
+}
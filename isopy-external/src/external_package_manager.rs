@@ -0,0 +1,210 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use isopy_lib::{
+    DownloadPackageOptions, GetPackageOptions, InstallPackageOptions, ListPackagesOptions,
+    ListTagsOptions, Package, PackageInfo, PackageManagerContext, PackageManagerOps, PluginError,
+    SourceFilter, TagFilter, Tags, UpdateIndexOptions, Version,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::external_package::ExternalPackage;
+use crate::external_version::ExternalVersion;
+use crate::protocol::{Method, PackageResult, TagsResult};
+use crate::session::call;
+
+pub struct ExternalPackageManager {
+    ctx: PackageManagerContext,
+    moniker: String,
+    path: PathBuf,
+}
+
+impl ExternalPackageManager {
+    pub(crate) fn new(ctx: PackageManagerContext, moniker: &str, path: &Path) -> Self {
+        Self {
+            ctx,
+            moniker: String::from(moniker),
+            path: path.to_path_buf(),
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        version: Option<&ExternalVersion>,
+        show_progress: bool,
+    ) -> Result<T> {
+        let package_id = match version {
+            Some(version) => format!("{}:{version}", self.moniker),
+            None => self.moniker.clone(),
+        };
+        call(&self.path, &self.ctx, method, &package_id, show_progress).await
+    }
+
+    async fn get_package_inner(
+        &self,
+        version: &ExternalVersion,
+        tag_filter: &TagFilter,
+        method: Method,
+        show_progress: bool,
+    ) -> Result<ExternalPackage> {
+        let Some(result) = self
+            .call::<Option<PackageResult>>(method, Some(version), show_progress)
+            .await?
+        else {
+            return Err(PluginError::PackageNotFound {
+                package_id: format!("{}:{version}", self.moniker),
+                tags: tag_filter.tags.clone(),
+            }
+            .into());
+        };
+        ExternalPackage::from_result(result)
+    }
+}
+
+#[async_trait]
+impl PackageManagerOps for ExternalPackageManager {
+    async fn update_index(&self, options: &UpdateIndexOptions) -> Result<()> {
+        self.call::<Value>(Method::UpdateIndex, None, options.show_progress)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_tags(&self, options: &ListTagsOptions) -> Result<Tags> {
+        let tags = self
+            .call::<TagsResult>(
+                Method::ListTags {
+                    target: options.target.to_string(),
+                },
+                None,
+                options.show_progress,
+            )
+            .await?;
+        Ok(Tags::new(tags.platform_tags, tags.other_tags))
+    }
+
+    async fn list_packages(
+        &self,
+        source_filter: SourceFilter,
+        tag_filter: &TagFilter,
+        options: &ListPackagesOptions,
+    ) -> Result<Vec<PackageInfo>> {
+        use isopy_lib::SourceFilter::{All, Local, Remote};
+
+        let results = self
+            .call::<Vec<PackageResult>>(
+                Method::ListPackages {
+                    tags: tag_filter.tags.clone(),
+                    target: options.target.to_string(),
+                },
+                None,
+                options.show_progress,
+            )
+            .await?;
+
+        // Plugins don't know about isopy's cache so local packages are
+        // identified here
+        let mut packages = Vec::new();
+        for result in results {
+            let package = ExternalPackage::from_result(result)?;
+            let path = self.ctx.check_asset(&package.url)?;
+            if matches!(
+                (source_filter, path.is_some()),
+                (All, _) | (Local, true) | (Remote, false)
+            ) {
+                packages.push(package.into_package_info(path));
+            }
+        }
+        Ok(packages)
+    }
+
+    async fn get_package(
+        &self,
+        version: &Version,
+        tag_filter: &TagFilter,
+        options: &GetPackageOptions,
+    ) -> Result<Option<PackageInfo>> {
+        let version = isopy_lib::downcast_version!(version, ExternalVersion);
+        let package = self
+            .get_package_inner(
+                version,
+                tag_filter,
+                Method::GetPackage {
+                    version: version.to_string(),
+                    tags: tag_filter.tags.clone(),
+                    target: options.target.to_string(),
+                },
+                options.show_progress,
+            )
+            .await?;
+        let path = self.ctx.check_asset(&package.url)?;
+        Ok(Some(package.into_package_info(path)))
+    }
+
+    async fn download_package(
+        &self,
+        version: &Version,
+        tag_filter: &TagFilter,
+        options: &DownloadPackageOptions,
+    ) -> Result<()> {
+        let version = isopy_lib::downcast_version!(version, ExternalVersion);
+        self.call::<Value>(
+            Method::DownloadPackage {
+                version: version.to_string(),
+                tags: tag_filter.tags.clone(),
+                target: options.target.to_string(),
+            },
+            Some(version),
+            options.show_progress,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn install_package(
+        &self,
+        version: &Version,
+        tag_filter: &TagFilter,
+        dir: &Path,
+        options: &InstallPackageOptions,
+    ) -> Result<Package> {
+        let version = isopy_lib::downcast_version!(version, ExternalVersion);
+        let package = self
+            .get_package_inner(
+                version,
+                tag_filter,
+                Method::InstallPackage {
+                    version: version.to_string(),
+                    tags: tag_filter.tags.clone(),
+                    target: options.target.to_string(),
+                    dir: dir.to_path_buf(),
+                },
+                options.show_progress,
+            )
+            .await?;
+        Ok(Package::new(package))
+    }
+}
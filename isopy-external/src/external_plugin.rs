@@ -0,0 +1,147 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Result, bail};
+use isopy_lib::{
+    EnvInfo, MakeEnvInfoOptions, PackageManager, PackageManagerContext, PathVar, PathVarPosition,
+    Platform, Plugin, PluginOps, Shell, Version,
+};
+use log::warn;
+use url::Url;
+
+use crate::external_package_manager::ExternalPackageManager;
+use crate::external_version::ExternalVersion;
+use crate::protocol::{
+    DescribeResult, EnvInfoResult, Method, PROTOCOL_VERSION, PathVarPositionResult,
+};
+use crate::session::call_sync;
+
+pub struct ExternalPlugin {
+    moniker: String,
+    path: PathBuf,
+    url: OnceLock<Url>,
+}
+
+impl ExternalPlugin {
+    pub(crate) fn new_plugin(moniker: &str, path: &Path) -> Plugin {
+        Plugin::new(Self {
+            moniker: String::from(moniker),
+            path: path.to_path_buf(),
+            url: OnceLock::new(),
+        })
+    }
+
+    fn describe(&self) -> Result<DescribeResult> {
+        let result = call_sync::<DescribeResult>(&self.path, Method::Describe)?;
+        if result.protocol_version != PROTOCOL_VERSION {
+            bail!(
+                "Plugin {} uses protocol version {} but isopy requires version {PROTOCOL_VERSION}",
+                self.path.display(),
+                result.protocol_version
+            )
+        }
+        Ok(result)
+    }
+}
+
+impl PluginOps for ExternalPlugin {
+    fn url(&self) -> &Url {
+        self.url.get_or_init(|| match self.describe() {
+            Ok(result) => result.url,
+            Err(e) => {
+                warn!("{e}");
+                Url::from_file_path(&self.path).expect("plugin path must be absolute")
+            }
+        })
+    }
+
+    fn parse_version(&self, s: &str) -> Result<Version> {
+        Ok(Version::new(s.parse::<ExternalVersion>()?))
+    }
+
+    fn make_env_info(&self, dir: &Path, _options: &MakeEnvInfoOptions) -> EnvInfo {
+        let result = call_sync::<EnvInfoResult>(
+            &self.path,
+            Method::MakeEnvInfo {
+                dir: dir.to_path_buf(),
+            },
+        )
+        .unwrap_or_else(|e| {
+            warn!("{e}");
+            EnvInfoResult::default()
+        });
+        EnvInfo {
+            path_dirs: result.path_dirs.into_iter().map(|p| dir.join(p)).collect(),
+            vars: result.vars.into_iter().collect(),
+            unset_vars: result.unset_vars,
+            path_vars: result
+                .path_vars
+                .into_iter()
+                .map(|v| PathVar {
+                    name: v.name,
+                    position: match v.position {
+                        PathVarPositionResult::Prepend => PathVarPosition::Prepend,
+                        PathVarPositionResult::Append => PathVarPosition::Append,
+                    },
+                    dirs: v.dirs.into_iter().map(|p| dir.join(p)).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    fn make_script_command(
+        &self,
+        script_path: &Path,
+        platform: Platform,
+        shell: Shell,
+    ) -> Result<Option<OsString>> {
+        let platform = match platform {
+            Platform::Linux => "linux",
+            Platform::MacOS => "macos",
+            Platform::Windows => "windows",
+        };
+        let shell = match shell {
+            Shell::Bash => "bash",
+            Shell::Cmd => "cmd",
+            Shell::Fish => "fish",
+            Shell::Nushell => "nushell",
+            Shell::PowerShell => "powershell",
+            Shell::Zsh => "zsh",
+        };
+        Ok(call_sync::<Option<String>>(
+            &self.path,
+            Method::MakeScriptCommand {
+                script_path: script_path.to_path_buf(),
+                platform: String::from(platform),
+                shell: String::from(shell),
+            },
+        )?
+        .map(OsString::from))
+    }
+
+    fn new_package_manager(&self, ctx: PackageManagerContext) -> PackageManager {
+        PackageManager::new(ExternalPackageManager::new(ctx, &self.moniker, &self.path))
+    }
+}
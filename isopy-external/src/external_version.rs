@@ -0,0 +1,84 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::any::Any;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+use anyhow::{Error, bail};
+use isopy_lib::VersionOps;
+
+// Version string interpreted by the external plugin only
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExternalVersion(String);
+
+impl Display for ExternalVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for ExternalVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        if s.is_empty() || s.contains(char::is_whitespace) {
+            bail!("Invalid version {s}")
+        }
+        Ok(Self(String::from(s)))
+    }
+}
+
+impl VersionOps for ExternalVersion {
+    fn as_str(&self) -> Cow<'_, String> {
+        Cow::Borrowed(&self.0)
+    }
+
+    fn label(&self) -> Option<Cow<'_, String>> {
+        None
+    }
+
+    fn box_clone(&self) -> Box<dyn VersionOps> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::external_version::ExternalVersion;
+
+    #[rstest]
+    #[case(true, "1.0.0")]
+    #[case(true, "nightly-2024-05-01")]
+    #[case(false, "")]
+    #[case(false, "1.0 .0")]
+    fn parse(#[case] expected: bool, #[case] input: &str) {
+        assert_eq!(expected, input.parse::<ExternalVersion>().is_ok());
+    }
+}
@@ -0,0 +1,352 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+// Messages exchanged between isopy and external plugins
+//
+// isopy runs the plugin executable once per operation and writes a single
+// Request as a line of JSON to its standard input. The plugin then
+// writes PluginMessage lines to its standard output: any number of
+// callbacks, each answered by isopy with a CallbackResponse line, and
+// finally a result or an error after which the plugin exits. Plugins may
+// log to standard error.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+// Plugins must reject requests with a protocol version they don't support
+pub const PROTOCOL_VERSION: u32 = 1;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Request {
+    #[serde(rename = "protocol_version")]
+    pub protocol_version: u32,
+
+    #[serde(flatten)]
+    pub method: Method,
+}
+
+impl Request {
+    #[must_use]
+    pub const fn new(method: Method) -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            method,
+        }
+    }
+}
+
+// Operations performed by plugins: targets are strings such as
+// "linux-x86_64-gnu" and versions are passed through as given by the user
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum Method {
+    // Returns DescribeResult
+    Describe,
+
+    // Returns null
+    UpdateIndex,
+
+    // Returns TagsResult
+    ListTags {
+        #[serde(rename = "target")]
+        target: String,
+    },
+
+    // Returns array of PackageResult
+    ListPackages {
+        #[serde(rename = "tags")]
+        tags: Vec<String>,
+
+        #[serde(rename = "target")]
+        target: String,
+    },
+
+    // Returns PackageResult or null if no matching package exists
+    GetPackage {
+        #[serde(rename = "version")]
+        version: String,
+
+        #[serde(rename = "tags")]
+        tags: Vec<String>,
+
+        #[serde(rename = "target")]
+        target: String,
+    },
+
+    // Returns null once the package has been downloaded using the
+    // download_asset callback
+    DownloadPackage {
+        #[serde(rename = "version")]
+        version: String,
+
+        #[serde(rename = "tags")]
+        tags: Vec<String>,
+
+        #[serde(rename = "target")]
+        target: String,
+    },
+
+    // Returns PackageResult once the package has been installed into "dir",
+    // which does not exist yet, e.g. using the unpack_asset callback
+    InstallPackage {
+        #[serde(rename = "version")]
+        version: String,
+
+        #[serde(rename = "tags")]
+        tags: Vec<String>,
+
+        #[serde(rename = "target")]
+        target: String,
+
+        #[serde(rename = "dir")]
+        dir: PathBuf,
+    },
+
+    // Returns EnvInfoResult: callbacks are not available
+    MakeEnvInfo {
+        #[serde(rename = "dir")]
+        dir: PathBuf,
+    },
+
+    // Returns command string or null if the plugin does not handle the
+    // script: callbacks are not available
+    MakeScriptCommand {
+        #[serde(rename = "script_path")]
+        script_path: PathBuf,
+
+        // "linux", "macos" or "windows"
+        #[serde(rename = "platform")]
+        platform: String,
+
+        // "bash", "cmd", "fish", "nushell", "powershell" or "zsh"
+        #[serde(rename = "shell")]
+        shell: String,
+    },
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginMessage {
+    Callback(Callback),
+    Result(Value),
+    Error(String),
+}
+
+// Operations performed by isopy on behalf of plugins so that downloads are
+// cached and verified in the same way as for built-in plugins: each returns
+// AssetResult
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum Callback {
+    // Returns path of asset if it has already been downloaded
+    CheckAsset {
+        #[serde(rename = "url")]
+        url: Url,
+    },
+
+    // Downloads asset into isopy's cache if not already downloaded,
+    // verifying it against the SHA-256 checksum if given
+    DownloadAsset {
+        #[serde(rename = "url")]
+        url: Url,
+
+        #[serde(rename = "checksum", default)]
+        checksum: Option<String>,
+
+        #[serde(rename = "json", default)]
+        json: bool,
+
+        #[serde(rename = "update", default)]
+        update: bool,
+    },
+
+    // Unpacks previously downloaded archive into "dir" and returns "dir": the
+    // archive type is inferred from the URL and leading path components are
+    // stripped automatically unless "strip_components" is given: "dir" must be
+    // inside the installation directory of an install_package request
+    UnpackAsset {
+        #[serde(rename = "url")]
+        url: Url,
+
+        #[serde(rename = "dir")]
+        dir: PathBuf,
+
+        #[serde(rename = "strip_components", default)]
+        strip_components: Option<usize>,
+    },
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackResponse {
+    Result(Value),
+    Error(String),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DescribeResult {
+    #[serde(rename = "protocol_version")]
+    pub protocol_version: u32,
+
+    #[serde(rename = "description")]
+    pub description: String,
+
+    #[serde(rename = "url")]
+    pub url: Url,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct TagsResult {
+    #[serde(rename = "platform_tags", default)]
+    pub platform_tags: Vec<String>,
+
+    #[serde(rename = "other_tags", default)]
+    pub other_tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PackageResult {
+    #[serde(rename = "version")]
+    pub version: String,
+
+    #[serde(rename = "url")]
+    pub url: Url,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct EnvInfoResult {
+    // Relative paths are resolved against the installation directory
+    #[serde(rename = "path_dirs", default)]
+    pub path_dirs: Vec<PathBuf>,
+
+    #[serde(rename = "vars", default)]
+    pub vars: BTreeMap<String, String>,
+
+    #[serde(rename = "unset_vars", default)]
+    pub unset_vars: Vec<String>,
+
+    #[serde(rename = "path_vars", default)]
+    pub path_vars: Vec<PathVarResult>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathVarPositionResult {
+    Prepend,
+    Append,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PathVarResult {
+    #[serde(rename = "name")]
+    pub name: String,
+
+    #[serde(rename = "position")]
+    pub position: PathVarPositionResult,
+
+    // Relative paths are resolved against the installation directory
+    #[serde(rename = "dirs")]
+    pub dirs: Vec<PathBuf>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AssetResult {
+    #[serde(rename = "path")]
+    pub path: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rstest::rstest;
+    use serde_json::json;
+
+    use crate::protocol::{Callback, Method, PluginMessage, Request};
+
+    #[test]
+    fn request_round_trip() -> Result<()> {
+        let request = Request::new(Method::GetPackage {
+            version: String::from("1.0.0"),
+            tags: vec![String::from("lts")],
+            target: String::from("linux-x86_64-gnu"),
+        });
+        let s = serde_json::to_string(&request)?;
+        assert_eq!(
+            json!({
+                "protocol_version": 1,
+                "method": "get_package",
+                "params": {"version": "1.0.0", "tags": ["lts"], "target": "linux-x86_64-gnu"}
+            }),
+            serde_json::from_str::<serde_json::Value>(&s)?
+        );
+        let request = serde_json::from_str::<Request>(&s)?;
+        assert!(matches!(request.method, Method::GetPackage { .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn request_without_params() -> Result<()> {
+        let request =
+            serde_json::from_str::<Request>(r#"{"protocol_version": 1, "method": "describe"}"#)?;
+        assert!(matches!(request.method, Method::Describe));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(r#"{"result": null}"#)]
+    #[case(r#"{"error": "failed"}"#)]
+    #[case(r#"{"callback": {"method": "check_asset", "params": {"url": "https://example.com/a.zip"}}}"#)]
+    #[case(r#"{"callback": {"method": "download_asset", "params": {"url": "https://example.com/a.zip"}}}"#)]
+    #[case(r#"{"callback": {"method": "unpack_asset", "params": {"url": "https://example.com/a.zip", "dir": "/tmp/a"}}}"#)]
+    fn parse_plugin_message(#[case] input: &str) -> Result<()> {
+        _ = serde_json::from_str::<PluginMessage>(input)?;
+        Ok(())
+    }
+
+    #[test]
+    fn parse_download_asset_defaults() -> Result<()> {
+        let callback = serde_json::from_str::<Callback>(
+            r#"{"method": "download_asset", "params": {"url": "https://example.com/a.zip"}}"#,
+        )?;
+        let Callback::DownloadAsset {
+            checksum,
+            json,
+            update,
+            ..
+        } = callback
+        else {
+            panic!("unexpected callback {callback:?}")
+        };
+        assert!(checksum.is_none() && !json && !update);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(r#"{"unknown": null}"#)]
+    #[case(r#"{"callback": {"method": "delete_everything"}}"#)]
+    fn parse_plugin_message_invalid(#[case] input: &str) {
+        assert!(serde_json::from_str::<PluginMessage>(input).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::io::{BufRead, BufReader, Result as IOResult, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::thread::spawn;
+
+use anyhow::{Context, Result, anyhow, bail};
+use isopy_lib::{
+    ArchiveType, DownloadAssetOptionsBuilder, PackageManagerContext, PluginError, StripComponents,
+    UnpackOptionsBuilder,
+};
+use log::warn;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio::sync::mpsc::unbounded_channel;
+
+use crate::protocol::{AssetResult, Callback, CallbackResponse, Method, PluginMessage, Request};
+
+// Running plugin process handling a single request
+struct Session {
+    path: PathBuf,
+    child: Child,
+    stdin: Option<ChildStdin>,
+}
+
+impl Session {
+    fn start(path: &Path, method: Method) -> Result<(Self, ChildStdout)> {
+        let mut child = Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Failed to start plugin {}", path.display()))?;
+        let stdin = child.stdin.take().expect("stdin must be piped");
+        let stdout = child.stdout.take().expect("stdout must be piped");
+        let mut session = Self {
+            path: path.to_path_buf(),
+            child,
+            stdin: Some(stdin),
+        };
+        session.write(&Request::new(method))?;
+        Ok((session, stdout))
+    }
+
+    fn write<T: Serialize>(&mut self, value: &T) -> Result<()> {
+        let mut s = serde_json::to_string(value)?;
+        s.push('\n');
+        let stdin = self.stdin.as_mut().expect("stdin must be open");
+        stdin
+            .write_all(s.as_bytes())
+            .and_then(|()| stdin.flush())
+            .with_context(|| format!("Failed to write to plugin {}", self.path.display()))
+    }
+
+    fn read(&self, line: Option<IOResult<String>>) -> Result<PluginMessage> {
+        let Some(line) = line else {
+            bail!("Plugin {} exited without responding", self.path.display())
+        };
+        let line =
+            line.with_context(|| format!("Failed to read from plugin {}", self.path.display()))?;
+        serde_json::from_str(&line).with_context(|| {
+            format!(
+                "Invalid message from plugin {}: {line}",
+                self.path.display()
+            )
+        })
+    }
+
+    fn finish<T: DeserializeOwned>(mut self, value: Value) -> Result<T> {
+        drop(self.stdin.take());
+        match self.child.wait() {
+            Ok(status) if !status.success() => {
+                warn!("Plugin {} exited with status {status}", self.path.display());
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to wait for plugin {}: {e}", self.path.display()),
+        }
+
+        serde_json::from_value(value)
+            .with_context(|| format!("Invalid result from plugin {}", self.path.display()))
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        if self.stdin.is_some() {
+            _ = self.child.kill();
+            _ = self.child.wait();
+        }
+    }
+}
+
+// Performs an operation for which callbacks are not available
+pub(crate) fn call_sync<T: DeserializeOwned>(path: &Path, method: Method) -> Result<T> {
+    let (session, stdout) = Session::start(path, method)?;
+    let mut lines = BufReader::new(stdout).lines();
+    match session.read(lines.next())? {
+        PluginMessage::Result(value) => session.finish(value),
+        PluginMessage::Error(message) => bail!("Plugin {} failed: {message}", path.display()),
+        PluginMessage::Callback(_) => bail!(
+            "Plugin {} made callback during operation that does not support callbacks",
+            path.display()
+        ),
+    }
+}
+
+// Performs an operation, handling callbacks from the plugin using the package
+// manager context until the plugin returns a result or error
+pub(crate) async fn call<T: DeserializeOwned>(
+    path: &Path,
+    ctx: &PackageManagerContext,
+    method: Method,
+    package_id: &str,
+    show_progress: bool,
+) -> Result<T> {
+    // Assets may only be unpacked into the directory of the package being
+    // installed
+    let install_dir = match &method {
+        Method::InstallPackage { dir, .. } => Some(dir.clone()),
+        _ => None,
+    };
+
+    let (mut session, stdout) = Session::start(path, method)?;
+
+    // Reading is blocking so lines are passed back from a separate thread
+    let (tx, mut rx) = unbounded_channel();
+    spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut callback_error = None;
+    loop {
+        match session.read(rx.recv().await)? {
+            PluginMessage::Callback(callback) => {
+                let response = match handle_callback(
+                    ctx,
+                    callback,
+                    package_id,
+                    install_dir.as_deref(),
+                    show_progress,
+                )
+                .await
+                {
+                    Ok(value) => CallbackResponse::Result(value),
+                    Err(e) => {
+                        let response = CallbackResponse::Error(e.to_string());
+                        callback_error = Some(e);
+                        response
+                    }
+                };
+                session.write(&response)?;
+            }
+            PluginMessage::Result(value) => return session.finish(value),
+            PluginMessage::Error(message) => {
+                // Preserve the error from a failed callback so that isopy can
+                // still report it, e.g. as package not downloaded
+                return Err(match callback_error {
+                    Some(e) => e.context(message),
+                    None => anyhow!("Plugin {} failed: {message}", path.display()),
+                });
+            }
+        }
+    }
+}
+
+async fn handle_callback(
+    ctx: &PackageManagerContext,
+    callback: Callback,
+    package_id: &str,
+    install_dir: Option<&Path>,
+    show_progress: bool,
+) -> Result<Value> {
+    Ok(match callback {
+        Callback::CheckAsset { url } => serde_json::to_value(AssetResult {
+            path: ctx.check_asset(&url)?,
+        })?,
+        Callback::DownloadAsset {
+            url,
+            checksum,
+            json,
+            update,
+        } => {
+            let mut builder = if json {
+                DownloadAssetOptionsBuilder::json()
+            } else {
+                DownloadAssetOptionsBuilder::default()
+            };
+            let options = builder
+                .update(update)
+                .show_progress(show_progress)
+                .checksum(checksum.map(|s| s.parse()).transpose()?)
+                .build()?;
+            let response = ctx.download_asset(&url, &options).await?;
+            serde_json::to_value(AssetResult {
+                path: Some(response.path),
+            })?
+        }
+        Callback::UnpackAsset {
+            url,
+            dir,
+            strip_components,
+        } => {
+            check_unpack_dir(install_dir, &dir)?;
+            let Some(path) = ctx.check_asset(&url)? else {
+                return Err(PluginError::PackageNotDownloaded {
+                    package_id: String::from(package_id),
+                }
+                .into());
+            };
+            let Some((archive_type, _)) = ArchiveType::strip_suffix(url.path()) else {
+                bail!("Cannot determine archive type of {url}")
+            };
+            archive_type
+                .unpack(
+                    &path,
+                    &dir,
+                    &UnpackOptionsBuilder::default()
+                        .show_progress(show_progress)
+                        .strip_components(
+                            strip_components.map_or(StripComponents::Auto, StripComponents::Count),
+                        )
+                        .build()?,
+                )
+                .await?;
+            serde_json::to_value(AssetResult { path: Some(dir) })?
+        }
+    })
+}
+
+fn check_unpack_dir(install_dir: Option<&Path>, dir: &Path) -> Result<()> {
+    let Some(install_dir) = install_dir else {
+        bail!("Assets can only be unpacked while installing a package")
+    };
+    if dir
+        .components()
+        .any(|component| matches!(component, Component::ParentDir))
+        || !dir.starts_with(install_dir)
+    {
+        bail!(
+            "Refusing to unpack asset outside installation directory {}: {}",
+            install_dir.display(),
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use rstest::rstest;
+
+    use crate::session::check_unpack_dir;
+
+    #[rstest]
+    #[case(true, "/envs/abc/python")]
+    #[case(true, "/envs/abc/python/sub")]
+    #[case(false, "/envs/abc/python/../other")]
+    #[case(false, "/envs/abc/other")]
+    #[case(false, "/envs/abc/python-other")]
+    #[case(false, "relative")]
+    fn check_unpack_dir_basics(#[case] expected: bool, #[case] dir: &str) {
+        let install_dir = Path::new("/envs/abc/python");
+        assert_eq!(
+            expected,
+            check_unpack_dir(Some(install_dir), Path::new(dir)).is_ok()
+        );
+    }
+
+    #[test]
+    fn check_unpack_dir_without_install() {
+        assert!(check_unpack_dir(None, Path::new("/envs/abc/python")).is_err());
+    }
+}
@@ -0,0 +1,260 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::env::current_exe;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use async_trait::async_trait;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use isopy_lib::{
+    DownloadAssetOptions, DownloadAssetResponse, DownloadPackageOptions,
+    DownloadPaginatedAssetOptions, DownloadPaginatedAssetResponse, GetPackageOptions,
+    InstallPackageOptions, ListPackagesOptions, ListTagsOptions, MakeEnvInfoOptions, Package,
+    PackageManagerContext, PackageManagerContextOps, Platform, Plugin, Shell, SourceFilter,
+    TagFilter,
+};
+use tar::{Builder, Header};
+use tempfile::{TempDir, tempdir};
+use url::Url;
+
+// Stands in for isopy's download cache: "downloading" an asset writes a
+// small tarball containing a single executable
+struct FakeContext {
+    dir: PathBuf,
+}
+
+impl FakeContext {
+    fn asset_path(&self, url: &Url) -> PathBuf {
+        self.dir.join(
+            url.path_segments()
+                .and_then(Iterator::last)
+                .expect("must have file name"),
+        )
+    }
+}
+
+#[async_trait]
+impl PackageManagerContextOps for FakeContext {
+    fn base_dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn config_dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn check_asset(&self, url: &Url) -> Result<Option<PathBuf>> {
+        let path = self.asset_path(url);
+        Ok(path.is_file().then_some(path))
+    }
+
+    fn check_asset_dir(&self, _url: &Url) -> Result<Option<PathBuf>> {
+        bail!("not supported")
+    }
+
+    fn make_asset_dir(&self, _url: &Url, _create_new: bool) -> Result<PathBuf> {
+        bail!("not supported")
+    }
+
+    async fn download_asset(
+        &self,
+        url: &Url,
+        _options: &DownloadAssetOptions,
+    ) -> Result<DownloadAssetResponse> {
+        let path = self.asset_path(url);
+        let mut builder =
+            Builder::new(GzEncoder::new(File::create(&path)?, Compression::default()));
+        let data = b"#!/bin/sh\necho hello\n";
+        let mut header = Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, "hello/bin/hello", &data[..])?;
+        builder.into_inner()?.finish()?;
+        Ok(DownloadAssetResponse { path })
+    }
+
+    async fn download_paginated_asset(
+        &self,
+        _url: &Url,
+        _options: &DownloadPaginatedAssetOptions,
+    ) -> Result<DownloadPaginatedAssetResponse> {
+        bail!("not supported")
+    }
+}
+
+// Examples are built alongside integration tests in the parent directory of
+// the test executable's directory
+fn reference_plugin_path() -> Result<PathBuf> {
+    let exe = current_exe()?;
+    let Some(deps_dir) = exe.parent() else {
+        bail!("cannot determine directory of {}", exe.display())
+    };
+    let Some(target_dir) = deps_dir.parent() else {
+        bail!("cannot determine target directory of {}", exe.display())
+    };
+    let mut path = target_dir.join("examples").join("isopy-plugin-reference");
+    if cfg!(windows) {
+        path.set_extension("exe");
+    }
+    Ok(path)
+}
+
+fn make_plugin() -> Result<(TempDir, Plugin, PackageManagerContext)> {
+    let temp_dir = tempdir()?;
+    let plugin = isopy_external::new_plugin("reference", &reference_plugin_path()?);
+    let ctx = PackageManagerContext::new(FakeContext {
+        dir: temp_dir.path().to_path_buf(),
+    });
+    Ok((temp_dir, plugin, ctx))
+}
+
+#[tokio::test]
+async fn list_download_install() -> Result<()> {
+    let (temp_dir, plugin, ctx) = make_plugin()?;
+    assert_eq!("https://github.com/rcook/isopy", plugin.url().as_str());
+
+    let package_manager = plugin.new_package_manager(ctx);
+    let tag_filter = TagFilter::default();
+
+    let tags = package_manager
+        .list_tags(&ListTagsOptions::default())
+        .await?;
+    assert_eq!(vec![String::from("stable")], tags.other_tags);
+
+    let packages = package_manager
+        .list_packages(
+            SourceFilter::All,
+            &tag_filter,
+            &ListPackagesOptions::default(),
+        )
+        .await?;
+    assert_eq!(
+        vec!["2.0.0", "1.0.0"],
+        packages
+            .iter()
+            .map(|p| p.version.as_str().into_owned())
+            .collect::<Vec<_>>()
+    );
+    assert!(packages.iter().all(|p| p.path.is_none()));
+
+    let version = plugin.parse_version("1.0.0")?;
+    assert!(
+        package_manager
+            .get_package(&version, &tag_filter, &GetPackageOptions::default())
+            .await?
+            .is_some_and(|p| p.path.is_none())
+    );
+
+    let dir = temp_dir.path().join("hello-1.0.0");
+    assert!(
+        package_manager
+            .install_package(
+                &version,
+                &tag_filter,
+                &dir,
+                &InstallPackageOptions::default()
+            )
+            .await
+            .is_err()
+    );
+
+    package_manager
+        .download_package(&version, &tag_filter, &DownloadPackageOptions::default())
+        .await?;
+    let packages = package_manager
+        .list_packages(
+            SourceFilter::Local,
+            &tag_filter,
+            &ListPackagesOptions::default(),
+        )
+        .await?;
+    assert_eq!(1, packages.len());
+    assert_eq!("hello-1.0.0.tar.gz", packages[0].name);
+
+    let package: Package = package_manager
+        .install_package(
+            &version,
+            &tag_filter,
+            &dir,
+            &InstallPackageOptions::default(),
+        )
+        .await?;
+    assert_eq!("1.0.0", package.version().as_str().as_str());
+    assert!(dir.join("bin").join("hello").is_file());
+
+    let env_info = plugin.make_env_info(&dir, &MakeEnvInfoOptions::default());
+    assert_eq!(vec![dir.join("bin")], env_info.path_dirs);
+    assert_eq!(
+        vec![(String::from("HELLO_HOME"), dir.display().to_string())],
+        env_info.vars
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn unknown_version() -> Result<()> {
+    let (_temp_dir, plugin, ctx) = make_plugin()?;
+    let package_manager = plugin.new_package_manager(ctx);
+    let version = plugin.parse_version("3.0.0")?;
+    assert!(
+        package_manager
+            .get_package(
+                &version,
+                &TagFilter::default(),
+                &GetPackageOptions::default()
+            )
+            .await
+            .is_err()
+    );
+    assert!(
+        package_manager
+            .download_package(
+                &version,
+                &TagFilter::default(),
+                &DownloadPackageOptions::default()
+            )
+            .await
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn make_script_command() -> Result<()> {
+    let (_temp_dir, plugin, _ctx) = make_plugin()?;
+    assert_eq!(
+        Some("hello \"script.hello\""),
+        plugin
+            .make_script_command(Path::new("script.hello"), Platform::Linux, Shell::Bash)?
+            .as_deref()
+            .and_then(|s| s.to_str())
+    );
+    assert!(
+        plugin
+            .make_script_command(Path::new("script.py"), Platform::Linux, Shell::Bash)?
+            .is_none()
+    );
+    Ok(())
+}
@@ -19,7 +19,9 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
-use isopy_lib::Plugin;
+use std::sync::Arc;
+
+use isopy_lib::{Plugin, PluginDescriptor, Stability};
 
 use crate::go_plugin::GoPlugin;
 
@@ -27,3 +29,14 @@ use crate::go_plugin::GoPlugin;
 pub fn new_plugin(moniker: &str) -> Plugin {
     GoPlugin::new_plugin(moniker)
 }
+
+#[must_use]
+pub fn descriptor() -> PluginDescriptor {
+    PluginDescriptor {
+        name: String::from("go"),
+        description: String::from("Go toolchains from go.dev"),
+        stability: Stability::Experimental,
+        enable_env_var: Some(String::from("ISOPY_GO")),
+        new_plugin: Arc::new(new_plugin),
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fs::read_to_string;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use isopy_lib::{ProjectVersion, ProjectVersionSpec, Version, VersionRequirement};
+
+use crate::go_version::GoVersion;
+use crate::go_version_requirement::GoVersionRequirement;
+
+const GO_MOD_FILE_NAME: &str = "go.mod";
+
+#[derive(Debug, Default, PartialEq)]
+struct GoMod {
+    go: Option<String>,
+    toolchain: Option<String>,
+}
+
+impl GoMod {
+    // Only the "go" and "toolchain" directives are of interest: these never
+    // appear inside blocks such as "require (...)"
+    fn parse(s: &str) -> Self {
+        let mut go_mod = Self::default();
+        let mut in_block = false;
+        for line in s.lines() {
+            let line = line
+                .split_once("//")
+                .map_or(line, |(before, _)| before)
+                .trim();
+            if in_block {
+                in_block = line != ")";
+                continue;
+            }
+
+            if line.ends_with('(') {
+                in_block = true;
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some("go"), Some(value), None) => go_mod.go = Some(String::from(value)),
+                (Some("toolchain"), Some(value), None) => {
+                    go_mod.toolchain = Some(String::from(value));
+                }
+                _ => {}
+            }
+        }
+        go_mod
+    }
+
+    // The toolchain directive names an exact toolchain while the go directive
+    // names the minimum language version, e.g. "1.22" or "1.22.0", and is
+    // resolved to the latest patch release: prereleases are taken as is
+    fn spec(&self) -> Result<Option<ProjectVersionSpec>> {
+        if let Some(toolchain) = self.toolchain.as_deref().filter(|s| *s != "default") {
+            let version = toolchain
+                .parse::<GoVersion>()
+                .with_context(|| format!("Invalid toolchain directive {toolchain}"))?;
+            return Ok(Some(ProjectVersionSpec::Exact(Version::new(version))));
+        }
+
+        let Some(go) = &self.go else {
+            return Ok(None);
+        };
+
+        let version = format!("go{go}")
+            .parse::<GoVersion>()
+            .with_context(|| format!("Invalid go directive {go}"))?;
+        Ok(Some(if version.is_stable() {
+            ProjectVersionSpec::Requirement(VersionRequirement::new(GoVersionRequirement::new(
+                version,
+            )))
+        } else {
+            ProjectVersionSpec::Exact(Version::new(version))
+        }))
+    }
+}
+
+pub(crate) fn detect_project_version(dir: &Path) -> Result<Option<ProjectVersion>> {
+    let path = dir.join(GO_MOD_FILE_NAME);
+    let s = match read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let spec = GoMod::parse(&s)
+        .spec()
+        .with_context(|| format!("Failed to read Go version from {}", path.display()))?;
+    Ok(spec.map(|spec| ProjectVersion { path, spec }))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use isopy_lib::ProjectVersionSpec;
+    use rstest::rstest;
+
+    use crate::go_mod::GoMod;
+
+    const GO_MOD: &str = r"module example.com/hello
+
+// go 1.19 in a comment is ignored
+go 1.22 // language version
+
+toolchain go1.22.3
+
+require (
+	golang.org/x/text v0.14.0
+	go v1.0.0
+)
+";
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            GoMod {
+                go: Some(String::from("1.22")),
+                toolchain: Some(String::from("go1.22.3")),
+            },
+            GoMod::parse(GO_MOD)
+        );
+    }
+
+    #[rstest]
+    #[case(Some("exact go1.22.3"), Some("1.22"), Some("go1.22.3"))]
+    #[case(Some("go1.22 or later patch release"), Some("1.22"), None)]
+    #[case(
+        Some("go1.22.1 or later patch release"),
+        Some("1.22.1"),
+        Some("default")
+    )]
+    #[case(Some("exact go1.23rc1"), Some("1.23rc1"), None)]
+    #[case(None, None, None)]
+    fn spec(
+        #[case] expected: Option<&str>,
+        #[case] go: Option<&str>,
+        #[case] toolchain: Option<&str>,
+    ) -> Result<()> {
+        let go_mod = GoMod {
+            go: go.map(String::from),
+            toolchain: toolchain.map(String::from),
+        };
+        let spec = go_mod.spec()?.map(|spec| match spec {
+            ProjectVersionSpec::Exact(version) => format!("exact {}", version.as_str()),
+            ProjectVersionSpec::Requirement(requirement) => requirement.to_string(),
+        });
+        assert_eq!(expected, spec.as_deref());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(Some("1.x"), None)]
+    #[case(None, Some("1.22.3"))]
+    fn spec_invalid(#[case] go: Option<&str>, #[case] toolchain: Option<&str>) {
+        let go_mod = GoMod {
+            go: go.map(String::from),
+            toolchain: toolchain.map(String::from),
+        };
+        assert!(go_mod.spec().is_err());
+    }
+}
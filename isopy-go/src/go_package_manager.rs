@@ -22,14 +22,15 @@
 use std::collections::HashSet;
 use std::path::Path;
 
-use anyhow::{Result, anyhow, bail};
+use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use isopy_lib::{
-    ArchiveType, DirUrl, DownloadAssetOptionsBuilder, DownloadPackageOptions, GetPackageOptions,
-    InstallPackageOptions, ListPackagesOptions, ListTagsOptions, Package, PackageInfo,
-    PackageManagerContext, PackageManagerOps, SourceFilter, TagFilter, Tags, UpdateIndexOptions,
-    Version, query,
+    Arch, ArchiveType, DirUrl, DownloadAssetOptionsBuilder, DownloadPackageOptions,
+    GetPackageOptions, InstallPackageOptions, ListPackagesOptions, ListTagsOptions, Package,
+    PackageInfo, PackageManagerContext, PackageManagerOps, Platform, PluginError, SourceFilter,
+    TagFilter, Tags, Target, UnpackOptionsBuilder, UpdateIndexOptions, Version, query,
 };
+use log::debug;
 use serde_json::Value;
 use tokio::fs::read_to_string;
 use url::Url;
@@ -38,33 +39,30 @@ use crate::api::Release;
 use crate::go_package::GoPackage;
 use crate::go_version::GoVersion;
 
-#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
-const PLATFORM_TAGS: [&str; 2] = ["arm64", "linux"];
-
-#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
-const PLATFORM_TAGS: [&str; 2] = ["amd64", "linux"];
-
-#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
-const PLATFORM_TAGS: [&str; 2] = ["arm64", "darwin"];
-
-#[cfg(all(target_os = "macos", target_arch = "x86_64"))]
-const PLATFORM_TAGS: [&str; 2] = ["amd64", "darwin"];
-
-#[cfg(all(target_os = "windows", target_arch = "aarch64"))]
-const PLATFORM_TAGS: [&str; 2] = ["arm64", "windows"];
-
-#[cfg(all(target_os = "windows", target_arch = "x86_64"))]
-const PLATFORM_TAGS: [&str; 2] = ["amd64", "windows"];
+const fn platform_tags(target: Target) -> [&'static str; 2] {
+    let arch = match target.arch {
+        Arch::Aarch64 => "arm64",
+        Arch::X86_64 => "amd64",
+    };
+    let os = match target.platform {
+        Platform::Linux => "linux",
+        Platform::MacOS => "darwin",
+        Platform::Windows => "windows",
+    };
+    [arch, os]
+}
 
 pub struct GoPackageManager {
     ctx: PackageManagerContext,
+    moniker: String,
     url: Url,
 }
 
 impl GoPackageManager {
-    pub(crate) fn new(ctx: PackageManagerContext, url: &Url) -> Self {
+    pub(crate) fn new(ctx: PackageManagerContext, moniker: &str, url: &Url) -> Self {
         Self {
             ctx,
+            moniker: String::from(moniker),
             url: url.clone(),
         }
     }
@@ -87,11 +85,16 @@ impl GoPackageManager {
         Ok(index)
     }
 
-    async fn get_packages(&self, update: bool, show_progress: bool) -> Result<Vec<GoPackage>> {
+    async fn get_packages(
+        &self,
+        update: bool,
+        show_progress: bool,
+        target: Target,
+    ) -> Result<Vec<GoPackage>> {
         let index = self.get_index(update, show_progress).await?;
+        let filter_tags = HashSet::from(platform_tags(target));
         let mut packages = Vec::new();
         for release in serde_json::from_value::<Vec<Release>>(index)? {
-            let filter_tags = HashSet::from(PLATFORM_TAGS);
             for file in release.files {
                 match file.kind.as_str() {
                     "archive" => {
@@ -123,7 +126,10 @@ impl GoPackageManager {
                         }
                     }
                     "installer" | "source" => {}
-                    _ => todo!("Unimplemented file kind {}", file.kind),
+                    _ => debug!(
+                        "Skipping file {} with unsupported kind {}",
+                        file.file_name, file.kind
+                    ),
                 }
             }
         }
@@ -135,23 +141,24 @@ impl GoPackageManager {
         update: bool,
         show_progress: bool,
         version: &GoVersion,
-        _tags: &TagFilter,
+        tag_filter: &TagFilter,
+        target: Target,
     ) -> Result<GoPackage> {
-        let packages = self.get_packages(update, show_progress).await?;
+        let packages = self.get_packages(update, show_progress, target).await?;
         let mut packages = packages
             .into_iter()
             .filter(|p| &p.version == version)
             .collect::<Vec<_>>();
-        if packages.is_empty() {
-            bail!("No matching packages found")
-        }
 
         Self::sort_packages(&mut packages);
 
-        Ok(packages
-            .into_iter()
-            .next()
-            .expect("Vector must contain at least one element"))
+        packages.into_iter().next().ok_or_else(|| {
+            PluginError::PackageNotFound {
+                package_id: format!("{}:{version}", self.moniker),
+                tags: tag_filter.tags.clone(),
+            }
+            .into()
+        })
     }
 }
 
@@ -162,10 +169,9 @@ impl PackageManagerOps for GoPackageManager {
         Ok(())
     }
 
-    async fn list_tags(&self, _options: &ListTagsOptions) -> Result<Tags> {
-        let mut platform_tags = PLATFORM_TAGS
-            .iter()
-            .copied()
+    async fn list_tags(&self, options: &ListTagsOptions) -> Result<Tags> {
+        let mut platform_tags = platform_tags(options.target)
+            .into_iter()
             .map(String::from)
             .collect::<Vec<_>>();
         platform_tags.sort();
@@ -189,10 +195,13 @@ impl PackageManagerOps for GoPackageManager {
             .iter()
             .map(String::from)
             .collect::<HashSet<_>>();
-        tags.extend(PLATFORM_TAGS.into_iter().map(String::from));
+        tags.extend(platform_tags(options.target).into_iter().map(String::from));
 
         let mut packages = Vec::new();
-        for p in self.get_packages(false, options.show_progress).await? {
+        for p in self
+            .get_packages(false, options.show_progress, options.target)
+            .await?
+        {
             if p.tags.is_superset(&tags)
                 && matches!(
                     (source_filter, p.path.is_some()),
@@ -219,7 +228,7 @@ impl PackageManagerOps for GoPackageManager {
     ) -> Result<Option<PackageInfo>> {
         let version = isopy_lib::downcast_version!(version, GoVersion);
         let package = self
-            .get_package_inner(false, options.show_progress, version, tags)
+            .get_package_inner(false, options.show_progress, version, tags, options.target)
             .await?;
         Ok(Some(package.into_package_info()))
     }
@@ -232,7 +241,7 @@ impl PackageManagerOps for GoPackageManager {
     ) -> Result<()> {
         let version = isopy_lib::downcast_version!(version, GoVersion);
         let package = self
-            .get_package_inner(false, options.show_progress, version, tags)
+            .get_package_inner(false, options.show_progress, version, tags, options.target)
             .await?;
         let options = DownloadAssetOptionsBuilder::default()
             .update(false)
@@ -251,24 +260,33 @@ impl PackageManagerOps for GoPackageManager {
         options: &InstallPackageOptions,
     ) -> Result<Package> {
         let version = isopy_lib::downcast_version!(version, GoVersion);
-        let Ok(package) = self
-            .get_package_inner(false, options.show_progress, version, tag_filter)
-            .await
-        else {
-            bail!(
-                "No release {version} with tags {tags:?} found",
-                tags = tag_filter.tags
-            );
-        };
+        let package = self
+            .get_package_inner(
+                false,
+                options.show_progress,
+                version,
+                tag_filter,
+                options.target,
+            )
+            .await?;
 
         let Some(path) = self.ctx.check_asset(&package.url)? else {
-            bail!(
-                "Failed to download release {version} with tags {tags:?}",
-                tags = tag_filter.tags
-            );
+            return Err(PluginError::PackageNotDownloaded {
+                package_id: format!("{}:{version}", self.moniker),
+            }
+            .into());
         };
 
-        package.archive_type.unpack(&path, dir, options).await?;
+        package
+            .archive_type
+            .unpack(
+                &path,
+                dir,
+                &UnpackOptionsBuilder::default()
+                    .show_progress(options.show_progress)
+                    .build()?,
+            )
+            .await?;
 
         Ok(Package::new(package))
     }
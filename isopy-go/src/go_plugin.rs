@@ -25,21 +25,27 @@ use std::sync::LazyLock;
 
 use anyhow::Result;
 use isopy_lib::{
-    EnvInfo, PackageManager, PackageManagerContext, Platform, Plugin, PluginOps, Shell, Version,
+    EnvInfo, MakeEnvInfoOptions, PackageManager, PackageManagerContext, Platform, Plugin,
+    PluginOps, ProjectVersion, Shell, Version,
 };
 use url::Url;
 
+use crate::go_mod::detect_project_version;
 use crate::go_package_manager::GoPackageManager;
 use crate::go_version::GoVersion;
 
 static INDEX_URL: LazyLock<Url> =
     LazyLock::new(|| "https://go.dev/dl/".parse().expect("Invalid index URL"));
 
-pub struct GoPlugin;
+pub struct GoPlugin {
+    moniker: String,
+}
 
 impl GoPlugin {
-    pub(crate) fn new_plugin(_moniker: &str) -> Plugin {
-        Plugin::new(Self)
+    pub(crate) fn new_plugin(moniker: &str) -> Plugin {
+        Plugin::new(Self {
+            moniker: String::from(moniker),
+        })
     }
 }
 
@@ -52,10 +58,40 @@ impl PluginOps for GoPlugin {
         Ok(Version::new(s.parse::<GoVersion>()?))
     }
 
-    fn make_env_info(&self, dir: &Path) -> EnvInfo {
+    fn make_env_info(&self, dir: &Path, options: &MakeEnvInfoOptions) -> EnvInfo {
+        let mut path_dirs = Vec::new();
+        let mut vars = vec![
+            (String::from("GOROOT"), dir.to_string_lossy().into_owned()),
+            // Prevent go.mod toolchain directives from downloading a different
+            // toolchain behind isopy's back unless configured otherwise
+            (
+                String::from("GOTOOLCHAIN"),
+                options
+                    .go_toolchain
+                    .clone()
+                    .unwrap_or_else(|| String::from("local")),
+            ),
+        ];
+
+        if let Some(workspace_dir) = &options.workspace_dir {
+            let bin_dir = workspace_dir.join("bin");
+            path_dirs.push(bin_dir.clone());
+            vars.push((
+                String::from("GOPATH"),
+                workspace_dir.to_string_lossy().into_owned(),
+            ));
+            vars.push((
+                String::from("GOBIN"),
+                bin_dir.to_string_lossy().into_owned(),
+            ));
+        }
+
+        path_dirs.push(dir.join("bin"));
         EnvInfo {
-            path_dirs: vec![dir.join("bin")],
-            vars: vec![],
+            path_dirs,
+            vars,
+            unset_vars: Vec::new(),
+            path_vars: Vec::new(),
         }
     }
 
@@ -65,10 +101,80 @@ impl PluginOps for GoPlugin {
         _platform: Platform,
         _shell: Shell,
     ) -> Result<Option<OsString>> {
-        todo!()
+        Ok(None)
     }
 
     fn new_package_manager(&self, ctx: PackageManagerContext) -> PackageManager {
-        PackageManager::new(GoPackageManager::new(ctx, &INDEX_URL))
+        PackageManager::new(GoPackageManager::new(ctx, &self.moniker, &INDEX_URL))
+    }
+
+    fn detect_project_version(&self, dir: &Path) -> Result<Option<ProjectVersion>> {
+        detect_project_version(dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use isopy_lib::MakeEnvInfoOptions;
+
+    use crate::go_plugin::GoPlugin;
+
+    #[test]
+    fn make_env_info_without_workspace() {
+        let plugin = GoPlugin::new_plugin("go");
+        let dir = Path::new("go");
+        let env_info = plugin.make_env_info(dir, &MakeEnvInfoOptions::default());
+        assert_eq!(vec![dir.join("bin")], env_info.path_dirs);
+        assert_eq!(
+            vec![
+                (String::from("GOROOT"), String::from("go")),
+                (String::from("GOTOOLCHAIN"), String::from("local")),
+            ],
+            env_info.vars
+        );
+    }
+
+    #[test]
+    fn make_env_info_go_toolchain() {
+        let plugin = GoPlugin::new_plugin("go");
+        let env_info = plugin.make_env_info(
+            Path::new("go"),
+            &MakeEnvInfoOptions {
+                go_toolchain: Some(String::from("auto")),
+                ..Default::default()
+            },
+        );
+        assert!(
+            env_info
+                .vars
+                .contains(&(String::from("GOTOOLCHAIN"), String::from("auto")))
+        );
+    }
+
+    #[test]
+    fn make_env_info_with_workspace() {
+        let plugin = GoPlugin::new_plugin("go");
+        let dir = Path::new("go");
+        let workspace_dir = PathBuf::from("workspaces").join("go");
+        let bin_dir = workspace_dir.join("bin");
+        let env_info = plugin.make_env_info(
+            dir,
+            &MakeEnvInfoOptions {
+                venv_dir: None,
+                workspace_dir: Some(workspace_dir.clone()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(vec![bin_dir.clone(), dir.join("bin")], env_info.path_dirs);
+        assert!(env_info.vars.contains(&(
+            String::from("GOPATH"),
+            workspace_dir.to_string_lossy().into_owned()
+        )));
+        assert!(env_info.vars.contains(&(
+            String::from("GOBIN"),
+            bin_dir.to_string_lossy().into_owned()
+        )));
     }
 }
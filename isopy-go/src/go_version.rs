@@ -37,6 +37,21 @@ pub struct GoVersion {
     raw: String,
 }
 
+impl GoVersion {
+    pub(crate) fn is_stable(&self) -> bool {
+        self.extra == Extra::Stable
+    }
+
+    // True if this is a stable release from the same release series, e.g.
+    // go1.22.x, no earlier than "minimum"
+    pub(crate) fn is_patch_of(&self, minimum: &Self) -> bool {
+        self.is_stable()
+            && self.major == minimum.major
+            && (minimum.minor.is_none() || self.minor == minimum.minor)
+            && self >= minimum
+    }
+}
+
 impl Display for GoVersion {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(f, "{}", self.raw)
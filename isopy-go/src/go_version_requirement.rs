@@ -0,0 +1,94 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use isopy_lib::{Version, VersionRequirementOps};
+
+use crate::go_version::GoVersion;
+
+// Requirement expressed by the "go" directive of a go.mod file: the latest
+// patch release of the given language version
+pub(crate) struct GoVersionRequirement {
+    minimum: GoVersion,
+}
+
+impl GoVersionRequirement {
+    pub(crate) const fn new(minimum: GoVersion) -> Self {
+        Self { minimum }
+    }
+}
+
+impl Display for GoVersionRequirement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{} or later patch release", self.minimum)
+    }
+}
+
+impl VersionRequirementOps for GoVersionRequirement {
+    fn choose(&self, versions: &[Version]) -> Option<Version> {
+        versions
+            .iter()
+            .filter_map(|v| v.as_any().downcast_ref::<GoVersion>())
+            .filter(|v| v.is_patch_of(&self.minimum))
+            .max()
+            .map(|v| Version::new(v.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use isopy_lib::{Version, VersionRequirementOps};
+    use rstest::rstest;
+
+    use crate::go_version::GoVersion;
+    use crate::go_version_requirement::GoVersionRequirement;
+
+    #[rstest]
+    #[case(Some("go1.22.3"), "go1.22")]
+    #[case(Some("go1.22.3"), "go1.22.1")]
+    #[case(None, "go1.22.4")]
+    #[case(None, "go1.20")]
+    #[case(Some("go1.23.1"), "go1")]
+    fn choose(#[case] expected: Option<&str>, #[case] minimum: &str) -> Result<()> {
+        let versions = [
+            "go1.23.1",
+            "go1.23rc1",
+            "go1.22.3",
+            "go1.22.1",
+            "go1.22.0",
+            "go1.21.5",
+        ]
+        .into_iter()
+        .map(|s| Ok(Version::new(s.parse::<GoVersion>()?)))
+        .collect::<Result<Vec<_>>>()?;
+        let requirement = GoVersionRequirement::new(minimum.parse()?);
+        assert_eq!(
+            expected,
+            requirement
+                .choose(&versions)
+                .map(|v| v.as_str().into_owned())
+                .as_deref()
+        );
+        Ok(())
+    }
+}
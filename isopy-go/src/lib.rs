@@ -21,9 +21,11 @@
 //
 mod api;
 mod entrypoint;
+mod go_mod;
 mod go_package;
 mod go_package_manager;
 mod go_plugin;
 mod go_version;
+mod go_version_requirement;
 
 pub use entrypoint::*;
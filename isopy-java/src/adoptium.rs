@@ -19,7 +19,7 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
-use anyhow::Result;
+use anyhow::{Result, bail};
 use reqwest::Client;
 use url::Url;
 
@@ -44,6 +44,6 @@ impl Adoptium {
             .client
             .get(self.server_url.join("/v3/info/release_versions")?);
         let response = request_builder.send().await?;
-        todo!("response={response:?}");
+        bail!("Unexpected response {response:?}")
     }
 }
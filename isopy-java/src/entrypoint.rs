@@ -19,7 +19,9 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
-use isopy_lib::Plugin;
+use std::sync::Arc;
+
+use isopy_lib::{Plugin, PluginDescriptor, Stability};
 
 use crate::java_plugin::JavaPlugin;
 
@@ -27,3 +29,14 @@ use crate::java_plugin::JavaPlugin;
 pub fn new_plugin(moniker: &str) -> Plugin {
     JavaPlugin::new_plugin(moniker)
 }
+
+#[must_use]
+pub fn descriptor() -> PluginDescriptor {
+    PluginDescriptor {
+        name: String::from("java"),
+        description: String::from("Java development kits from Adoptium"),
+        stability: Stability::Experimental,
+        enable_env_var: Some(String::from("ISOPY_JAVA")),
+        new_plugin: Arc::new(new_plugin),
+    }
+}
@@ -26,8 +26,8 @@ use async_trait::async_trait;
 use isopy_lib::{
     DownloadPackageOptions, GetPackageOptions, InstallPackageOptions, LinkHeader,
     ListPackagesOptions, ListTagsOptions, Package, PackageInfo, PackageManagerContext,
-    PackageManagerOps, ProgressIndicator, ProgressIndicatorOptionsBuilder, SourceFilter, TagFilter,
-    Tags, UpdateIndexOptions, Version,
+    PackageManagerOps, PluginError, ProgressIndicator, ProgressIndicatorOptionsBuilder,
+    SourceFilter, TagFilter, Tags, UpdateIndexOptions, Version,
 };
 use reqwest::Client;
 use tokio::fs::{read, read_dir, write};
@@ -37,13 +37,15 @@ use crate::serialization::versions_response::VersionsResponse;
 
 pub struct JavaPackageManager {
     ctx: PackageManagerContext,
+    moniker: String,
     url: Url,
 }
 
 impl JavaPackageManager {
-    pub(crate) fn new(ctx: PackageManagerContext, url: &Url) -> Self {
+    pub(crate) fn new(ctx: PackageManagerContext, moniker: &str, url: &Url) -> Self {
         Self {
             ctx,
+            moniker: String::from(moniker),
             url: url.clone(),
         }
     }
@@ -85,7 +87,8 @@ impl JavaPackageManager {
             .build()?;
 
         let progress_indicator = ProgressIndicator::new(&options)?;
-        for i in 0.. {
+        let mut i = 0;
+        loop {
             progress_indicator.set_message(format!("Downloading page {i}"));
 
             let response = request_builder.send().await?.error_for_status()?;
@@ -98,8 +101,8 @@ impl JavaPackageManager {
             };
 
             request_builder = client.get(next_url);
+            i += 1;
         }
-        unreachable!()
     }
 }
 
@@ -111,7 +114,7 @@ impl PackageManagerOps for JavaPackageManager {
     }
 
     async fn list_tags(&self, _options: &ListTagsOptions) -> Result<Tags> {
-        todo!()
+        Err(PluginError::unsupported(&self.moniker, "list tags").into())
     }
 
     async fn list_packages(
@@ -121,17 +124,13 @@ impl PackageManagerOps for JavaPackageManager {
         options: &ListPackagesOptions,
     ) -> Result<Vec<PackageInfo>> {
         let dir = self.get_index(options.show_progress, false).await?;
+        let url = Url::parse("https://httpbin.org")?;
         let mut packages = Vec::new();
         for path in Self::get_page_paths(&dir).await? {
             let bytes = read(&path).await?;
             let response = serde_json::from_slice::<VersionsResponse>(&bytes)?;
             packages.extend(response.versions.into_iter().map(|v| {
-                PackageInfo::new(
-                    v.semver.clone(),
-                    &Url::parse("https://httpbin.org").unwrap(),
-                    Version::new(v),
-                    None::<PathBuf>,
-                )
+                PackageInfo::new(v.semver.clone(), &url, Version::new(v), None::<PathBuf>)
             }));
         }
         Ok(packages)
@@ -143,7 +142,7 @@ impl PackageManagerOps for JavaPackageManager {
         _tags: &TagFilter,
         _options: &GetPackageOptions,
     ) -> Result<Option<PackageInfo>> {
-        todo!()
+        Err(PluginError::unsupported(&self.moniker, "get package").into())
     }
 
     async fn download_package(
@@ -152,7 +151,7 @@ impl PackageManagerOps for JavaPackageManager {
         _tags: &TagFilter,
         _options: &DownloadPackageOptions,
     ) -> Result<()> {
-        todo!()
+        Err(PluginError::unsupported(&self.moniker, "download package").into())
     }
 
     async fn install_package(
@@ -162,6 +161,6 @@ impl PackageManagerOps for JavaPackageManager {
         _dir: &Path,
         _options: &InstallPackageOptions,
     ) -> Result<Package> {
-        todo!()
+        Err(PluginError::unsupported(&self.moniker, "install package").into())
     }
 }
@@ -25,12 +25,14 @@ use std::sync::LazyLock;
 
 use anyhow::Result;
 use isopy_lib::{
-    EnvInfo, PackageManager, PackageManagerContext, Platform, Plugin, PluginOps, Shell, Version,
+    EnvInfo, MakeEnvInfoOptions, PackageManager, PackageManagerContext, Platform, Plugin,
+    PluginOps, ProjectVersion, Shell, Version,
 };
 use url::Url;
 
 use crate::java_package_manager::JavaPackageManager;
 use crate::java_version::JavaVersion;
+use crate::project_version::detect_project_version;
 
 static INDEX_URL: LazyLock<Url> = LazyLock::new(|| {
     "https://api.adoptium.net/"
@@ -38,11 +40,15 @@ static INDEX_URL: LazyLock<Url> = LazyLock::new(|| {
         .expect("Invalid index URL")
 });
 
-pub struct JavaPlugin;
+pub struct JavaPlugin {
+    moniker: String,
+}
 
 impl JavaPlugin {
-    pub(crate) fn new_plugin(_moniker: &str) -> Plugin {
-        Plugin::new(Self)
+    pub(crate) fn new_plugin(moniker: &str) -> Plugin {
+        Plugin::new(Self {
+            moniker: String::from(moniker),
+        })
     }
 }
 
@@ -55,10 +61,12 @@ impl PluginOps for JavaPlugin {
         Ok(Version::new(s.parse::<JavaVersion>()?))
     }
 
-    fn make_env_info(&self, dir: &Path) -> EnvInfo {
+    fn make_env_info(&self, dir: &Path, _options: &MakeEnvInfoOptions) -> EnvInfo {
         EnvInfo {
             path_dirs: vec![dir.join("bin")],
             vars: vec![],
+            unset_vars: vec![],
+            path_vars: vec![],
         }
     }
 
@@ -68,10 +76,14 @@ impl PluginOps for JavaPlugin {
         _platform: Platform,
         _shell: Shell,
     ) -> Result<Option<OsString>> {
-        todo!()
+        Ok(None)
     }
 
     fn new_package_manager(&self, ctx: PackageManagerContext) -> PackageManager {
-        PackageManager::new(JavaPackageManager::new(ctx, &INDEX_URL))
+        PackageManager::new(JavaPackageManager::new(ctx, &self.moniker, &INDEX_URL))
+    }
+
+    fn detect_project_version(&self, dir: &Path) -> Result<Option<ProjectVersion>> {
+        detect_project_version(dir)
     }
 }
@@ -0,0 +1,123 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use isopy_lib::{Version, VersionRequirementOps};
+
+use crate::java_version::JavaVersion;
+use crate::maven_version::MavenVersion;
+use crate::maven_version_range::MavenVersionRange;
+use crate::serialization::version::Version as AdoptiumVersion;
+
+// Chooses the latest general availability release from the Adoptium index
+// within a version range
+pub(crate) struct JavaVersionRequirement {
+    range: MavenVersionRange,
+}
+
+impl JavaVersionRequirement {
+    pub(crate) const fn new(range: MavenVersionRange) -> Self {
+        Self { range }
+    }
+}
+
+impl Display for JavaVersionRequirement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.range)
+    }
+}
+
+impl VersionRequirementOps for JavaVersionRequirement {
+    fn choose(&self, versions: &[Version]) -> Option<Version> {
+        let version = versions
+            .iter()
+            .filter_map(|v| v.as_any().downcast_ref::<AdoptiumVersion>())
+            .filter(|v| {
+                v.pre.is_none()
+                    && self
+                        .range
+                        .contains(&MavenVersion::new(v.major, Some(v.minor)))
+            })
+            .max_by_key(|v| (v.major, v.minor, v.security, v.build))?;
+
+        // Versions are identified by isopy in the same form as the
+        // "release_name" of Adoptium releases without the build number
+        format!(
+            "jdk-{}.{}.{}",
+            version.major, version.minor, version.security
+        )
+        .parse::<JavaVersion>()
+        .ok()
+        .map(Version::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use isopy_lib::{Version, VersionRequirementOps};
+    use rstest::rstest;
+
+    use crate::java_version_requirement::JavaVersionRequirement;
+    use crate::maven_version_range::MavenVersionRange;
+    use crate::serialization::version::Version as AdoptiumVersion;
+
+    fn adoptium_version(
+        major: u32,
+        minor: u32,
+        security: u32,
+        build: u32,
+        pre: Option<&str>,
+    ) -> Version {
+        Version::new(AdoptiumVersion {
+            major,
+            minor,
+            build,
+            openjdk_version: format!("{major}.{minor}.{security}+{build}"),
+            optional: None,
+            pre: pre.map(String::from),
+            security,
+            semver: format!("{major}.{minor}.{security}+{build}"),
+        })
+    }
+
+    #[rstest]
+    #[case(Some("jdk-21.0.2"), 21)]
+    #[case(Some("jdk-17.0.10"), 17)]
+    #[case(None, 11)]
+    fn choose(#[case] expected: Option<&str>, #[case] major: u32) {
+        let versions = [
+            adoptium_version(22, 0, 0, 36, Some("ea")),
+            adoptium_version(21, 0, 3, 1, Some("ea")),
+            adoptium_version(21, 0, 2, 13, None),
+            adoptium_version(21, 0, 1, 12, None),
+            adoptium_version(17, 0, 10, 7, None),
+        ];
+        let requirement = JavaVersionRequirement::new(MavenVersionRange::feature(major));
+        assert_eq!(
+            expected,
+            requirement
+                .choose(&versions)
+                .map(|v| v.as_str().into_owned())
+                .as_deref()
+        );
+    }
+}
@@ -25,9 +25,11 @@ mod java_package;
 mod java_package_manager;
 mod java_plugin;
 mod java_version;
+mod java_version_requirement;
 mod maven_version;
 mod maven_version_limit;
 mod maven_version_range;
+mod project_version;
 mod serialization;
 
 pub use entrypoint::*;
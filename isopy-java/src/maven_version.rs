@@ -19,22 +19,45 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 
-#[allow(unused)]
+#[derive(Clone, Debug)]
 pub struct MavenVersion {
     major: u32,
     minor: Option<u32>,
 }
 
 impl MavenVersion {
-    #[allow(unused)]
     #[must_use]
     pub(crate) const fn new(major: u32, minor: Option<u32>) -> Self {
         Self { major, minor }
     }
 }
 
+// Missing components compare as zero so that "22" and "22.0" are equal
+impl Ord for MavenVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then_with(|| self.minor.unwrap_or(0).cmp(&other.minor.unwrap_or(0)))
+    }
+}
+
+impl PartialOrd for MavenVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for MavenVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MavenVersion {}
+
 impl Display for MavenVersion {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         if let Some(minor) = self.minor {
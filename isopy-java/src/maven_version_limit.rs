@@ -21,7 +21,7 @@
 //
 use crate::maven_version::MavenVersion;
 
-#[allow(unused)]
+#[derive(Debug)]
 pub enum MavenVersionLimit {
     Open(Option<MavenVersion>),
     Closed(Option<MavenVersion>),
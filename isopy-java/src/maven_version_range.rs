@@ -28,7 +28,7 @@ use crate::maven_version_limit::MavenVersionLimit;
 
 const MAVEN_VERSION: &AsciiSet = &CONTROLS.add(b'(').add(b')').add(b',').add(b'[').add(b']');
 
-#[allow(unused)]
+#[derive(Debug)]
 pub enum MavenVersionRange {
     #[allow(unused)]
     OpenJdkVersion(String),
@@ -38,11 +38,44 @@ pub enum MavenVersionRange {
 }
 
 impl MavenVersionRange {
+    // Range of releases of a Java feature version, e.g. "[21,22)"
+    #[must_use]
+    pub(crate) const fn feature(major: u32) -> Self {
+        Self::VersionRange(
+            MavenVersionLimit::Closed(Some(MavenVersion::new(major, None))),
+            MavenVersionLimit::Open(Some(MavenVersion::new(major + 1, None))),
+        )
+    }
+
     #[allow(unused)]
     #[must_use]
     pub(crate) fn to_path_segment(&self) -> String {
         utf8_percent_encode(&self.to_string(), MAVEN_VERSION).to_string()
     }
+
+    // OpenJDK version strings are only meaningful to the Adoptium API so
+    // never match here
+    pub(crate) fn contains(&self, version: &MavenVersion) -> bool {
+        use crate::maven_version_limit::MavenVersionLimit::{Closed, Open};
+
+        match self {
+            Self::OpenJdkVersion(_) => false,
+            Self::Version(value) => version == value,
+            Self::VersionRange(lower, upper) => {
+                let above_lower = match lower {
+                    Open(None) | Closed(None) => true,
+                    Open(Some(value)) => version > value,
+                    Closed(Some(value)) => version >= value,
+                };
+                let below_upper = match upper {
+                    Open(None) | Closed(None) => true,
+                    Open(Some(value)) => version < value,
+                    Closed(Some(value)) => version <= value,
+                };
+                above_lower && below_upper
+            }
+        }
+    }
 }
 
 impl Display for MavenVersionRange {
@@ -130,4 +163,33 @@ mod tests {
         assert_eq!(expected_str, version.to_string());
         assert_eq!(expected_path_segment, version.to_path_segment());
     }
+
+    #[rstest]
+    #[case(true, 21, Some(0))]
+    #[case(true, 21, None)]
+    #[case(true, 21, Some(5))]
+    #[case(false, 20, Some(9))]
+    #[case(false, 22, Some(0))]
+    fn contains_feature(#[case] expected: bool, #[case] major: u32, #[case] minor: Option<u32>) {
+        assert_eq!(
+            expected,
+            MavenVersionRange::feature(21).contains(&MavenVersion::new(major, minor))
+        );
+        assert_eq!("[21,22)", MavenVersionRange::feature(21).to_string());
+    }
+
+    #[rstest]
+    #[case(
+        true,
+        VersionRange(Open(None), Closed(Some(MavenVersion::new(17, None))))
+    )]
+    #[case(
+        false,
+        VersionRange(Open(Some(MavenVersion::new(17, Some(0)))), Open(None))
+    )]
+    #[case(true, Version(MavenVersion::new(17, Some(0))))]
+    #[case(false, OpenJdkVersion(String::from("17")))]
+    fn contains(#[case] expected: bool, #[case] range: MavenVersionRange) {
+        assert_eq!(expected, range.contains(&MavenVersion::new(17, None)));
+    }
 }
@@ -0,0 +1,254 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fs::read_to_string;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result, bail};
+use isopy_lib::{ProjectVersion, ProjectVersionSpec, Version, VersionRequirement};
+use regex::Regex;
+
+use crate::java_version::JavaVersion;
+use crate::java_version_requirement::JavaVersionRequirement;
+use crate::maven_version_range::MavenVersionRange;
+
+const JAVA_VERSION_FILE_NAME: &str = ".java-version";
+const SDKMANRC_FILE_NAME: &str = ".sdkmanrc";
+const POM_FILE_NAME: &str = "pom.xml";
+const GRADLE_KOTLIN_FILE_NAME: &str = "build.gradle.kts";
+const GRADLE_GROOVY_FILE_NAME: &str = "build.gradle";
+
+// Matches e.g. "languageVersion = JavaLanguageVersion.of(21)" and
+// "languageVersion.set(JavaLanguageVersion.of(21))"
+static GRADLE_LANGUAGE_VERSION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"languageVersion\s*(?:=|\.set\()\s*JavaLanguageVersion\.of\(\s*"?(\d+)"?\s*\)"#)
+        .expect("Invalid regex")
+});
+
+static POM_PROPERTY_REFERENCE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\$\{([A-Za-z0-9_.-]+)\}$").expect("Invalid regex"));
+
+// Extracts the version string from the contents of a source file
+type ReadVersion = fn(&str) -> Option<&str>;
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match read_to_string(path) {
+        Ok(s) => Ok(Some(s)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// Full versions such as "21.0.2" name an exact release while feature
+// versions such as "21", "21.0" or legacy "1.8" select the latest release
+fn parse_java_version(s: &str) -> Result<ProjectVersionSpec> {
+    let parts = s
+        .split('.')
+        .map(str::parse::<u32>)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Unsupported Java version {s}"))?;
+    Ok(match parts.as_slice() {
+        [1, major] | [major] | [major, 0] => {
+            ProjectVersionSpec::Requirement(VersionRequirement::new(JavaVersionRequirement::new(
+                MavenVersionRange::feature(*major),
+            )))
+        }
+        [major, minor, security] => ProjectVersionSpec::Exact(Version::new(
+            format!("jdk-{major}.{minor}.{security}").parse::<JavaVersion>()?,
+        )),
+        _ => bail!("Unsupported Java version {s}"),
+    })
+}
+
+// jenv may prefix the version with a vendor, e.g. "temurin64-21.0.2"
+fn read_java_version_file(s: &str) -> Option<&str> {
+    let line = s.lines().map(str::trim).find(|line| !line.is_empty())?;
+    Some(line.rsplit_once('-').map_or(line, |(_, version)| version))
+}
+
+// SDKMAN! identifiers include a vendor suffix, e.g. "java=21.0.2-tem"
+fn read_sdkmanrc(s: &str) -> Option<&str> {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .find_map(|line| line.strip_prefix("java="))
+        .map(|value| {
+            value
+                .trim()
+                .split_once('-')
+                .map_or(value, |(version, _)| version)
+        })
+}
+
+fn find_pom_element<'a>(s: &'a str, name: &str) -> Option<&'a str> {
+    let start_tag = format!("<{name}>");
+    let end_tag = format!("</{name}>");
+    let start = s.find(&start_tag)? + start_tag.len();
+    let end = s[start..].find(&end_tag)? + start;
+    Some(s[start..end].trim())
+}
+
+// The release is usually a literal but may refer to another property, e.g.
+// "${java.version}"
+fn read_pom(s: &str) -> Option<&str> {
+    let value = find_pom_element(s, "maven.compiler.release")?;
+    match POM_PROPERTY_REFERENCE_REGEX.captures(value) {
+        Some(captures) => find_pom_element(s, captures.get(1)?.as_str()),
+        None => Some(value),
+    }
+}
+
+fn read_gradle(s: &str) -> Option<&str> {
+    GRADLE_LANGUAGE_VERSION_REGEX
+        .captures(s)
+        .and_then(|captures| captures.get(1))
+        .map(|m| m.as_str())
+}
+
+// Sources are tried in order of precedence until one yields a version
+pub(crate) fn detect_project_version(dir: &Path) -> Result<Option<ProjectVersion>> {
+    let sources: [(&str, ReadVersion); 5] = [
+        (JAVA_VERSION_FILE_NAME, read_java_version_file),
+        (SDKMANRC_FILE_NAME, read_sdkmanrc),
+        (POM_FILE_NAME, read_pom),
+        (GRADLE_KOTLIN_FILE_NAME, read_gradle),
+        (GRADLE_GROOVY_FILE_NAME, read_gradle),
+    ];
+
+    for (file_name, read) in sources {
+        let path = dir.join(file_name);
+        let Some(s) = read_optional(&path)? else {
+            continue;
+        };
+
+        let Some(version) = read(&s) else {
+            continue;
+        };
+
+        let spec = parse_java_version(version)
+            .with_context(|| format!("Failed to read Java version from {}", path.display()))?;
+        return Ok(Some(ProjectVersion { path, spec }));
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::write;
+
+    use anyhow::Result;
+    use isopy_lib::ProjectVersionSpec;
+    use rstest::rstest;
+    use tempfile::tempdir;
+
+    use crate::project_version::{
+        detect_project_version, parse_java_version, read_gradle, read_java_version_file, read_pom,
+        read_sdkmanrc,
+    };
+
+    const POM: &str = r"<project>
+  <properties>
+    <java.version>17</java.version>
+    <maven.compiler.release>${java.version}</maven.compiler.release>
+  </properties>
+</project>
+";
+
+    #[rstest]
+    #[case(Some("[21,22)"), "21")]
+    #[case(Some("[21,22)"), "21.0")]
+    #[case(Some("[8,9)"), "1.8")]
+    #[case(Some("exact jdk-21.0.2"), "21.0.2")]
+    #[case(None, "21.0.2.1")]
+    #[case(None, "temurin")]
+    fn parse(#[case] expected: Option<&str>, #[case] input: &str) {
+        let spec = parse_java_version(input).ok().map(|spec| match spec {
+            ProjectVersionSpec::Exact(version) => format!("exact {}", version.as_str()),
+            ProjectVersionSpec::Requirement(requirement) => requirement.to_string(),
+        });
+        assert_eq!(expected, spec.as_deref());
+    }
+
+    #[rstest]
+    #[case(Some("21.0.2"), "21.0.2\n")]
+    #[case(Some("21.0.2"), "temurin64-21.0.2\n")]
+    #[case(Some("17"), "\n17\n")]
+    #[case(None, "")]
+    fn java_version_file(#[case] expected: Option<&str>, #[case] input: &str) {
+        assert_eq!(expected, read_java_version_file(input));
+    }
+
+    #[rstest]
+    #[case(Some("21.0.2"), "# comment\njava=21.0.2-tem\n")]
+    #[case(Some("17"), "java=17\n")]
+    #[case(None, "maven=3.9.6\n")]
+    fn sdkmanrc(#[case] expected: Option<&str>, #[case] input: &str) {
+        assert_eq!(expected, read_sdkmanrc(input));
+    }
+
+    #[rstest]
+    #[case(Some("17"), POM)]
+    #[case(
+        Some("21"),
+        "<properties><maven.compiler.release> 21 </maven.compiler.release></properties>"
+    )]
+    #[case(None, "<project></project>")]
+    fn pom(#[case] expected: Option<&str>, #[case] input: &str) {
+        assert_eq!(expected, read_pom(input));
+    }
+
+    #[rstest]
+    #[case(
+        Some("21"),
+        "java {\n    toolchain {\n        languageVersion = JavaLanguageVersion.of(21)\n    }\n}\n"
+    )]
+    #[case(
+        Some("17"),
+        "java.toolchain.languageVersion.set(JavaLanguageVersion.of(\"17\"))\n"
+    )]
+    #[case(None, "plugins { id 'java' }\n")]
+    fn gradle(#[case] expected: Option<&str>, #[case] input: &str) {
+        assert_eq!(expected, read_gradle(input));
+    }
+
+    #[test]
+    fn detect() -> Result<()> {
+        let dir = tempdir()?;
+        assert!(detect_project_version(dir.path())?.is_none());
+
+        write(dir.path().join("pom.xml"), POM)?;
+        let project_version = detect_project_version(dir.path())?.expect("must detect version");
+        assert_eq!(dir.path().join("pom.xml"), project_version.path);
+        assert_eq!("[17,18)", project_version.spec.to_string());
+
+        write(dir.path().join(".sdkmanrc"), "java=21.0.2-tem\n")?;
+        let project_version = detect_project_version(dir.path())?.expect("must detect version");
+        assert_eq!(dir.path().join(".sdkmanrc"), project_version.path);
+        assert_eq!("jdk-21.0.2", project_version.spec.to_string());
+
+        write(dir.path().join(".java-version"), "jdk\n")?;
+        assert!(detect_project_version(dir.path()).is_err());
+        Ok(())
+    }
+}
@@ -19,28 +19,42 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
-use std::fs::{File, create_dir_all};
-use std::io::{Read, copy};
+use std::borrow::Cow;
+use std::fs::{File, canonicalize, create_dir_all, hard_link, remove_file, symlink_metadata};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{Read, Write, copy};
+use std::num::NonZeroUsize;
 use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::sync_channel;
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::thread::{self, available_parallelism};
+use std::time::{Duration, UNIX_EPOCH};
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, anyhow, bail};
+use bzip2::read::BzDecoder;
 use flate2::read::GzDecoder;
 use log::info;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
-use tar::Archive;
+use tar::{Archive, Entry};
+use xz2::read::XzDecoder;
 use zip::ZipArchive;
 use zstd::stream::read::Decoder as ZstdDecoder;
 
 use crate::extent::Extent;
-use crate::package_manager::InstallPackageOptions;
 use crate::progress_indicator::{ProgressIndicator, ProgressIndicatorOptionsBuilder};
+use crate::unpack_options::{StripComponents, UnpackOptions};
 
-#[derive(Clone, Copy, Debug, EnumIter)]
+#[derive(Clone, Copy, Debug, EnumIter, PartialEq)]
 pub enum ArchiveType {
+    TarBz2,
     TarGz,
+    TarXz,
     TarZst,
+    Tgz,
     Zip,
+    SevenZip,
+    Pkg,
 }
 
 impl ArchiveType {
@@ -57,22 +71,40 @@ impl ArchiveType {
     #[must_use]
     pub const fn suffix(&self) -> &str {
         match self {
+            Self::TarBz2 => ".tar.bz2",
             Self::TarGz => ".tar.gz",
+            Self::TarXz => ".tar.xz",
             Self::TarZst => ".tar.zst",
+            Self::Tgz => ".tgz",
             Self::Zip => ".zip",
+            Self::SevenZip => ".7z",
+            Self::Pkg => ".pkg",
         }
     }
 
+    #[must_use]
+    pub const fn is_supported(&self) -> bool {
+        !matches!(self, Self::SevenZip | Self::Pkg)
+    }
+
     pub async fn unpack(
         &self,
         archive_path: &Path,
         dir: &Path,
-        options: &InstallPackageOptions,
+        options: &UnpackOptions,
     ) -> Result<()> {
         if dir.exists() {
             bail!("Output directory {} already exists", dir.display())
         }
 
+        if !self.is_supported() {
+            bail!(
+                "Cannot unpack {}: {} archives are not supported yet",
+                archive_path.display(),
+                self.suffix()
+            )
+        }
+
         let progress_indicator = ProgressIndicator::new(
             &ProgressIndicatorOptionsBuilder::default()
                 .enabled(options.show_progress)
@@ -85,18 +117,25 @@ impl ArchiveType {
         let archive_type = *self;
         let archive_path = archive_path.to_path_buf();
         let dir = dir.to_path_buf();
+        let options = options.clone();
         let progress = progress_indicator.clone();
         tokio::task::spawn_blocking(move || -> Result<()> {
+            let open = || File::open(&archive_path);
             match archive_type {
-                Self::TarGz => {
-                    let file = File::open(&archive_path)?;
-                    unpack_tar(GzDecoder::new(file), &dir, &progress)?;
+                Self::TarBz2 => {
+                    unpack_tar(|| Ok(BzDecoder::new(open()?)), &dir, &options, &progress)?;
+                }
+                Self::TarGz | Self::Tgz => {
+                    unpack_tar(|| Ok(GzDecoder::new(open()?)), &dir, &options, &progress)?;
+                }
+                Self::TarXz => {
+                    unpack_tar(|| Ok(XzDecoder::new(open()?)), &dir, &options, &progress)?;
                 }
                 Self::TarZst => {
-                    let file = File::open(&archive_path)?;
-                    unpack_tar(ZstdDecoder::new(file)?, &dir, &progress)?;
+                    unpack_tar(|| Ok(ZstdDecoder::new(open()?)?), &dir, &options, &progress)?;
                 }
-                Self::Zip => unpack_zip(&archive_path, &dir, &progress)?,
+                Self::Zip => unpack_zip(&archive_path, &dir, &options, &progress)?,
+                Self::SevenZip | Self::Pkg => unreachable!(),
             }
             info!("Unpacked package to {}", dir.display());
             Ok(())
@@ -108,75 +147,511 @@ impl ArchiveType {
     }
 }
 
-fn unpack_tar<R: Read>(reader: R, dir: &Path, progress: &ProgressIndicator) -> Result<()> {
-    let mut archive = Archive::new(reader);
+// Files larger than this are written by the thread reading the tar stream
+// rather than being buffered in memory and handed to a writer thread
+const MAX_QUEUED_FILE_SIZE: u64 = 4 * 1024 * 1024;
+
+const QUEUE_DEPTH: usize = 64;
+
+// Upper bound on file data buffered across all writer threads at any one
+// time, regardless of the number of threads
+const MAX_QUEUED_BYTES: u64 = 64 * 1024 * 1024;
+
+struct TarTarget<'a> {
+    dir: &'a Path,
+    root: PathBuf,
+    strip: usize,
+    subdir: Option<&'a Path>,
+    progress: &'a ProgressIndicator,
+}
+
+impl TarTarget<'_> {
+    // Returns the relative path, output path and canonical parent directory
+    // of the entry or None if the entry is not selected
+    fn prepare<R: Read>(
+        &self,
+        entry: &Entry<'_, R>,
+    ) -> Result<Option<(PathBuf, PathBuf, PathBuf)>> {
+        let path = entry.path()?;
+        let Some(relative) = select_path(&path, self.strip, self.subdir) else {
+            return Ok(None);
+        };
+
+        let out_path = self.dir.join(&relative);
+        reject_traversal(self.dir, &out_path)?;
+
+        self.progress
+            .set_message(format!("Unpacking {}", relative.display()));
+
+        // Resolve the parent on disk so that we never write through a link
+        // planted by an earlier entry
+        let parent = match out_path.parent() {
+            Some(parent) => {
+                create_dir_all(parent)?;
+                canonicalize(parent)?
+            }
+            None => self.root.clone(),
+        };
+        if !parent.starts_with(&self.root) {
+            bail!(
+                "Refusing to extract path outside target directory: {}",
+                relative.display()
+            );
+        }
+
+        Ok(Some((relative, out_path, parent)))
+    }
+
+    fn unpack_symlink<R: Read>(
+        &self,
+        entry: &mut Entry<'_, R>,
+        relative: &Path,
+        out_path: &Path,
+        parent: &Path,
+    ) -> Result<()> {
+        let Some(target) = entry.link_name()?.map(Cow::into_owned) else {
+            bail!("Symbolic link {} has no target", relative.display());
+        };
+        reject_link_target(&self.root, parent, &target, relative)?;
+        entry.unpack(out_path)?;
+        Ok(())
+    }
+
+    fn unpack_hard_link(&self, relative: &Path, out_path: &Path, target: &Path) -> Result<()> {
+        let resolved = resolve_hard_link_target(
+            self.dir,
+            &self.root,
+            target,
+            relative,
+            self.strip,
+            self.subdir,
+        )?;
+        if symlink_metadata(out_path).is_ok() {
+            remove_file(out_path)?;
+        }
+        hard_link(&resolved, out_path)?;
+        Ok(())
+    }
+
+    // Targets are checked lexically as each link is created, but a chain of
+    // links can still resolve elsewhere once the whole tree is on disk
+    fn check_symlinks(&self, symlinks: &[PathBuf]) -> Result<()> {
+        for symlink in symlinks {
+            if let Ok(resolved) = canonicalize(symlink)
+                && !resolved.starts_with(&self.root)
+            {
+                bail!(
+                    "Refusing to extract symbolic link {} resolving outside target directory",
+                    symlink.display()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+struct ByteBudget {
+    available: Mutex<u64>,
+    released: Condvar,
+}
+
+impl ByteBudget {
+    const fn new(bytes: u64) -> Self {
+        Self {
+            available: Mutex::new(bytes),
+            released: Condvar::new(),
+        }
+    }
+
+    // Blocks until the requested number of bytes is free: callers must never
+    // ask for more than the total budget
+    fn acquire(&self, bytes: u64) -> BytePermit<'_> {
+        let mut available = self
+            .available
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        while *available < bytes {
+            available = self
+                .released
+                .wait(available)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        *available -= bytes;
+        drop(available);
+        BytePermit {
+            budget: self,
+            bytes,
+        }
+    }
+}
+
+// Returns its bytes to the budget when dropped, including when a queued file
+// is discarded because its worker has failed
+struct BytePermit<'a> {
+    budget: &'a ByteBudget,
+    bytes: u64,
+}
+
+impl Drop for BytePermit<'_> {
+    fn drop(&mut self) {
+        *self
+            .budget
+            .available
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) += self.bytes;
+        self.budget.released.notify_all();
+    }
+}
+
+struct QueuedFile<'a> {
+    out_path: PathBuf,
+    data: Vec<u8>,
+    mode: u32,
+    mtime: u64,
+    _permit: BytePermit<'a>,
+}
+
+impl QueuedFile<'_> {
+    fn write(&self) -> Result<()> {
+        // Replace rather than write through anything already at this path
+        if symlink_metadata(&self.out_path).is_ok() {
+            remove_file(&self.out_path)?;
+        }
+
+        let mut file = File::create(&self.out_path)?;
+        file.write_all(&self.data)?;
+        file.set_modified(UNIX_EPOCH + Duration::from_secs(self.mtime))?;
+
+        #[cfg(unix)]
+        {
+            use std::fs::{Permissions, set_permissions};
+            use std::os::unix::fs::PermissionsExt;
+            set_permissions(&self.out_path, Permissions::from_mode(self.mode & 0o7777))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn unpack_tar<R: Read>(
+    open: impl Fn() -> Result<R>,
+    dir: &Path,
+    options: &UnpackOptions,
+    progress: &ProgressIndicator,
+) -> Result<()> {
+    let strip = match options.strip_components {
+        // A tar stream can't be rewound, so detecting the common root costs a
+        // full extra decompression pass over the archive
+        StripComponents::Auto => {
+            let mut archive = Archive::new(open()?);
+            let mut entries = Vec::new();
+            for entry in archive.entries()? {
+                let entry = entry?;
+                entries.push((
+                    entry.path()?.into_owned(),
+                    entry.header().entry_type().is_dir(),
+                ));
+            }
+            detect_common_root(&entries)
+        }
+        StripComponents::Count(n) => n,
+    };
+
+    let mut archive = Archive::new(open()?);
     archive.set_preserve_permissions(true);
     archive.set_preserve_mtime(true);
     archive.set_overwrite(true);
 
+    create_dir_all(dir)?;
+    let target = TarTarget {
+        dir,
+        root: canonicalize(dir)?,
+        strip,
+        subdir: options.subdir.as_deref(),
+        progress,
+    };
+
+    let threads = thread_count(options);
+    let symlinks = if threads > 1 {
+        unpack_tar_entries_parallel(&mut archive, &target, threads)?
+    } else {
+        unpack_tar_entries(&mut archive, &target)?
+    };
+
+    target.check_symlinks(&symlinks)
+}
+
+fn unpack_tar_entries<R: Read>(
+    archive: &mut Archive<R>,
+    target: &TarTarget<'_>,
+) -> Result<Vec<PathBuf>> {
+    let mut symlinks = Vec::new();
     for entry in archive.entries()? {
         let mut entry = entry?;
-        let path = entry.path()?.into_owned();
-        let Some(stripped) = strip_one_component(&path) else {
+        let Some((relative, out_path, parent)) = target.prepare(&entry)? else {
             continue;
         };
 
-        let out_path = dir.join(&stripped);
-        reject_traversal(dir, &out_path)?;
-
-        progress.set_message(format!("Unpacking {}", stripped.display()));
-        if let Some(parent) = out_path.parent() {
-            create_dir_all(parent)?;
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_symlink() {
+            target.unpack_symlink(&mut entry, &relative, &out_path, &parent)?;
+            symlinks.push(out_path);
+        } else if entry_type.is_hard_link() {
+            let Some(link_target) = entry.link_name()?.map(Cow::into_owned) else {
+                bail!("Hard link {} has no target", relative.display());
+            };
+            target.unpack_hard_link(&relative, &out_path, &link_target)?;
+        } else {
+            entry.unpack(&out_path)?;
         }
-        entry.unpack(&out_path)?;
     }
 
-    Ok(())
+    Ok(symlinks)
+}
+
+// Reading a tar stream is inherently sequential, so this thread decompresses
+// entries, creates directories and links, and hands regular files off to a
+// bounded pool of writer threads
+fn unpack_tar_entries_parallel<R: Read>(
+    archive: &mut Archive<R>,
+    target: &TarTarget<'_>,
+    threads: usize,
+) -> Result<Vec<PathBuf>> {
+    let budget = ByteBudget::new(MAX_QUEUED_BYTES);
+    thread::scope(|scope| {
+        let mut senders = Vec::with_capacity(threads);
+        let mut workers = Vec::with_capacity(threads);
+        for _ in 0..threads {
+            let (sender, receiver) = sync_channel::<QueuedFile>(QUEUE_DEPTH);
+            senders.push(sender);
+            workers.push(scope.spawn(move || -> Result<()> {
+                for file in receiver {
+                    file.write()?;
+                }
+                Ok(())
+            }));
+        }
+
+        let mut symlinks = Vec::new();
+        let mut hard_links = Vec::new();
+        let result = (|| -> Result<()> {
+            for entry in archive.entries()? {
+                let mut entry = entry?;
+                let Some((relative, out_path, parent)) = target.prepare(&entry)? else {
+                    continue;
+                };
+
+                let entry_type = entry.header().entry_type();
+                if entry_type.is_symlink() {
+                    target.unpack_symlink(&mut entry, &relative, &out_path, &parent)?;
+                    symlinks.push(out_path);
+                } else if entry_type.is_hard_link() {
+                    // Link targets may still be queued, so defer these until
+                    // all writer threads have finished
+                    let Some(link_target) = entry.link_name()?.map(Cow::into_owned) else {
+                        bail!("Hard link {} has no target", relative.display());
+                    };
+                    hard_links.push((relative, out_path, link_target));
+                } else if entry_type.is_file() && entry.size() <= MAX_QUEUED_FILE_SIZE {
+                    let permit = budget.acquire(entry.size());
+                    let mut data = Vec::with_capacity(usize::try_from(entry.size())?);
+                    entry.read_to_end(&mut data)?;
+                    let file = QueuedFile {
+                        out_path,
+                        data,
+                        mode: entry.header().mode()?,
+                        mtime: entry.header().mtime()?,
+                        _permit: permit,
+                    };
+
+                    // Always send a given path to the same thread so that
+                    // duplicate entries are still written in archive order
+                    let sender = &senders[worker_index(&file.out_path, threads)];
+                    if sender.send(file).is_err() {
+                        // The worker has already failed: its error is
+                        // reported below
+                        break;
+                    }
+                } else {
+                    entry.unpack(&out_path)?;
+                }
+            }
+            Ok(())
+        })();
+
+        drop(senders);
+        for worker in workers {
+            worker
+                .join()
+                .map_err(|_| anyhow!("Extraction thread panicked"))??;
+        }
+        result?;
+
+        for (relative, out_path, link_target) in hard_links {
+            target.unpack_hard_link(&relative, &out_path, &link_target)?;
+        }
+
+        Ok(symlinks)
+    })
 }
 
-fn unpack_zip(archive_path: &Path, dir: &Path, progress: &ProgressIndicator) -> Result<()> {
+fn unpack_zip(
+    archive_path: &Path,
+    dir: &Path,
+    options: &UnpackOptions,
+    progress: &ProgressIndicator,
+) -> Result<()> {
     let file = File::open(archive_path)?;
     let mut archive = ZipArchive::new(file)?;
 
+    let strip = match options.strip_components {
+        StripComponents::Auto => {
+            let mut entries = Vec::new();
+            for i in 0..archive.len() {
+                let entry = archive.by_index(i)?;
+                if let Some(enclosed) = entry.enclosed_name() {
+                    entries.push((enclosed.to_path_buf(), entry.is_dir()));
+                }
+            }
+            detect_common_root(&entries)
+        }
+        StripComponents::Count(n) => n,
+    };
+
+    // Create the directory tree up front so that files can then be written
+    // in any order
+    let mut files = Vec::new();
     for i in 0..archive.len() {
-        let mut entry = archive.by_index(i)?;
+        let entry = archive.by_index(i)?;
         let Some(enclosed) = entry.enclosed_name() else {
             continue;
         };
-        let Some(stripped) = strip_one_component(enclosed) else {
+        let Some(relative) = select_path(enclosed, strip, options.subdir.as_deref()) else {
             continue;
         };
 
-        let out_path = dir.join(&stripped);
+        let out_path = dir.join(&relative);
         reject_traversal(dir, &out_path)?;
 
-        progress.set_message(format!("Unpacking {}", stripped.display()));
-
         if entry.is_dir() {
             create_dir_all(&out_path)?;
         } else {
             if let Some(parent) = out_path.parent() {
                 create_dir_all(parent)?;
             }
-            let mut out = File::create(&out_path)?;
-            copy(&mut entry, &mut out)?;
-
-            #[cfg(unix)]
-            if let Some(mode) = entry.unix_mode() {
-                use std::fs::{Permissions, set_permissions};
-                use std::os::unix::fs::PermissionsExt;
-                set_permissions(&out_path, Permissions::from_mode(mode))?;
-            }
+            files.push((i, relative, out_path));
+        }
+    }
+
+    let threads = thread_count(options).min(files.len());
+    if threads <= 1 {
+        for (i, relative, out_path) in &files {
+            progress.set_message(format!("Unpacking {}", relative.display()));
+            unpack_zip_file(&mut archive, *i, out_path)?;
+        }
+        return Ok(());
+    }
+
+    // Zip archives support random access, so each thread opens its own
+    // handle and decompresses its share of the entries independently
+    let mut shares = vec![Vec::new(); threads];
+    for file in &files {
+        shares[worker_index(&file.2, threads)].push(file);
+    }
+
+    thread::scope(|scope| {
+        let workers = shares
+            .into_iter()
+            .map(|share| {
+                scope.spawn(move || -> Result<()> {
+                    let mut archive = ZipArchive::new(File::open(archive_path)?)?;
+                    for (i, relative, out_path) in share {
+                        progress.set_message(format!("Unpacking {}", relative.display()));
+                        unpack_zip_file(&mut archive, *i, out_path)?;
+                    }
+                    Ok(())
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for worker in workers {
+            worker
+                .join()
+                .map_err(|_| anyhow!("Extraction thread panicked"))??;
         }
+        Ok(())
+    })
+}
+
+fn unpack_zip_file(archive: &mut ZipArchive<File>, index: usize, out_path: &Path) -> Result<()> {
+    let mut entry = archive.by_index(index)?;
+    let mut out = File::create(out_path)?;
+    copy(&mut entry, &mut out)?;
+
+    #[cfg(unix)]
+    if let Some(mode) = entry.unix_mode() {
+        use std::fs::{Permissions, set_permissions};
+        use std::os::unix::fs::PermissionsExt;
+        set_permissions(out_path, Permissions::from_mode(mode))?;
     }
 
     Ok(())
 }
 
-fn strip_one_component(path: &Path) -> Option<PathBuf> {
-    let mut components = path.components();
-    components.next()?;
+fn thread_count(options: &UnpackOptions) -> usize {
+    match options.threads {
+        0 => available_parallelism().map_or(1, NonZeroUsize::get),
+        n => n,
+    }
+}
+
+fn worker_index(path: &Path, threads: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    usize::try_from(hasher.finish() % threads as u64).expect("index must be less than threads")
+}
+
+// Returns 1 if every entry lives under the same top-level directory and 0
+// otherwise, i.e. the number of components to strip in "auto" mode
+fn detect_common_root(entries: &[(PathBuf, bool)]) -> usize {
+    let mut root = None;
+    for (path, is_dir) in entries {
+        let mut components = path
+            .components()
+            .filter(|c| !matches!(c, Component::CurDir));
+        let Some(first) = components.next() else {
+            continue;
+        };
+        let Component::Normal(first) = first else {
+            return 0;
+        };
+        if components.next().is_none() && !is_dir {
+            return 0;
+        }
+        match root {
+            None => root = Some(first),
+            Some(root) if root != first => return 0,
+            Some(_) => {}
+        }
+    }
+
+    usize::from(root.is_some())
+}
+
+fn select_path(path: &Path, strip: usize, subdir: Option<&Path>) -> Option<PathBuf> {
+    let mut components = path
+        .components()
+        .filter(|c| !matches!(c, Component::CurDir));
+    for _ in 0..strip {
+        components.next()?;
+    }
+
     let rest: PathBuf = components.collect();
+    let rest = match subdir {
+        Some(subdir) => rest.strip_prefix(subdir).ok()?.to_path_buf(),
+        None => rest,
+    };
+
     if rest.as_os_str().is_empty() {
         None
     } else {
@@ -184,6 +659,79 @@ fn strip_one_component(path: &Path) -> Option<PathBuf> {
     }
 }
 
+fn resolve_hard_link_target(
+    dir: &Path,
+    root: &Path,
+    target: &Path,
+    relative: &Path,
+    strip: usize,
+    subdir: Option<&Path>,
+) -> Result<PathBuf> {
+    if target.has_root() {
+        bail!(
+            "Refusing to extract hard link {} with absolute target {}",
+            relative.display(),
+            target.display()
+        );
+    }
+
+    // Hard link targets name other entries in the archive, so they
+    // are mapped the same way as entry paths
+    let Some(target_relative) = select_path(target, strip, subdir) else {
+        bail!(
+            "Refusing to extract hard link {} to {} outside target directory",
+            relative.display(),
+            target.display()
+        );
+    };
+    let target_path = dir.join(&target_relative);
+    reject_traversal(dir, &target_path)?;
+    let resolved = canonicalize(&target_path).with_context(|| {
+        format!(
+            "Hard link {} refers to missing entry {}",
+            relative.display(),
+            target.display()
+        )
+    })?;
+    if !resolved.starts_with(root) {
+        bail!(
+            "Refusing to extract hard link {} to {} outside target directory",
+            relative.display(),
+            target.display()
+        );
+    }
+
+    Ok(resolved)
+}
+
+fn reject_link_target(root: &Path, parent: &Path, target: &Path, relative: &Path) -> Result<()> {
+    let mut resolved = parent.to_path_buf();
+    for component in target.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::Normal(c) => resolved.push(c),
+            Component::Prefix(_) | Component::RootDir => bail!(
+                "Refusing to extract symbolic link {} with absolute target {}",
+                relative.display(),
+                target.display()
+            ),
+        }
+    }
+
+    if !resolved.starts_with(root) {
+        bail!(
+            "Refusing to extract symbolic link {} to {} outside target directory",
+            relative.display(),
+            target.display()
+        );
+    }
+
+    Ok(())
+}
+
 fn reject_traversal(root: &Path, candidate: &Path) -> Result<()> {
     for component in candidate.components() {
         if matches!(component, Component::ParentDir) {
@@ -206,24 +754,134 @@ fn reject_traversal(root: &Path, candidate: &Path) -> Result<()> {
 mod tests {
     use std::path::PathBuf;
 
+    use rstest::rstest;
+
     use super::*;
 
+    #[rstest]
+    #[case("node-v20.11.1-linux-x64.tar.xz", ".tar.xz", "node-v20.11.1-linux-x64")]
+    #[case("jdk-8u202-linux-x64.tar.bz2", ".tar.bz2", "jdk-8u202-linux-x64")]
+    #[case("tool-1.0.tgz", ".tgz", "tool-1.0")]
+    #[case("tool-1.0.tar.gz", ".tar.gz", "tool-1.0")]
+    #[case("tool-1.0.tar.zst", ".tar.zst", "tool-1.0")]
+    #[case("tool-1.0.zip", ".zip", "tool-1.0")]
+    #[case("tool-1.0.7z", ".7z", "tool-1.0")]
+    #[case("tool-1.0.pkg", ".pkg", "tool-1.0")]
+    fn strip_suffix_recognizes_all_types(
+        #[case] input: &str,
+        #[case] expected_suffix: &str,
+        #[case] expected_prefix: &str,
+    ) {
+        let (archive_type, prefix) =
+            ArchiveType::strip_suffix(input).expect("suffix must be recognized");
+        assert_eq!(expected_suffix, archive_type.suffix());
+        assert_eq!(expected_prefix, prefix);
+    }
+
+    #[test]
+    fn byte_budget_blocks_until_released() {
+        let budget = ByteBudget::new(10);
+        let first = budget.acquire(6);
+        thread::scope(|scope| {
+            let waiter = scope.spawn(|| {
+                let _second = budget.acquire(6);
+            });
+            thread::sleep(Duration::from_millis(50));
+            assert!(!waiter.is_finished());
+            drop(first);
+            waiter.join().expect("waiter must not panic");
+        });
+        assert_eq!(10, *budget.available.lock().expect("lock must succeed"));
+    }
+
+    #[test]
+    fn strip_suffix_rejects_unknown() {
+        assert!(ArchiveType::strip_suffix("tool-1.0.rar").is_none());
+    }
+
     #[test]
-    fn strip_one_component_drops_first_segment() {
+    fn select_path_drops_first_segment() {
         assert_eq!(
             Some(PathBuf::from("bin/python")),
-            strip_one_component(Path::new("cpython-3.14/bin/python"))
+            select_path(Path::new("cpython-3.14/bin/python"), 1, None)
         );
     }
 
     #[test]
-    fn strip_one_component_returns_none_for_top_level() {
-        assert_eq!(None, strip_one_component(Path::new("cpython-3.14")));
+    fn select_path_returns_none_for_top_level() {
+        assert_eq!(None, select_path(Path::new("cpython-3.14"), 1, None));
     }
 
     #[test]
-    fn strip_one_component_returns_none_for_empty() {
-        assert_eq!(None, strip_one_component(Path::new("")));
+    fn select_path_returns_none_for_empty() {
+        assert_eq!(None, select_path(Path::new(""), 1, None));
+    }
+
+    #[rstest]
+    #[case(Some("README"), "README", 0, None)]
+    #[case(Some("bin/java"), "jdk/Contents/Home/bin/java", 3, None)]
+    #[case(None, "jdk/Contents", 3, None)]
+    #[case(
+        Some("bin/python3"),
+        "./python/install/bin/python3",
+        1,
+        Some("install")
+    )]
+    #[case(None, "python/build/config.log", 1, Some("install"))]
+    #[case(None, "python/install", 1, Some("install"))]
+    #[case(
+        Some("bin/python3"),
+        "python/install/bin/python3",
+        0,
+        Some("python/install")
+    )]
+    fn select_path_basics(
+        #[case] expected: Option<&str>,
+        #[case] input: &str,
+        #[case] strip: usize,
+        #[case] subdir: Option<&str>,
+    ) {
+        assert_eq!(
+            expected.map(PathBuf::from),
+            select_path(Path::new(input), strip, subdir.map(Path::new))
+        );
+    }
+
+    #[rstest]
+    #[case(1, &[("top", true), ("top/a", false), ("top/b/c", false)])]
+    #[case(1, &[("./top/a", false), ("./top/b", false)])]
+    #[case(0, &[("top/a", false), ("other/b", false)])]
+    #[case(0, &[("top/a", false), ("README", false)])]
+    #[case(0, &[("README", false)])]
+    #[case(0, &[])]
+    fn detect_common_root_basics(#[case] expected: usize, #[case] entries: &[(&str, bool)]) {
+        let entries = entries
+            .iter()
+            .map(|(path, is_dir)| (PathBuf::from(path), *is_dir))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, detect_common_root(&entries));
+    }
+
+    #[rstest]
+    #[case(true, "python3.12")]
+    #[case(true, "../lib/libpython3.12.so")]
+    #[case(true, "./python3.12")]
+    #[case(false, "../../escape")]
+    #[case(true, "../../root/lib")]
+    #[case(false, "../../rootx/escape")]
+    #[case(false, "/etc/passwd")]
+    fn reject_link_target_basics(#[case] expected_ok: bool, #[case] target: &str) {
+        let root = Path::new("/tmp/root");
+        assert_eq!(
+            expected_ok,
+            reject_link_target(
+                root,
+                &root.join("bin"),
+                Path::new(target),
+                Path::new("bin/python3")
+            )
+            .is_ok()
+        );
     }
 
     #[test]
@@ -19,25 +19,35 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
+use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::path::Path;
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
 use anyhow::{Error, Result};
-use hex::decode;
+use hex::{decode, encode};
 use sha2::{Digest, Sha256};
 use tokio::fs::read;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Checksum(Vec<u8>);
 
 impl Checksum {
-    pub async fn validate_file(&self, path: &Path) -> Result<bool> {
+    pub async fn from_file(path: &Path) -> Result<Self> {
         let data = read(path).await?;
         let mut hasher = Sha256::new();
         hasher.update(data);
-        let hash = hasher.finalize().to_vec();
-        Ok(self.0 == hash)
+        Ok(Self(hasher.finalize().to_vec()))
+    }
+
+    pub async fn validate_file(&self, path: &Path) -> Result<bool> {
+        Ok(*self == Self::from_file(path).await?)
+    }
+}
+
+impl Display for Checksum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", encode(&self.0))
     }
 }
 
@@ -0,0 +1,265 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fs::read;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Result, bail};
+use log::debug;
+
+use crate::shell::Platform;
+use crate::target::{Libc, Target};
+
+const ELF_MAGIC: &[u8] = b"\x7fELF";
+const ELF_CLASS_32: u8 = 1;
+const ELF_CLASS_64: u8 = 2;
+const ELF_DATA_LSB: u8 = 1;
+const ELF_DATA_MSB: u8 = 2;
+const PT_INTERP: u32 = 3;
+
+// Determines the C library of the running Linux system by inspecting the
+// dynamic loader of /bin/sh and falling back to the output of "ldd --version":
+// returns None on other operating systems
+#[must_use]
+pub fn detect_libc() -> Option<Libc> {
+    if Target::host().platform != Platform::Linux {
+        return None;
+    }
+
+    Some(
+        libc_from_elf_file(Path::new("/bin/sh"))
+            .or_else(|| run_ldd_version().as_deref().and_then(libc_from_ldd_output))
+            .unwrap_or(Libc::Gnu),
+    )
+}
+
+// Determines C library from ELF interpreter of given executable
+#[must_use]
+pub fn libc_from_elf_file(path: &Path) -> Option<Libc> {
+    let interpreter = match read(path)
+        .map_err(Into::into)
+        .and_then(|bytes| read_elf_interpreter(&bytes))
+    {
+        Ok(Some(interpreter)) => interpreter,
+        Ok(None) => {
+            debug!("{} has no ELF interpreter", path.display());
+            return None;
+        }
+        Err(e) => {
+            debug!("Could not read ELF interpreter of {}: {e}", path.display());
+            return None;
+        }
+    };
+
+    let file_name = Path::new(&interpreter).file_name()?.to_str()?;
+    if file_name.starts_with("ld-musl-") {
+        Some(Libc::Musl)
+    } else if file_name.starts_with("ld-linux") {
+        Some(Libc::Gnu)
+    } else {
+        debug!("Unrecognized ELF interpreter {interpreter}");
+        None
+    }
+}
+
+// Determines C library from combined stdout and stderr of "ldd --version"
+#[must_use]
+pub fn libc_from_ldd_output(s: &str) -> Option<Libc> {
+    let s = s.to_lowercase();
+    if s.contains("musl") {
+        Some(Libc::Musl)
+    } else if s.contains("glibc") || s.contains("gnu libc") || s.contains("gnu c library") {
+        Some(Libc::Gnu)
+    } else {
+        None
+    }
+}
+
+fn run_ldd_version() -> Option<String> {
+    // musl's ldd prints its version to stderr and exits with a nonzero status
+    let output = match Command::new("ldd").arg("--version").output() {
+        Ok(output) => output,
+        Err(e) => {
+            debug!("Could not run ldd: {e}");
+            return None;
+        }
+    };
+    let mut s = String::from_utf8_lossy(&output.stdout).into_owned();
+    s.push_str(&String::from_utf8_lossy(&output.stderr));
+    Some(s)
+}
+
+fn read_elf_interpreter(bytes: &[u8]) -> Result<Option<String>> {
+    struct Reader<'a> {
+        bytes: &'a [u8],
+        msb: bool,
+    }
+
+    impl Reader<'_> {
+        fn slice<const N: usize>(&self, offset: usize) -> Result<[u8; N]> {
+            let Some(slice) = offset
+                .checked_add(N)
+                .and_then(|end| self.bytes.get(offset..end))
+            else {
+                bail!("ELF file is truncated");
+            };
+            Ok(slice.try_into()?)
+        }
+
+        fn u16(&self, offset: usize) -> Result<u16> {
+            let b = self.slice::<2>(offset)?;
+            Ok(if self.msb {
+                u16::from_be_bytes(b)
+            } else {
+                u16::from_le_bytes(b)
+            })
+        }
+
+        fn u32(&self, offset: usize) -> Result<u32> {
+            let b = self.slice::<4>(offset)?;
+            Ok(if self.msb {
+                u32::from_be_bytes(b)
+            } else {
+                u32::from_le_bytes(b)
+            })
+        }
+
+        fn u64(&self, offset: usize) -> Result<u64> {
+            let b = self.slice::<8>(offset)?;
+            Ok(if self.msb {
+                u64::from_be_bytes(b)
+            } else {
+                u64::from_le_bytes(b)
+            })
+        }
+
+        fn usize(&self, offset: usize, is_64: bool) -> Result<usize> {
+            Ok(if is_64 {
+                usize::try_from(self.u64(offset)?)?
+            } else {
+                usize::try_from(self.u32(offset)?)?
+            })
+        }
+    }
+
+    if !bytes.starts_with(ELF_MAGIC) {
+        bail!("Not an ELF file");
+    }
+
+    let Some(&class) = bytes.get(4) else {
+        bail!("ELF file is truncated");
+    };
+    let is_64 = match class {
+        ELF_CLASS_32 => false,
+        ELF_CLASS_64 => true,
+        _ => bail!("Unsupported ELF class {class}"),
+    };
+
+    let msb = match bytes.get(5) {
+        Some(&ELF_DATA_LSB) => false,
+        Some(&ELF_DATA_MSB) => true,
+        _ => bail!("Unsupported ELF data encoding"),
+    };
+
+    let reader = Reader { bytes, msb };
+
+    // Offsets of e_phoff, e_phentsize and e_phnum in ELF header and of
+    // p_offset and p_filesz in program header
+    let (phoff, phentsize, phnum, p_offset, p_filesz) = if is_64 {
+        (0x20, 0x36, 0x38, 0x08, 0x20)
+    } else {
+        (0x1c, 0x2a, 0x2c, 0x04, 0x10)
+    };
+
+    let phoff = reader.usize(phoff, is_64)?;
+    let phentsize = usize::from(reader.u16(phentsize)?);
+    let phnum = usize::from(reader.u16(phnum)?);
+
+    for i in 0..phnum {
+        let header = phoff + i * phentsize;
+        if reader.u32(header)? != PT_INTERP {
+            continue;
+        }
+
+        let offset = reader.usize(header + p_offset, is_64)?;
+        let size = reader.usize(header + p_filesz, is_64)?;
+        let Some(interpreter) = offset
+            .checked_add(size)
+            .and_then(|end| bytes.get(offset..end))
+        else {
+            bail!("ELF file is truncated");
+        };
+
+        let interpreter = interpreter.split(|b| *b == 0).next().unwrap_or_default();
+        return Ok(Some(String::from_utf8(interpreter.to_vec())?));
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+    use std::path::{Path, PathBuf};
+
+    use anyhow::Result;
+    use rstest::rstest;
+
+    use crate::detect_libc::{libc_from_elf_file, libc_from_ldd_output};
+    use crate::target::Libc;
+
+    fn fixture_path(file_name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("libc")
+            .join(file_name)
+    }
+
+    #[rstest]
+    #[case(Some(Libc::Musl), "sh-musl-x86_64")]
+    #[case(Some(Libc::Musl), "sh-musl-aarch64")]
+    #[case(Some(Libc::Gnu), "sh-gnu-x86_64")]
+    #[case(Some(Libc::Gnu), "sh-gnu-armhf")]
+    #[case(Some(Libc::Gnu), "sh-gnu-aarch64_be")]
+    #[case(None, "sh-static")]
+    #[case(None, "sh-truncated")]
+    #[case(None, "ldd-gnu.txt")]
+    #[case(None, "does-not-exist")]
+    fn elf_file(#[case] expected: Option<Libc>, #[case] file_name: &str) {
+        assert_eq!(expected, libc_from_elf_file(&fixture_path(file_name)));
+    }
+
+    #[rstest]
+    #[case(Some(Libc::Gnu), "ldd-gnu.txt")]
+    #[case(Some(Libc::Musl), "ldd-musl.txt")]
+    fn ldd_output(#[case] expected: Option<Libc>, #[case] file_name: &str) -> Result<()> {
+        let s = read_to_string(fixture_path(file_name))?;
+        assert_eq!(expected, libc_from_ldd_output(&s));
+        Ok(())
+    }
+
+    #[test]
+    fn ldd_output_unrecognized() {
+        assert_eq!(None, libc_from_ldd_output("ldd: unrecognized option"));
+    }
+}
@@ -21,8 +21,31 @@
 //
 use std::path::PathBuf;
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PathVarPosition {
+    Prepend,
+    Append,
+}
+
+// List-valued variable such as PKG_CONFIG_PATH whose directories are added
+// to any inherited value rather than replacing it
+#[derive(Debug, Eq, PartialEq)]
+pub struct PathVar {
+    pub name: String,
+    pub position: PathVarPosition,
+    pub dirs: Vec<PathBuf>,
+}
+
 #[derive(Debug)]
 pub struct EnvInfo {
     pub path_dirs: Vec<PathBuf>,
     pub vars: Vec<(String, String)>,
+
+    // Variables removed from the inherited environment before any of the
+    // variables above are set
+    pub unset_vars: Vec<String>,
+
+    // Applied after all other variables: directories are always added to
+    // PATH via path_dirs
+    pub path_vars: Vec<PathVar>,
 }
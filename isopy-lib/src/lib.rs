@@ -24,6 +24,7 @@ pub mod pagination;
 mod accept;
 mod archive_type;
 mod checksum;
+mod detect_libc;
 mod env_info;
 mod extent;
 mod extra;
@@ -36,19 +37,26 @@ mod package_info;
 mod package_manager;
 mod package_manager_context;
 mod plugin;
+mod plugin_descriptor;
+mod plugin_error;
 mod progress_indicator;
+mod project_version;
 mod sanitize;
+mod shasums;
 mod shell;
 mod source_filter;
 mod tag_filter;
 mod tags;
+mod target;
 mod triple;
+mod unpack_options;
 mod url;
 mod version;
 
 pub use accept::*;
 pub use archive_type::*;
 pub use checksum::*;
+pub use detect_libc::*;
 pub use env_info::*;
 pub use extent::*;
 pub use extra::*;
@@ -60,12 +68,18 @@ pub use package_info::*;
 pub use package_manager::*;
 pub use package_manager_context::*;
 pub use plugin::*;
+pub use plugin_descriptor::*;
+pub use plugin_error::*;
 pub use progress_indicator::*;
+pub use project_version::*;
 pub use sanitize::*;
+pub use shasums::*;
 pub use shell::*;
 pub use source_filter::*;
 pub use tag_filter::*;
 pub use tags::*;
+pub use target::*;
 pub use triple::*;
+pub use unpack_options::*;
 pub use url::*;
 pub use version::*;
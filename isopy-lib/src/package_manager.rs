@@ -31,36 +31,42 @@ use crate::package::Package;
 use crate::package_info::PackageInfo;
 use crate::tag_filter::TagFilter;
 use crate::tags::Tags;
+use crate::target::Target;
 use crate::version::Version;
 
 #[derive(Builder, Default)]
 #[builder(default)]
 pub struct DownloadPackageOptions {
     pub show_progress: bool,
+    pub target: Target,
 }
 
 #[derive(Builder, Default)]
 #[builder(default)]
 pub struct GetPackageOptions {
     pub show_progress: bool,
+    pub target: Target,
 }
 
 #[derive(Builder, Default)]
 #[builder(default)]
 pub struct InstallPackageOptions {
     pub show_progress: bool,
+    pub target: Target,
 }
 
 #[derive(Builder, Default)]
 #[builder(default)]
 pub struct ListPackagesOptions {
     pub show_progress: bool,
+    pub target: Target,
 }
 
 #[derive(Builder, Default)]
 #[builder(default)]
 pub struct ListTagsOptions {
     pub show_progress: bool,
+    pub target: Target,
 }
 
 #[derive(Builder, Default)]
@@ -80,6 +80,7 @@ pub struct DownloadPaginatedAssetResponse {
 #[async_trait]
 pub trait PackageManagerContextOps: Send + Sync {
     fn base_dir(&self) -> &Path;
+    fn config_dir(&self) -> &Path;
     fn check_asset(&self, url: &Url) -> Result<Option<PathBuf>>;
     fn check_asset_dir(&self, url: &Url) -> Result<Option<PathBuf>>;
     fn make_asset_dir(&self, url: &Url, create_new: bool) -> Result<PathBuf>;
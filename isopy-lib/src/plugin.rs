@@ -20,22 +20,50 @@
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
 use std::ffi::OsString;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Result, bail};
+use derive_builder::Builder;
 use url::Url;
 
 use crate::env_info::EnvInfo;
 use crate::macros::dyn_trait_struct;
 use crate::package_manager::PackageManager;
 use crate::package_manager_context::PackageManagerContext;
+use crate::project_version::ProjectVersion;
 use crate::shell::{Platform, Shell};
 use crate::version::Version;
 
+#[derive(Builder, Default)]
+#[builder(default)]
+pub struct MakeEnvInfoOptions {
+    pub venv_dir: Option<PathBuf>,
+
+    // Per-environment directory for state written by the package's own
+    // tooling, e.g. GOPATH: not set unless enabled by configuration or the
+    // package is a read-only shared install
+    pub workspace_dir: Option<PathBuf>,
+
+    // Environment should not pick up per-user state from outside isopy,
+    // e.g. Python's user site-packages directory
+    pub isolated: bool,
+
+    // Value of GOTOOLCHAIN for Go environments: "local" if not configured
+    pub go_toolchain: Option<String>,
+}
+
+#[derive(Builder, Default)]
+#[builder(default)]
+pub struct CreateVenvOptions {
+    pub requirements: Vec<PathBuf>,
+    pub system_site_packages: bool,
+    pub lock: Option<PathBuf>,
+}
+
 pub trait PluginOps: Send + Sync {
     fn url(&self) -> &Url;
     fn parse_version(&self, s: &str) -> Result<Version>;
-    fn make_env_info(&self, dir: &Path) -> EnvInfo;
+    fn make_env_info(&self, dir: &Path, options: &MakeEnvInfoOptions) -> EnvInfo;
     fn make_script_command(
         &self,
         script_path: &Path,
@@ -43,5 +71,23 @@ pub trait PluginOps: Send + Sync {
         shell: Shell,
     ) -> Result<Option<OsString>>;
     fn new_package_manager(&self, ctx: PackageManagerContext) -> PackageManager;
+
+    fn create_venv(
+        &self,
+        _dir: &Path,
+        venv_dir: &Path,
+        _options: &CreateVenvOptions,
+    ) -> Result<()> {
+        bail!(
+            "Cannot create virtual environment {}: package manager does not support virtual environments",
+            venv_dir.display()
+        )
+    }
+
+    // Infers the version required by the project in "dir" from files
+    // understood by the package's own tooling
+    fn detect_project_version(&self, _dir: &Path) -> Result<Option<ProjectVersion>> {
+        Ok(None)
+    }
 }
 dyn_trait_struct!(Plugin, PluginOps);
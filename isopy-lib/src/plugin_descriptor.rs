@@ -0,0 +1,53 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::sync::Arc;
+
+use crate::plugin::Plugin;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stability {
+    Stable,
+    Experimental,
+    External,
+}
+
+impl Display for Stability {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Stable => write!(f, "stable"),
+            Self::Experimental => write!(f, "experimental"),
+            Self::External => write!(f, "external"),
+        }
+    }
+}
+
+// Metadata with which a plugin registers itself with isopy: plugins with an
+// enable env var are only available when it is set to true
+#[derive(Clone)]
+pub struct PluginDescriptor {
+    pub name: String,
+    pub description: String,
+    pub stability: Stability,
+    pub enable_env_var: Option<String>,
+    pub new_plugin: Arc<dyn Fn(&str) -> Plugin + Send + Sync>,
+}
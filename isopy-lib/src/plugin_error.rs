@@ -0,0 +1,158 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+// Errors that plugins report for conditions the user can act on: the
+// application prints the hint to tell the user what to do next
+#[derive(Debug)]
+pub enum PluginError {
+    Unsupported {
+        moniker: String,
+        operation: &'static str,
+    },
+    PackageNotFound {
+        package_id: String,
+        tags: Vec<String>,
+    },
+    PackageNotDownloaded {
+        package_id: String,
+    },
+    AmbiguousPackage {
+        package_id: String,
+        candidates: Vec<String>,
+    },
+}
+
+impl PluginError {
+    #[must_use]
+    pub fn unsupported(moniker: &str, operation: &'static str) -> Self {
+        Self::Unsupported {
+            moniker: String::from(moniker),
+            operation,
+        }
+    }
+
+    #[must_use]
+    pub fn hint(&self) -> String {
+        match self {
+            Self::Unsupported { moniker, .. } => format!(
+                "The {moniker} plugin is experimental and does not implement this operation yet"
+            ),
+            Self::PackageNotFound { .. } => String::from(
+                "Use \"isopy packages --remote\" to list available packages or \"isopy update\" to refresh the index",
+            ),
+            Self::PackageNotDownloaded { package_id } => format!(
+                "Use \"isopy download {package_id}\" or pass \"--download\" to download missing packages"
+            ),
+            Self::AmbiguousPackage { .. } => {
+                String::from("Use \"--tags\" to select one of the candidates")
+            }
+        }
+    }
+}
+
+impl Display for PluginError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Unsupported { moniker, operation } => {
+                write!(
+                    f,
+                    "Operation \"{operation}\" is not supported by {moniker} plugin"
+                )
+            }
+            Self::PackageNotFound { package_id, tags } if tags.is_empty() => {
+                write!(f, "No package with ID {package_id} found in index")
+            }
+            Self::PackageNotFound { package_id, tags } => write!(
+                f,
+                "No package with ID {package_id} and tags {} found in index",
+                tags.join(", ")
+            ),
+            Self::PackageNotDownloaded { package_id } => {
+                write!(f, "Package with ID {package_id} has not been downloaded")
+            }
+            Self::AmbiguousPackage {
+                package_id,
+                candidates,
+            } => write!(
+                f,
+                "More than one viable candidate for package {package_id}: {}",
+                candidates.join(", ")
+            ),
+        }
+    }
+}
+
+impl StdError for PluginError {}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Error;
+
+    use crate::plugin_error::PluginError;
+
+    #[test]
+    fn downcast_through_context() {
+        let e = Error::new(PluginError::PackageNotDownloaded {
+            package_id: String::from("python:3.13.1"),
+        })
+        .context("Failed to install package");
+        let plugin_error = e
+            .downcast_ref::<PluginError>()
+            .expect("Must be a plugin error");
+        assert_eq!(
+            "Package with ID python:3.13.1 has not been downloaded",
+            plugin_error.to_string()
+        );
+        assert!(plugin_error.hint().contains("isopy download python:3.13.1"));
+    }
+
+    #[test]
+    fn display_ambiguous_package() {
+        let e = PluginError::AmbiguousPackage {
+            package_id: String::from("python:3.13.1"),
+            candidates: vec![String::from("a.tar.gz"), String::from("b.tar.gz")],
+        };
+        assert_eq!(
+            "More than one viable candidate for package python:3.13.1: a.tar.gz, b.tar.gz",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn display_package_not_found() {
+        let e = PluginError::PackageNotFound {
+            package_id: String::from("go:1.22.0"),
+            tags: Vec::new(),
+        };
+        assert_eq!("No package with ID go:1.22.0 found in index", e.to_string());
+        let e = PluginError::PackageNotFound {
+            package_id: String::from("go:1.22.0"),
+            tags: vec![String::from("arm64")],
+        };
+        assert_eq!(
+            "No package with ID go:1.22.0 and tags arm64 found in index",
+            e.to_string()
+        );
+    }
+}
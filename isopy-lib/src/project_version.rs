@@ -0,0 +1,60 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::PathBuf;
+
+use crate::macros::dyn_trait_struct;
+use crate::version::Version;
+
+pub trait VersionRequirementOps: Display + Send + Sync {
+    // Chooses the most suitable, typically the latest, of the available
+    // versions satisfying the requirement
+    fn choose(&self, versions: &[Version]) -> Option<Version>;
+}
+dyn_trait_struct!(VersionRequirement, VersionRequirementOps);
+
+impl Display for VersionRequirement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Display::fmt(&self.0, f)
+    }
+}
+
+pub enum ProjectVersionSpec {
+    Exact(Version),
+    Requirement(VersionRequirement),
+}
+
+impl Display for ProjectVersionSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Exact(version) => write!(f, "{}", version.as_str()),
+            Self::Requirement(requirement) => write!(f, "{requirement}"),
+        }
+    }
+}
+
+// Version of a package inferred from files in a project directory such as
+// go.mod
+pub struct ProjectVersion {
+    pub path: PathBuf,
+    pub spec: ProjectVersionSpec,
+}
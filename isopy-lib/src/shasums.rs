@@ -0,0 +1,81 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+
+use crate::checksum::Checksum;
+
+// Parses SHA-256 checksum files such as SHASUMS256.txt published alongside
+// releases: each line consists of a digest and a file name separated by
+// whitespace
+pub fn parse_shasums(s: &str) -> Result<HashMap<String, Checksum>> {
+    let mut checksums = HashMap::new();
+    for line in s.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let Some((checksum, file_name)) = line.split_once(char::is_whitespace) else {
+            bail!("Invalid checksum line {line}")
+        };
+
+        // File names may be prefixed with "*" to indicate binary mode
+        let file_name = file_name.trim_start();
+        let file_name = file_name.strip_prefix('*').unwrap_or(file_name);
+        checksums.insert(String::from(file_name), checksum.parse()?);
+    }
+    Ok(checksums)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::shasums::parse_shasums;
+
+    const SHASUMS: &str = "\
+0c3c3ad8b3f3e0f1d38f4a8a6e9f0d4f5c2b9a7a1e6d3c8b7f4e2a1d0c9b8a7f  node-v20.11.1-darwin-arm64.tar.gz
+3f0b3a6c5a2e1d4f7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f0a  node-v20.11.1-linux-x64.tar.xz
+a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90 *node-v20.11.1-win-x64.zip
+
+";
+
+    #[test]
+    fn parse() -> Result<()> {
+        let checksums = parse_shasums(SHASUMS)?;
+        assert_eq!(3, checksums.len());
+        assert_eq!(
+            "3f0b3a6c5a2e1d4f7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f0a",
+            checksums["node-v20.11.1-linux-x64.tar.xz"].to_string()
+        );
+        assert!(checksums.contains_key("node-v20.11.1-win-x64.zip"));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(parse_shasums("not-a-checksum").is_err());
+        assert!(parse_shasums("xyz  node-v20.11.1-linux-x64.tar.xz").is_err());
+    }
+}
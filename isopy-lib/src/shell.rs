@@ -24,24 +24,28 @@ use std::path::Path;
 
 use anyhow::Result;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Platform {
     Linux,
     MacOS,
     Windows,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Shell {
     Bash,
     Cmd,
+    Fish,
+    Nushell,
+    PowerShell,
+    Zsh,
 }
 
 #[must_use]
 pub fn env_var_substitution(shell: Shell, env_var: &str) -> OsString {
     let mut s = OsString::new();
     match shell {
-        Shell::Bash => {
+        Shell::Bash | Shell::Fish | Shell::Zsh => {
             s.push("$");
             s.push(env_var);
         }
@@ -50,15 +54,44 @@ pub fn env_var_substitution(shell: Shell, env_var: &str) -> OsString {
             s.push(env_var);
             s.push("%");
         }
+        Shell::Nushell => {
+            s.push("$env.");
+            s.push(env_var);
+        }
+        Shell::PowerShell => {
+            s.push("$env:");
+            s.push(env_var);
+        }
     }
     s
 }
 
+// PowerShell and Nushell use the host's separator: Bash on Windows uses
+// Unix-style paths and separators
 #[must_use]
 pub fn path_separator(shell: Shell) -> &'static OsStr {
     match shell {
-        Shell::Bash => OsStr::new(":"),
+        Shell::Bash | Shell::Fish | Shell::Zsh => OsStr::new(":"),
         Shell::Cmd => OsStr::new(";"),
+        Shell::Nushell | Shell::PowerShell => {
+            if cfg!(target_os = "windows") {
+                OsStr::new(";")
+            } else {
+                OsStr::new(":")
+            }
+        }
+    }
+}
+
+// Renders a string literal that the shell will not expand or split
+#[must_use]
+pub fn quote(shell: Shell, s: &str) -> String {
+    match shell {
+        Shell::Bash | Shell::Zsh => format!("'{}'", s.replace('\'', "'\\''")),
+        Shell::Cmd => format!("\"{s}\""),
+        Shell::Fish => format!("'{}'", s.replace('\\', "\\\\").replace('\'', "\\'")),
+        Shell::Nushell => format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")),
+        Shell::PowerShell => format!("'{}'", s.replace('\'', "''")),
     }
 }
 
@@ -92,8 +125,8 @@ pub fn render_absolute_path(shell: Shell, path: &Path) -> Result<OsString> {
     }
 
     match shell {
-        Shell::Bash => render_absolute_path_windows_bash(path),
-        Shell::Cmd => Ok(OsString::from(path)),
+        Shell::Bash | Shell::Fish | Shell::Zsh => render_absolute_path_windows_bash(path),
+        Shell::Cmd | Shell::Nushell | Shell::PowerShell => Ok(OsString::from(path)),
     }
 }
 
@@ -115,7 +148,7 @@ fn render_absolute_path_windows_bash(path: &Path) -> Result<OsString> {
 
     let mut s = OsString::new();
     s.push("/");
-    let temp = String::from_utf8(vec![raw]).expect("unimplemented");
+    let temp = String::from_utf8(vec![raw])?;
     s.push(temp);
 
     let Some(Component::RootDir) = iter.next() else {
@@ -132,3 +165,22 @@ fn render_absolute_path_windows_bash(path: &Path) -> Result<OsString> {
 
     Ok(s)
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::shell::{Shell, quote};
+
+    #[rstest]
+    #[case("'a b'", Shell::Bash, "a b")]
+    #[case("'it'\\''s'", Shell::Bash, "it's")]
+    #[case("'$HOME'", Shell::Zsh, "$HOME")]
+    #[case("\"a b\"", Shell::Cmd, "a b")]
+    #[case("'it\\'s \\\\'", Shell::Fish, "it's \\")]
+    #[case("\"say \\\"hi\\\" \\\\\"", Shell::Nushell, "say \"hi\" \\")]
+    #[case("'it''s'", Shell::PowerShell, "it's")]
+    fn quote_basics(#[case] expected: &str, #[case] shell: Shell, #[case] input: &str) {
+        assert_eq!(expected, quote(shell, input));
+    }
+}
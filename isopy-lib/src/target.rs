@@ -0,0 +1,253 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+use anyhow::{Context, Error, bail};
+
+use crate::shell::Platform;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Arch {
+    Aarch64,
+    X86_64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Libc {
+    Gnu,
+    Musl,
+}
+
+impl Display for Libc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Gnu => write!(f, "gnu"),
+            Self::Musl => write!(f, "musl"),
+        }
+    }
+}
+
+impl FromStr for Libc {
+    type Err = Error;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        match s {
+            "gnu" => Ok(Self::Gnu),
+            "musl" => Ok(Self::Musl),
+            _ => bail!("Unsupported C library {s}: expected gnu or musl"),
+        }
+    }
+}
+
+// Operating system, architecture and, on Linux, C library of the machine
+// that packages are selected for: defaults to the machine isopy was built for
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Target {
+    pub platform: Platform,
+    pub arch: Arch,
+    pub libc: Option<Libc>,
+}
+
+impl Target {
+    #[must_use]
+    pub const fn host() -> Self {
+        #[cfg(target_os = "linux")]
+        const PLATFORM: Platform = Platform::Linux;
+        #[cfg(target_os = "macos")]
+        const PLATFORM: Platform = Platform::MacOS;
+        #[cfg(target_os = "windows")]
+        const PLATFORM: Platform = Platform::Windows;
+
+        #[cfg(target_arch = "aarch64")]
+        const ARCH: Arch = Arch::Aarch64;
+        #[cfg(target_arch = "x86_64")]
+        const ARCH: Arch = Arch::X86_64;
+
+        #[cfg(all(target_os = "linux", target_env = "musl"))]
+        const LIBC: Option<Libc> = Some(Libc::Musl);
+        #[cfg(all(target_os = "linux", not(target_env = "musl")))]
+        const LIBC: Option<Libc> = Some(Libc::Gnu);
+        #[cfg(not(target_os = "linux"))]
+        const LIBC: Option<Libc> = None;
+
+        Self {
+            platform: PLATFORM,
+            arch: ARCH,
+            libc: LIBC,
+        }
+    }
+}
+
+impl Default for Target {
+    fn default() -> Self {
+        Self::host()
+    }
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let platform = match self.platform {
+            Platform::Linux => "linux",
+            Platform::MacOS => "macos",
+            Platform::Windows => "windows",
+        };
+        let arch = match self.arch {
+            Arch::Aarch64 => "aarch64",
+            Arch::X86_64 => "x86_64",
+        };
+        write!(f, "{platform}-{arch}")?;
+        match self.libc {
+            Some(libc) => write!(f, "-{libc}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Target {
+    type Err = Error;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        let parts = s.split('-').collect::<Vec<_>>();
+        let (platform, arch, libc) = match parts.as_slice() {
+            [platform, arch] => (*platform, *arch, None),
+            [platform, arch, libc] => (*platform, *arch, Some(*libc)),
+            _ => bail!(
+                "Invalid target {s}: expected <OS>-<ARCH>[-<LIBC>], e.g. linux-aarch64-gnu or windows-x86_64"
+            ),
+        };
+
+        let platform = match platform {
+            "linux" => Platform::Linux,
+            "macos" | "darwin" => Platform::MacOS,
+            "windows" => Platform::Windows,
+            _ => bail!("Invalid target {s}: unsupported operating system {platform}"),
+        };
+
+        let arch = match arch {
+            "aarch64" | "arm64" => Arch::Aarch64,
+            "x86_64" | "amd64" => Arch::X86_64,
+            _ => bail!("Invalid target {s}: unsupported architecture {arch}"),
+        };
+
+        let libc = match (platform, libc) {
+            (Platform::Linux, None) => Some(Libc::Gnu),
+            (Platform::Linux, Some(libc)) => Some(
+                libc.parse()
+                    .with_context(|| format!("Invalid target {s}"))?,
+            ),
+            (_, None) => None,
+            (_, Some(_)) => bail!("Invalid target {s}: C library is only supported on Linux"),
+        };
+
+        Ok(Self {
+            platform,
+            arch,
+            libc,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rstest::rstest;
+
+    use crate::shell::Platform;
+    use crate::target::{Arch, Libc, Target};
+
+    #[rstest]
+    #[case(
+        "linux-aarch64-gnu",
+        Platform::Linux,
+        Arch::Aarch64,
+        Some(Libc::Gnu),
+        "linux-aarch64-gnu"
+    )]
+    #[case(
+        "linux-x86_64-gnu",
+        Platform::Linux,
+        Arch::X86_64,
+        Some(Libc::Gnu),
+        "linux-x86_64"
+    )]
+    #[case(
+        "linux-x86_64-musl",
+        Platform::Linux,
+        Arch::X86_64,
+        Some(Libc::Musl),
+        "linux-x86_64-musl"
+    )]
+    #[case("macos-aarch64", Platform::MacOS, Arch::Aarch64, None, "darwin-arm64")]
+    #[case(
+        "windows-x86_64",
+        Platform::Windows,
+        Arch::X86_64,
+        None,
+        "windows-amd64"
+    )]
+    fn from_str(
+        #[case] expected_str: &str,
+        #[case] expected_platform: Platform,
+        #[case] expected_arch: Arch,
+        #[case] expected_libc: Option<Libc>,
+        #[case] input: &str,
+    ) -> Result<()> {
+        let target = input.parse::<Target>()?;
+        assert_eq!(expected_platform, target.platform);
+        assert_eq!(expected_arch, target.arch);
+        assert_eq!(expected_libc, target.libc);
+        assert_eq!(expected_str, target.to_string());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("linux")]
+    #[case("freebsd-x86_64")]
+    #[case("linux-riscv64")]
+    #[case("linux-x86_64-uclibc")]
+    #[case("windows-x86_64-gnu")]
+    #[case("linux-x86_64-gnu-extra")]
+    fn from_str_invalid(#[case] input: &str) {
+        assert!(input.parse::<Target>().is_err());
+    }
+
+    #[rstest]
+    #[case(Libc::Gnu, "gnu")]
+    #[case(Libc::Musl, "musl")]
+    fn libc_round_trip(#[case] expected: Libc, #[case] input: &str) -> Result<()> {
+        let libc = input.parse::<Libc>()?;
+        assert_eq!(expected, libc);
+        assert_eq!(input, libc.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn host_round_trip() -> Result<()> {
+        let host = Target::host();
+        assert_eq!(host, host.to_string().parse()?);
+        assert_eq!(host, Target::default());
+        Ok(())
+    }
+}
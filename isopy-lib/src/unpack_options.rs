@@ -0,0 +1,53 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::path::PathBuf;
+
+use derive_builder::Builder;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StripComponents {
+    // Detects the common root directory: for tar archives this reads the
+    // whole archive twice, so only use it when the count isn't known
+    Auto,
+    Count(usize),
+}
+
+impl Default for StripComponents {
+    fn default() -> Self {
+        Self::Count(1)
+    }
+}
+
+#[derive(Builder, Clone, Debug, Default)]
+#[builder(default)]
+pub struct UnpackOptions {
+    pub show_progress: bool,
+
+    pub strip_components: StripComponents,
+
+    #[builder(setter(into, strip_option))]
+    pub subdir: Option<PathBuf>,
+
+    // Number of threads used to write files: 0 selects one per available
+    // core and 1 extracts sequentially on a single thread
+    pub threads: usize,
+}
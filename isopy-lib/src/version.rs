@@ -30,6 +30,12 @@ pub trait VersionOps: Debug + Send + Sync {
     fn label(&self) -> Option<Cow<'_, String>>;
     fn box_clone(&self) -> Box<dyn VersionOps>;
     fn as_any(&self) -> &dyn Any;
+
+    // Distinguishes independent packages of plugins such as the binary
+    // plugin so that an environment can contain one package of each name
+    fn package_name(&self) -> Option<Cow<'_, String>> {
+        None
+    }
 }
 dyn_trait_struct!(Version, VersionOps);
 
@@ -24,11 +24,14 @@ use std::io::Write;
 use std::path::Path;
 
 use anyhow::Result;
+use bzip2::write::BzEncoder;
 use flate2::Compression;
 use flate2::write::GzEncoder;
-use isopy_lib::{ArchiveType, InstallPackageOptionsBuilder};
+use isopy_lib::{ArchiveType, StripComponents, UnpackOptions, UnpackOptionsBuilder};
+use rstest::rstest;
 use tar::{Builder as TarBuilder, Header};
 use tempfile::TempDir;
+use xz2::write::XzEncoder;
 use zip::write::{FileOptions, ZipWriter};
 
 const TOP: &str = "cpython-3.14";
@@ -71,22 +74,69 @@ fn build_tar_bytes(entries: &[Entry<'_>]) -> Result<Vec<u8>> {
     Ok(buf)
 }
 
-fn build_tar_gz(path: &Path, entries: &[Entry<'_>]) -> Result<()> {
-    let tar_bytes = build_tar_bytes(entries)?;
+fn build_evil_tar_bytes() -> Vec<u8> {
+    // `tar::Builder::append_data` refuses to write `..` components, so we
+    // construct the header byte-for-byte to produce a malicious archive.
+    let payload = b"owned";
+    let mut header = Header::new_gnu();
+    header.set_size(payload.len() as u64);
+    header.set_mode(0o644);
+    header.set_entry_type(tar::EntryType::Regular);
+    // Write the path directly into the header bytes, bypassing the safety check.
+    let path_bytes = b"top/../../escape.txt";
+    let name_field = &mut header.as_old_mut().name[..];
+    name_field.fill(0);
+    name_field[..path_bytes.len()].copy_from_slice(path_bytes);
+    header.set_cksum();
+
+    let mut tar_bytes = Vec::new();
+    tar_bytes.extend_from_slice(header.as_bytes());
+    tar_bytes.extend_from_slice(payload);
+    // tar records are 512-byte aligned; pad payload and add two empty end blocks.
+    let pad = (512 - (payload.len() % 512)) % 512;
+    tar_bytes.extend(std::iter::repeat_n(0u8, pad));
+    tar_bytes.extend(std::iter::repeat_n(0u8, 1024));
+    tar_bytes
+}
+
+fn write_compressed_tar(path: &Path, archive_type: ArchiveType, tar_bytes: &[u8]) -> Result<()> {
     let file = std::fs::File::create(path)?;
-    let mut encoder = GzEncoder::new(file, Compression::default());
-    encoder.write_all(&tar_bytes)?;
-    encoder.finish()?;
+    match archive_type {
+        ArchiveType::TarBz2 => {
+            let mut encoder = BzEncoder::new(file, bzip2::Compression::default());
+            encoder.write_all(tar_bytes)?;
+            encoder.finish()?;
+        }
+        ArchiveType::TarGz | ArchiveType::Tgz => {
+            let mut encoder = GzEncoder::new(file, Compression::default());
+            encoder.write_all(tar_bytes)?;
+            encoder.finish()?;
+        }
+        ArchiveType::TarXz => {
+            let mut encoder = XzEncoder::new(file, 6);
+            encoder.write_all(tar_bytes)?;
+            encoder.finish()?;
+        }
+        ArchiveType::TarZst => {
+            let mut encoder = zstd::stream::write::Encoder::new(file, 0)?;
+            encoder.write_all(tar_bytes)?;
+            encoder.finish()?;
+        }
+        _ => unreachable!("{archive_type:?} is not a tarball"),
+    }
     Ok(())
 }
 
+fn build_tar(path: &Path, archive_type: ArchiveType, entries: &[Entry<'_>]) -> Result<()> {
+    write_compressed_tar(path, archive_type, &build_tar_bytes(entries)?)
+}
+
+fn build_tar_gz(path: &Path, entries: &[Entry<'_>]) -> Result<()> {
+    build_tar(path, ArchiveType::TarGz, entries)
+}
+
 fn build_tar_zst(path: &Path, entries: &[Entry<'_>]) -> Result<()> {
-    let tar_bytes = build_tar_bytes(entries)?;
-    let file = std::fs::File::create(path)?;
-    let mut encoder = zstd::stream::write::Encoder::new(file, 0)?;
-    encoder.write_all(&tar_bytes)?;
-    encoder.finish()?;
-    Ok(())
+    build_tar(path, ArchiveType::TarZst, entries)
 }
 
 fn build_zip(path: &Path, entries: &[Entry<'_>]) -> Result<()> {
@@ -131,12 +181,86 @@ fn assert_unix_mode(dir: &Path, path: &str, expected: u32) -> Result<()> {
 }
 
 async fn unpack(archive: &Path, out: &Path, archive_type: ArchiveType) -> Result<()> {
-    let options = InstallPackageOptionsBuilder::default()
+    let options = UnpackOptionsBuilder::default()
         .show_progress(false)
         .build()?;
     archive_type.unpack(archive, out, &options).await
 }
 
+fn build_tar_gz_raw(path: &Path, entries: &[Entry<'_>]) -> Result<()> {
+    let mut buf = Vec::new();
+    {
+        let mut builder = TarBuilder::new(&mut buf);
+        for e in entries {
+            let mut header = Header::new_gnu();
+            header.set_size(e.data.len() as u64);
+            header.set_mode(e.mode);
+            header.set_cksum();
+            builder.append_data(&mut header, e.path, e.data)?;
+        }
+        builder.finish()?;
+    }
+    write_compressed_tar(path, ArchiveType::TarGz, &buf)
+}
+
+fn build_zip_raw(path: &Path, entries: &[Entry<'_>]) -> Result<()> {
+    let file = std::fs::File::create(path)?;
+    let mut writer = ZipWriter::new(file);
+    for e in entries {
+        let options = FileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .unix_permissions(e.mode);
+        writer.start_file(e.path, options)?;
+        writer.write_all(e.data)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+fn rootless_entries() -> Vec<Entry<'static>> {
+    vec![
+        Entry {
+            path: "README",
+            data: b"readme",
+            mode: 0o644,
+        },
+        Entry {
+            path: "bin/tool",
+            data: b"tool",
+            mode: 0o755,
+        },
+    ]
+}
+
+fn nested_entries() -> Vec<Entry<'static>> {
+    vec![
+        Entry {
+            path: "python/install/bin/python3",
+            data: b"python",
+            mode: 0o755,
+        },
+        Entry {
+            path: "python/install/lib/libpython.so",
+            data: b"lib",
+            mode: 0o644,
+        },
+        Entry {
+            path: "python/build/config.log",
+            data: b"log",
+            mode: 0o644,
+        },
+    ]
+}
+
+async fn unpack_with_options(
+    archive: &Path,
+    out: &Path,
+    archive_type: ArchiveType,
+    options: &UnpackOptions,
+) -> Result<()> {
+    archive_type.unpack(archive, out, options).await
+}
+
 #[tokio::test]
 async fn tar_gz_extracts_and_strips_top() -> Result<()> {
     let entries = sample_entries();
@@ -193,6 +317,43 @@ async fn zip_extracts_and_strips_top() -> Result<()> {
     Ok(())
 }
 
+#[rstest]
+#[case(ArchiveType::TarBz2)]
+#[case(ArchiveType::TarXz)]
+#[case(ArchiveType::Tgz)]
+#[tokio::test]
+async fn tar_extracts_and_strips_top(#[case] archive_type: ArchiveType) -> Result<()> {
+    let entries = sample_entries();
+    let tmp = TempDir::with_prefix("isopy-lib-unpack-")?;
+    let archive = tmp.path().join(format!("pkg{}", archive_type.suffix()));
+    build_tar(&archive, archive_type, &entries)?;
+
+    let out = tmp.path().join("out");
+    unpack(&archive, &out, archive_type).await?;
+
+    assert_entries_extracted(&out, &entries)?;
+    #[cfg(unix)]
+    assert_unix_mode(&out, "bin/python", 0o755)?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case(ArchiveType::SevenZip)]
+#[case(ArchiveType::Pkg)]
+#[tokio::test]
+async fn rejects_unsupported_archive_type(#[case] archive_type: ArchiveType) -> Result<()> {
+    let tmp = TempDir::with_prefix("isopy-lib-unpack-")?;
+    let archive = tmp.path().join(format!("pkg{}", archive_type.suffix()));
+    std::fs::write(&archive, b"not really an archive")?;
+
+    let out = tmp.path().join("out");
+    let err = unpack(&archive, &out, archive_type).await.unwrap_err();
+    assert!(err.to_string().contains("not supported"), "got: {err}");
+    assert!(!out.exists());
+    Ok(())
+}
+
 #[tokio::test]
 async fn rejects_existing_output_directory() -> Result<()> {
     let entries = sample_entries();
@@ -210,42 +371,20 @@ async fn rejects_existing_output_directory() -> Result<()> {
     Ok(())
 }
 
+#[rstest]
+#[case(ArchiveType::TarBz2)]
+#[case(ArchiveType::TarGz)]
+#[case(ArchiveType::TarXz)]
+#[case(ArchiveType::TarZst)]
+#[case(ArchiveType::Tgz)]
 #[tokio::test]
-async fn rejects_tar_with_parent_dir_traversal() -> Result<()> {
+async fn rejects_tar_with_parent_dir_traversal(#[case] archive_type: ArchiveType) -> Result<()> {
     let tmp = TempDir::with_prefix("isopy-lib-unpack-")?;
-    let archive = tmp.path().join("evil.tar.gz");
-
-    // `tar::Builder::append_data` refuses to write `..` components, so we
-    // construct the header byte-for-byte to produce a malicious archive.
-    let payload = b"owned";
-    let mut header = Header::new_gnu();
-    header.set_size(payload.len() as u64);
-    header.set_mode(0o644);
-    header.set_entry_type(tar::EntryType::Regular);
-    // Write the path directly into the header bytes, bypassing the safety check.
-    let path_bytes = b"top/../../escape.txt";
-    let name_field = &mut header.as_old_mut().name[..];
-    name_field.fill(0);
-    name_field[..path_bytes.len()].copy_from_slice(path_bytes);
-    header.set_cksum();
-
-    let mut tar_bytes = Vec::new();
-    tar_bytes.extend_from_slice(header.as_bytes());
-    tar_bytes.extend_from_slice(payload);
-    // tar records are 512-byte aligned; pad payload and add two empty end blocks.
-    let pad = (512 - (payload.len() % 512)) % 512;
-    tar_bytes.extend(std::iter::repeat_n(0u8, pad));
-    tar_bytes.extend(std::iter::repeat_n(0u8, 1024));
-
-    let file = std::fs::File::create(&archive)?;
-    let mut encoder = GzEncoder::new(file, Compression::default());
-    encoder.write_all(&tar_bytes)?;
-    encoder.finish()?;
+    let archive = tmp.path().join(format!("evil{}", archive_type.suffix()));
+    write_compressed_tar(&archive, archive_type, &build_evil_tar_bytes())?;
 
     let out = tmp.path().join("out");
-    let err = unpack(&archive, &out, ArchiveType::TarGz)
-        .await
-        .unwrap_err();
+    let err = unpack(&archive, &out, archive_type).await.unwrap_err();
     let msg = format!("{err}");
     assert!(msg.contains("..") || msg.contains("outside"), "got: {msg}");
 
@@ -323,3 +462,291 @@ async fn zip_creates_nested_directories() -> Result<()> {
     assert_eq!("deep", read_to_string(out.join("a/b/c/d/deep.txt"))?);
     Ok(())
 }
+
+#[rstest]
+#[case(ArchiveType::TarGz)]
+#[case(ArchiveType::Zip)]
+#[tokio::test]
+async fn auto_strip_keeps_rootless_archive(#[case] archive_type: ArchiveType) -> Result<()> {
+    let entries = rootless_entries();
+    let tmp = TempDir::with_prefix("isopy-lib-unpack-")?;
+    let archive = tmp.path().join(format!("pkg{}", archive_type.suffix()));
+    match archive_type {
+        ArchiveType::Zip => build_zip_raw(&archive, &entries)?,
+        _ => build_tar_gz_raw(&archive, &entries)?,
+    }
+
+    let out = tmp.path().join("out");
+    let options = UnpackOptionsBuilder::default()
+        .strip_components(StripComponents::Auto)
+        .build()?;
+    unpack_with_options(&archive, &out, archive_type, &options).await?;
+
+    assert_eq!("readme", read_to_string(out.join("README"))?);
+    assert_eq!("tool", read_to_string(out.join("bin/tool"))?);
+    Ok(())
+}
+
+#[rstest]
+#[case(ArchiveType::TarGz)]
+#[case(ArchiveType::Zip)]
+#[tokio::test]
+async fn auto_strip_removes_single_root(#[case] archive_type: ArchiveType) -> Result<()> {
+    let entries = sample_entries();
+    let tmp = TempDir::with_prefix("isopy-lib-unpack-")?;
+    let archive = tmp.path().join(format!("pkg{}", archive_type.suffix()));
+    match archive_type {
+        ArchiveType::Zip => build_zip(&archive, &entries)?,
+        _ => build_tar_gz(&archive, &entries)?,
+    }
+
+    let out = tmp.path().join("out");
+    let options = UnpackOptionsBuilder::default()
+        .strip_components(StripComponents::Auto)
+        .build()?;
+    unpack_with_options(&archive, &out, archive_type, &options).await?;
+
+    assert_entries_extracted(&out, &entries)?;
+    Ok(())
+}
+
+#[rstest]
+#[case(ArchiveType::TarGz)]
+#[case(ArchiveType::Zip)]
+#[tokio::test]
+async fn strip_zero_keeps_all_components(#[case] archive_type: ArchiveType) -> Result<()> {
+    let entries = sample_entries();
+    let tmp = TempDir::with_prefix("isopy-lib-unpack-")?;
+    let archive = tmp.path().join(format!("pkg{}", archive_type.suffix()));
+    match archive_type {
+        ArchiveType::Zip => build_zip(&archive, &entries)?,
+        _ => build_tar_gz(&archive, &entries)?,
+    }
+
+    let out = tmp.path().join("out");
+    let options = UnpackOptionsBuilder::default()
+        .strip_components(StripComponents::Count(0))
+        .build()?;
+    unpack_with_options(&archive, &out, archive_type, &options).await?;
+
+    assert!(out.join(TOP).is_dir());
+    for e in &entries {
+        assert_eq!(e.data, read(out.join(TOP).join(e.path))?.as_slice());
+    }
+    Ok(())
+}
+
+#[rstest]
+#[case(ArchiveType::TarGz)]
+#[case(ArchiveType::Zip)]
+#[tokio::test]
+async fn subdir_selects_nested_payload(#[case] archive_type: ArchiveType) -> Result<()> {
+    let entries = nested_entries();
+    let tmp = TempDir::with_prefix("isopy-lib-unpack-")?;
+    let archive = tmp.path().join(format!("pkg{}", archive_type.suffix()));
+    match archive_type {
+        ArchiveType::Zip => build_zip_raw(&archive, &entries)?,
+        _ => build_tar_gz_raw(&archive, &entries)?,
+    }
+
+    let out = tmp.path().join("out");
+    let options = UnpackOptionsBuilder::default()
+        .strip_components(StripComponents::Count(1))
+        .subdir("install")
+        .build()?;
+    unpack_with_options(&archive, &out, archive_type, &options).await?;
+
+    assert_eq!("python", read_to_string(out.join("bin/python3"))?);
+    assert_eq!("lib", read_to_string(out.join("lib/libpython.so"))?);
+    assert!(!out.join("build").exists());
+    assert!(!out.join("install").exists());
+
+    #[cfg(unix)]
+    assert_unix_mode(&out, "bin/python3", 0o755)?;
+
+    Ok(())
+}
+
+enum TarItem<'a> {
+    File(&'a str, &'a [u8]),
+    Symlink(&'a str, &'a str),
+    HardLink(&'a str, &'a str),
+}
+
+fn build_tar_gz_with_links(path: &Path, items: &[TarItem<'_>]) -> Result<()> {
+    let mut buf = Vec::new();
+    {
+        let mut builder = TarBuilder::new(&mut buf);
+        for item in items {
+            let mut header = Header::new_gnu();
+            match item {
+                TarItem::File(name, data) => {
+                    header.set_size(data.len() as u64);
+                    header.set_mode(0o644);
+                    header.set_cksum();
+                    builder.append_data(&mut header, name, *data)?;
+                }
+                TarItem::Symlink(name, target) | TarItem::HardLink(name, target) => {
+                    header.set_entry_type(if matches!(item, TarItem::Symlink(..)) {
+                        tar::EntryType::Symlink
+                    } else {
+                        tar::EntryType::Link
+                    });
+                    header.set_size(0);
+                    header.set_mode(0o777);
+                    header.set_path(name)?;
+                    // Bypass the builder's checks so that malicious targets
+                    // make it into the archive
+                    header.set_link_name_literal(target)?;
+                    header.set_cksum();
+                    builder.append(&header, std::io::empty())?;
+                }
+            }
+        }
+        builder.finish()?;
+    }
+    write_compressed_tar(path, ArchiveType::TarGz, &buf)
+}
+
+async fn unpack_links(items: &[TarItem<'_>], threads: usize) -> Result<(TempDir, Result<()>)> {
+    let tmp = TempDir::with_prefix("isopy-lib-unpack-")?;
+    let archive = tmp.path().join("pkg.tar.gz");
+    build_tar_gz_with_links(&archive, items)?;
+    // Sentinel for tests to check that nothing was written outside `out`
+    std::fs::write(tmp.path().join("escape.txt"), b"original")?;
+    let options = UnpackOptionsBuilder::default().threads(threads).build()?;
+    let result = unpack_with_options(
+        &archive,
+        &tmp.path().join("out"),
+        ArchiveType::TarGz,
+        &options,
+    )
+    .await;
+    Ok((tmp, result))
+}
+
+#[cfg(unix)]
+#[rstest]
+#[tokio::test]
+async fn tar_preserves_relative_symlinks(#[values(1, 4)] threads: usize) -> Result<()> {
+    let (tmp, result) = unpack_links(
+        &[
+            TarItem::File("python/bin/python3.12", b"python"),
+            TarItem::Symlink("python/bin/python3", "python3.12"),
+            TarItem::File("python/lib/libpython3.12.so", b"lib"),
+            TarItem::Symlink("python/bin/libpython.so", "../lib/libpython3.12.so"),
+        ],
+        threads,
+    )
+    .await?;
+    result?;
+
+    let out = tmp.path().join("out");
+    assert_eq!(
+        Path::new("python3.12"),
+        std::fs::read_link(out.join("bin/python3"))?
+    );
+    assert_eq!("python", read_to_string(out.join("bin/python3"))?);
+    assert_eq!("lib", read_to_string(out.join("bin/libpython.so"))?);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[rstest]
+#[tokio::test]
+async fn tar_preserves_hard_links(#[values(1, 4)] threads: usize) -> Result<()> {
+    let (tmp, result) = unpack_links(
+        &[
+            TarItem::File("python/bin/python3.12", b"python"),
+            TarItem::HardLink("python/bin/python3", "python/bin/python3.12"),
+        ],
+        threads,
+    )
+    .await?;
+    result?;
+
+    let out = tmp.path().join("out");
+    assert!(!std::fs::symlink_metadata(out.join("bin/python3"))?.is_symlink());
+    assert_eq!("python", read_to_string(out.join("bin/python3"))?);
+    Ok(())
+}
+
+#[rstest]
+#[case::absolute_symlink(&[TarItem::Symlink("top/evil", "/etc")])]
+#[case::escaping_symlink(&[TarItem::Symlink("top/bin/evil", "../../escape")])]
+#[case::write_through_symlink(&[
+    TarItem::Symlink("top/evil", ".."),
+    TarItem::File("top/evil/escape.txt", b"owned"),
+])]
+#[case::nested_escaping_symlink(&[
+    TarItem::Symlink("top/a/up", ".."),
+    TarItem::Symlink("top/a/up/evil", ".."),
+])]
+#[case::symlink_chain(&[
+    TarItem::Symlink("top/self", "."),
+    TarItem::Symlink("top/evil", "self/.."),
+])]
+#[case::absolute_hard_link(&[TarItem::HardLink("top/evil", "/etc/passwd")])]
+#[case::escaping_hard_link(&[TarItem::HardLink("top/evil", "top/../../escape.txt")])]
+#[case::hard_link_through_symlink(&[
+    TarItem::Symlink("top/up", ".."),
+    TarItem::HardLink("top/evil", "top/up/../escape.txt"),
+])]
+#[tokio::test]
+async fn rejects_tar_with_malicious_links(
+    #[case] items: &[TarItem<'_>],
+    #[values(1, 4)] threads: usize,
+) -> Result<()> {
+    let (tmp, result) = unpack_links(items, threads).await?;
+
+    let err = result.unwrap_err();
+    let msg = format!("{err}");
+    assert!(
+        msg.contains("outside") || msg.contains("absolute") || msg.contains(".."),
+        "got: {msg}"
+    );
+    assert_eq!("original", read_to_string(tmp.path().join("escape.txt"))?);
+    Ok(())
+}
+
+fn many_entries() -> Vec<(String, Vec<u8>)> {
+    (0..200)
+        .map(|i| {
+            (
+                format!("lib/python3.14/pkg{}/module{i}.py", i % 10),
+                format!("# module {i}\n").repeat(i + 1).into_bytes(),
+            )
+        })
+        .collect()
+}
+
+#[rstest]
+#[case(ArchiveType::TarZst)]
+#[case(ArchiveType::Zip)]
+#[tokio::test]
+async fn parallel_extraction_matches_sequential(#[case] archive_type: ArchiveType) -> Result<()> {
+    let contents = many_entries();
+    let entries = contents
+        .iter()
+        .map(|(path, data)| Entry {
+            path,
+            data,
+            mode: 0o644,
+        })
+        .collect::<Vec<_>>();
+    let tmp = TempDir::with_prefix("isopy-lib-unpack-")?;
+    let archive = tmp.path().join(format!("pkg{}", archive_type.suffix()));
+    match archive_type {
+        ArchiveType::Zip => build_zip(&archive, &entries)?,
+        _ => build_tar(&archive, archive_type, &entries)?,
+    }
+
+    for threads in [1, 4] {
+        let out = tmp.path().join(format!("out-{threads}"));
+        let options = UnpackOptionsBuilder::default().threads(threads).build()?;
+        unpack_with_options(&archive, &out, archive_type, &options).await?;
+        assert_entries_extracted(&out, &entries)?;
+    }
+
+    Ok(())
+}
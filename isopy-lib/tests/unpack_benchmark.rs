@@ -0,0 +1,141 @@
+// Copyright (c) 2026 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+// Compares sequential extraction with the parallel path on a synthetic
+// archive. Run with:
+//
+//     cargo test -p isopy-lib --release --test unpack_benchmark -- --ignored --nocapture
+//
+use std::fs::read;
+use std::io::Write;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::thread::available_parallelism;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use isopy_lib::{ArchiveType, UnpackOptionsBuilder};
+use tar::{Builder as TarBuilder, Header};
+use tempfile::TempDir;
+use zip::write::{FileOptions, ZipWriter};
+
+const FILE_COUNT: usize = 4000;
+const FILE_SIZE: usize = 32 * 1024;
+
+fn file_path(i: usize) -> String {
+    format!("python/lib/python3.14/pkg{}/module{i}.py", i % 50)
+}
+
+fn file_data(i: usize) -> Vec<u8> {
+    // Mildly compressible content that differs between files
+    let line = format!("value_{i} = {}  # padding\n", i * 7919);
+    line.repeat(FILE_SIZE / line.len() + 1)
+        .into_bytes()
+        .into_iter()
+        .take(FILE_SIZE)
+        .collect()
+}
+
+fn build_tar_zst(path: &Path) -> Result<()> {
+    let file = std::fs::File::create(path)?;
+    let encoder = zstd::stream::write::Encoder::new(file, 3)?;
+    let mut builder = TarBuilder::new(encoder);
+    for i in 0..FILE_COUNT {
+        let data = file_data(i);
+        let mut header = Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, file_path(i), data.as_slice())?;
+    }
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+fn build_zip(path: &Path) -> Result<()> {
+    let file = std::fs::File::create(path)?;
+    let mut writer = ZipWriter::new(file);
+    for i in 0..FILE_COUNT {
+        let options = FileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .unix_permissions(0o644);
+        writer.start_file(file_path(i), options)?;
+        writer.write_all(&file_data(i))?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+async fn time_unpack(
+    archive: &Path,
+    out: &Path,
+    archive_type: ArchiveType,
+    threads: usize,
+) -> Result<Duration> {
+    let options = UnpackOptionsBuilder::default().threads(threads).build()?;
+    let start = Instant::now();
+    archive_type.unpack(archive, out, &options).await?;
+    Ok(start.elapsed())
+}
+
+fn assert_extracted(dir: &Path) -> Result<()> {
+    for i in (0..FILE_COUNT).step_by(97) {
+        let relative = file_path(i);
+        let relative = relative.strip_prefix("python/").expect("must have prefix");
+        assert_eq!(file_data(i), read(dir.join(relative))?);
+    }
+    Ok(())
+}
+
+async fn compare(archive_type: ArchiveType, build: fn(&Path) -> Result<()>) -> Result<()> {
+    let tmp = TempDir::with_prefix("isopy-lib-unpack-benchmark-")?;
+    let archive = tmp.path().join(format!("pkg{}", archive_type.suffix()));
+    build(&archive)?;
+
+    let sequential_dir = tmp.path().join("sequential");
+    let sequential = time_unpack(&archive, &sequential_dir, archive_type, 1).await?;
+    assert_extracted(&sequential_dir)?;
+
+    let parallel_dir = tmp.path().join("parallel");
+    let parallel = time_unpack(&archive, &parallel_dir, archive_type, 0).await?;
+    assert_extracted(&parallel_dir)?;
+
+    println!(
+        "{suffix}: {FILE_COUNT} files, sequential {sequential:?}, parallel {parallel:?} on {threads} threads ({speedup:.2}x)",
+        suffix = archive_type.suffix(),
+        threads = available_parallelism().map_or(1, NonZeroUsize::get),
+        speedup = sequential.as_secs_f64() / parallel.as_secs_f64()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "benchmark"]
+async fn benchmark_tar_zst() -> Result<()> {
+    compare(ArchiveType::TarZst, build_tar_zst).await
+}
+
+#[tokio::test]
+#[ignore = "benchmark"]
+async fn benchmark_zip() -> Result<()> {
+    compare(ArchiveType::Zip, build_zip).await
+}
@@ -0,0 +1,93 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use serde::{Deserialize, Serialize};
+
+// "lts" is false for non-LTS releases and the release line's codename
+// (e.g. "Iron") otherwise
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Lts {
+    Flag(bool),
+    Codename(String),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Release {
+    #[serde(rename = "version")]
+    pub(crate) version: String,
+
+    #[serde(rename = "date")]
+    pub(crate) date: String,
+
+    #[serde(rename = "files")]
+    pub(crate) files: Vec<String>,
+
+    #[serde(rename = "lts")]
+    pub(crate) lts: Lts,
+}
+
+impl Release {
+    pub(crate) fn lts_codename(&self) -> Option<&str> {
+        match &self.lts {
+            Lts::Flag(_) => None,
+            Lts::Codename(codename) => Some(codename),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::api::Release;
+
+    #[test]
+    fn deserialize() -> Result<()> {
+        let releases = serde_json::from_str::<Vec<Release>>(
+            r#"[
+                {
+                    "version": "v21.6.2",
+                    "date": "2024-02-13",
+                    "files": ["linux-x64", "osx-arm64-tar", "win-x64-zip"],
+                    "npm": "10.2.4",
+                    "lts": false,
+                    "security": false
+                },
+                {
+                    "version": "v20.11.1",
+                    "date": "2024-02-13",
+                    "files": ["linux-arm64", "linux-x64"],
+                    "npm": "10.2.4",
+                    "lts": "Iron",
+                    "security": true
+                }
+            ]"#,
+        )?;
+        assert_eq!(2, releases.len());
+        assert_eq!("v21.6.2", releases[0].version);
+        assert_eq!(None, releases[0].lts_codename());
+        assert_eq!(3, releases[0].files.len());
+        assert_eq!("v20.11.1", releases[1].version);
+        assert_eq!(Some("Iron"), releases[1].lts_codename());
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use isopy_lib::{ArchiveType, PackageInfo, PackageOps, Triple, Version};
+use url::Url;
+
+use crate::node_version::NodeVersion;
+
+pub struct NodePackage {
+    pub(crate) name: String,
+    pub(crate) archive_type: ArchiveType,
+    pub(crate) url: Url,
+    pub(crate) checksums_url: Url,
+    pub(crate) version: Triple,
+    pub(crate) lts_codename: Option<String>,
+    pub(crate) other_version: Version,
+    pub(crate) path: Option<PathBuf>,
+    pub(crate) tags: HashSet<String>,
+}
+
+impl NodePackage {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        name: &str,
+        archive_type: ArchiveType,
+        url: &Url,
+        checksums_url: &Url,
+        version: &Triple,
+        lts_codename: Option<&str>,
+        path: Option<&PathBuf>,
+        tags: Vec<String>,
+    ) -> Self {
+        let version = version.to_owned();
+        let other_version = Version::new(NodeVersion::Release(version.clone()));
+        let tags = tags.into_iter().collect::<HashSet<_>>();
+        Self {
+            name: name.to_owned(),
+            archive_type,
+            url: url.to_owned(),
+            checksums_url: checksums_url.to_owned(),
+            version,
+            lts_codename: lts_codename.map(String::from),
+            other_version,
+            path: path.cloned(),
+            tags,
+        }
+    }
+
+    pub(crate) fn into_package_info(self) -> PackageInfo {
+        let version = PackageOps::version(&self).to_owned();
+        PackageInfo::new(self.name, &self.url, version, self.path)
+    }
+}
+
+impl PackageOps for NodePackage {
+    fn version(&self) -> &Version {
+        &self.other_version
+    }
+
+    fn url(&self) -> &Url {
+        &self.url
+    }
+}
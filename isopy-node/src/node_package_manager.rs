@@ -0,0 +1,365 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use isopy_lib::{
+    Arch, ArchiveType, Checksum, DownloadAssetOptionsBuilder, DownloadPackageOptions,
+    GetPackageOptions, InstallPackageOptions, Libc, ListPackagesOptions, ListTagsOptions, Package,
+    PackageInfo, PackageManagerContext, PackageManagerOps, Platform, PluginError, SourceFilter,
+    TagFilter, Tags, Target, Triple, UnpackOptionsBuilder, UpdateIndexOptions, Version,
+    parse_shasums,
+};
+use log::debug;
+use tokio::fs::read_to_string;
+use url::Url;
+
+use crate::api::Release;
+use crate::node_package::NodePackage;
+use crate::node_version::NodeVersion;
+
+const INDEX_FILE_NAME: &str = "index.json";
+const SHASUMS_FILE_NAME: &str = "SHASUMS256.txt";
+const LTS_TAG: &str = "lts";
+
+// Key in "files" of index entry, OS and architecture in archive file name
+// and archive type of build for given target: nodejs.org only publishes
+// glibc builds for Linux
+const fn node_platform(target: Target) -> Option<(&'static str, [&'static str; 2], ArchiveType)> {
+    Some(match (target.platform, target.arch) {
+        (Platform::Linux, _) if matches!(target.libc, Some(Libc::Musl)) => return None,
+        (Platform::Linux, Arch::Aarch64) => ("linux-arm64", ["linux", "arm64"], ArchiveType::TarXz),
+        (Platform::Linux, Arch::X86_64) => ("linux-x64", ["linux", "x64"], ArchiveType::TarXz),
+        (Platform::MacOS, Arch::Aarch64) => {
+            ("osx-arm64-tar", ["darwin", "arm64"], ArchiveType::TarXz)
+        }
+        (Platform::MacOS, Arch::X86_64) => ("osx-x64-tar", ["darwin", "x64"], ArchiveType::TarXz),
+        (Platform::Windows, Arch::Aarch64) => ("win-arm64-zip", ["win", "arm64"], ArchiveType::Zip),
+        (Platform::Windows, Arch::X86_64) => ("win-x64-zip", ["win", "x64"], ArchiveType::Zip),
+    })
+}
+
+pub struct NodePackageManager {
+    ctx: PackageManagerContext,
+    moniker: String,
+    url: Url,
+}
+
+impl NodePackageManager {
+    pub(crate) fn new(ctx: PackageManagerContext, moniker: &str, url: &Url) -> Self {
+        Self {
+            ctx,
+            moniker: String::from(moniker),
+            url: url.clone(),
+        }
+    }
+
+    fn sort_packages(packages: &mut [NodePackage]) {
+        packages.sort_by_cached_key(|p| p.version.clone());
+        packages.reverse();
+    }
+
+    async fn get_index(&self, update: bool, show_progress: bool) -> Result<Vec<Release>> {
+        let options = DownloadAssetOptionsBuilder::json()
+            .update(update)
+            .show_progress(show_progress)
+            .build()?;
+        let url = self.url.join(INDEX_FILE_NAME)?;
+        let response = self.ctx.download_asset(&url, &options).await?;
+        let s = read_to_string(response.path).await?;
+        let index = serde_json::from_str(&s)?;
+        Ok(index)
+    }
+
+    async fn get_packages(
+        &self,
+        update: bool,
+        show_progress: bool,
+        target: Target,
+    ) -> Result<Vec<NodePackage>> {
+        let Some((key, [os, arch], archive_type)) = node_platform(target) else {
+            debug!("No Node.js builds are available for target {target}");
+            return Ok(Vec::new());
+        };
+
+        let mut packages = Vec::new();
+        for release in self.get_index(update, show_progress).await? {
+            if !release.files.iter().any(|f| f == key) {
+                continue;
+            }
+
+            let Some(version) = release
+                .version
+                .strip_prefix('v')
+                .and_then(|s| s.parse::<Triple>().ok())
+            else {
+                debug!(
+                    "Skipping release with unsupported version {}",
+                    release.version
+                );
+                continue;
+            };
+
+            let file_name = format!(
+                "node-{}-{os}-{arch}{}",
+                release.version,
+                archive_type.suffix()
+            );
+            let url = self.url.join(&format!("{}/{file_name}", release.version))?;
+            let checksums_url = self
+                .url
+                .join(&format!("{}/{SHASUMS_FILE_NAME}", release.version))?;
+            let path = self.ctx.check_asset(&url)?;
+
+            let lts_codename = release.lts_codename();
+            let mut tags = vec![String::from(os), String::from(arch)];
+            if let Some(codename) = lts_codename {
+                tags.push(String::from(LTS_TAG));
+                tags.push(codename.to_lowercase());
+            }
+
+            packages.push(NodePackage::new(
+                &file_name,
+                archive_type,
+                &url,
+                &checksums_url,
+                &version,
+                lts_codename,
+                path.as_ref(),
+                tags,
+            ));
+        }
+        Ok(packages)
+    }
+
+    async fn get_package_inner(
+        &self,
+        update: bool,
+        show_progress: bool,
+        version: &NodeVersion,
+        tag_filter: &TagFilter,
+        target: Target,
+    ) -> Result<NodePackage> {
+        let tags = tag_filter
+            .tags
+            .iter()
+            .map(String::from)
+            .collect::<HashSet<_>>();
+
+        let mut packages = self
+            .get_packages(update, show_progress, target)
+            .await?
+            .into_iter()
+            .filter(|p| {
+                version.matches(&p.version, p.lts_codename.as_deref()) && p.tags.is_superset(&tags)
+            })
+            .collect::<Vec<_>>();
+
+        Self::sort_packages(&mut packages);
+
+        packages.into_iter().next().ok_or_else(|| {
+            PluginError::PackageNotFound {
+                package_id: format!("{}:{version}", self.moniker),
+                tags: tag_filter.tags.clone(),
+            }
+            .into()
+        })
+    }
+
+    async fn get_checksum(&self, package: &NodePackage, show_progress: bool) -> Result<Checksum> {
+        let options = DownloadAssetOptionsBuilder::default()
+            .update(false)
+            .show_progress(show_progress)
+            .build()?;
+        let response = self
+            .ctx
+            .download_asset(&package.checksums_url, &options)
+            .await?;
+        let s = read_to_string(response.path).await?;
+        parse_shasums(&s)?.remove(&package.name).ok_or_else(|| {
+            anyhow!(
+                "No checksum for {} found in {}",
+                package.name,
+                package.checksums_url
+            )
+        })
+    }
+}
+
+#[async_trait]
+impl PackageManagerOps for NodePackageManager {
+    async fn update_index(&self, options: &UpdateIndexOptions) -> Result<()> {
+        self.get_index(true, options.show_progress).await?;
+        Ok(())
+    }
+
+    async fn list_tags(&self, options: &ListTagsOptions) -> Result<Tags> {
+        let Some((_, platform_tags, _)) = node_platform(options.target) else {
+            return Ok(Tags::default());
+        };
+
+        let mut platform_tags = platform_tags
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        platform_tags.sort();
+
+        let mut other_tags = self
+            .get_packages(false, options.show_progress, options.target)
+            .await?
+            .into_iter()
+            .flat_map(|p| p.tags)
+            .filter(|t| !platform_tags.contains(t))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        other_tags.sort();
+
+        Ok(Tags {
+            platform_tags,
+            other_tags,
+        })
+    }
+
+    async fn list_packages(
+        &self,
+        source_filter: SourceFilter,
+        tag_filter: &TagFilter,
+        options: &ListPackagesOptions,
+    ) -> Result<Vec<PackageInfo>> {
+        use isopy_lib::SourceFilter::{All, Local, Remote};
+
+        let tags = tag_filter
+            .tags
+            .iter()
+            .map(String::from)
+            .collect::<HashSet<_>>();
+
+        let mut packages = Vec::new();
+        for p in self
+            .get_packages(false, options.show_progress, options.target)
+            .await?
+        {
+            if p.tags.is_superset(&tags)
+                && matches!(
+                    (source_filter, p.path.is_some()),
+                    (All, _) | (Local, true) | (Remote, false)
+                )
+            {
+                packages.push(p);
+            }
+        }
+
+        Self::sort_packages(&mut packages);
+
+        Ok(packages
+            .into_iter()
+            .map(NodePackage::into_package_info)
+            .collect::<Vec<_>>())
+    }
+
+    async fn get_package(
+        &self,
+        version: &Version,
+        tag_filter: &TagFilter,
+        options: &GetPackageOptions,
+    ) -> Result<Option<PackageInfo>> {
+        let version = isopy_lib::downcast_version!(version, NodeVersion);
+        let package = self
+            .get_package_inner(
+                false,
+                options.show_progress,
+                version,
+                tag_filter,
+                options.target,
+            )
+            .await?;
+        Ok(Some(package.into_package_info()))
+    }
+
+    async fn download_package(
+        &self,
+        version: &Version,
+        tag_filter: &TagFilter,
+        options: &DownloadPackageOptions,
+    ) -> Result<()> {
+        let version = isopy_lib::downcast_version!(version, NodeVersion);
+        let package = self
+            .get_package_inner(
+                false,
+                options.show_progress,
+                version,
+                tag_filter,
+                options.target,
+            )
+            .await?;
+        let checksum = self.get_checksum(&package, options.show_progress).await?;
+        let options = DownloadAssetOptionsBuilder::default()
+            .update(false)
+            .checksum(Some(checksum))
+            .show_progress(options.show_progress)
+            .build()?;
+        _ = self.ctx.download_asset(&package.url, &options).await?;
+        Ok(())
+    }
+
+    async fn install_package(
+        &self,
+        version: &Version,
+        tag_filter: &TagFilter,
+        dir: &Path,
+        options: &InstallPackageOptions,
+    ) -> Result<Package> {
+        let version = isopy_lib::downcast_version!(version, NodeVersion);
+        let package = self
+            .get_package_inner(
+                false,
+                options.show_progress,
+                version,
+                tag_filter,
+                options.target,
+            )
+            .await?;
+
+        let Some(path) = self.ctx.check_asset(&package.url)? else {
+            return Err(PluginError::PackageNotDownloaded {
+                package_id: format!("{}:{version}", self.moniker),
+            }
+            .into());
+        };
+
+        package
+            .archive_type
+            .unpack(
+                &path,
+                dir,
+                &UnpackOptionsBuilder::default()
+                    .show_progress(options.show_progress)
+                    .build()?,
+            )
+            .await?;
+
+        Ok(Package::new(package))
+    }
+}
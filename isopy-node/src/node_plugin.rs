@@ -0,0 +1,154 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::Result;
+use isopy_lib::{
+    EnvInfo, MakeEnvInfoOptions, PackageManager, PackageManagerContext, Platform, Plugin,
+    PluginOps, Shell, Version, render_absolute_path,
+};
+use url::Url;
+
+use crate::node_package_manager::NodePackageManager;
+use crate::node_version::NodeVersion;
+
+static INDEX_URL: LazyLock<Url> = LazyLock::new(|| {
+    "https://nodejs.org/dist/"
+        .parse()
+        .expect("Invalid index URL")
+});
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+const NODE_BIN_FILE_NAME: &str = "node";
+
+#[cfg(target_os = "windows")]
+const NODE_BIN_FILE_NAME: &str = "node.exe";
+
+const NODE_SCRIPT_EXTS: [&str; 2] = ["js", "mjs"];
+
+pub struct NodePlugin {
+    moniker: String,
+}
+
+impl NodePlugin {
+    pub(crate) fn new_plugin(moniker: &str) -> Plugin {
+        Plugin::new(Self {
+            moniker: String::from(moniker),
+        })
+    }
+}
+
+impl PluginOps for NodePlugin {
+    fn url(&self) -> &Url {
+        &INDEX_URL
+    }
+
+    fn parse_version(&self, s: &str) -> Result<Version> {
+        Ok(Version::new(s.parse::<NodeVersion>()?))
+    }
+
+    fn make_env_info(&self, dir: &Path, _options: &MakeEnvInfoOptions) -> EnvInfo {
+        // node, npm and npx live in "bin" except on Windows where they are
+        // at the top level of the archive
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        fn make_path_dirs(dir: &Path) -> Vec<PathBuf> {
+            vec![dir.join("bin")]
+        }
+
+        #[cfg(target_os = "windows")]
+        fn make_path_dirs(dir: &Path) -> Vec<PathBuf> {
+            vec![dir.to_path_buf()]
+        }
+
+        EnvInfo {
+            path_dirs: make_path_dirs(dir),
+            vars: vec![],
+            unset_vars: vec![],
+            path_vars: vec![],
+        }
+    }
+
+    fn make_script_command(
+        &self,
+        script_path: &Path,
+        _platform: Platform,
+        shell: Shell,
+    ) -> Result<Option<OsString>> {
+        let is_node_script = script_path
+            .extension()
+            .and_then(OsStr::to_str)
+            .is_some_and(|ext| NODE_SCRIPT_EXTS.iter().any(|e| e.eq_ignore_ascii_case(ext)));
+        if !is_node_script {
+            return Ok(None);
+        }
+
+        let delimiter: &str = match shell {
+            Shell::Bash | Shell::Fish | Shell::Nushell | Shell::PowerShell | Shell::Zsh => "'",
+            Shell::Cmd => "\"",
+        };
+
+        let mut s = OsString::new();
+        s.push(NODE_BIN_FILE_NAME);
+        s.push(" ");
+        s.push(delimiter);
+        s.push(render_absolute_path(shell, script_path)?);
+        s.push(delimiter);
+        Ok(Some(s))
+    }
+
+    fn new_package_manager(&self, ctx: PackageManagerContext) -> PackageManager {
+        PackageManager::new(NodePackageManager::new(ctx, &self.moniker, &INDEX_URL))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use anyhow::Result;
+    use isopy_lib::{Platform, Shell};
+    use rstest::rstest;
+
+    use crate::node_plugin::NodePlugin;
+
+    #[rstest]
+    #[case(true, "hello.js")]
+    #[case(true, "hello.mjs")]
+    #[case(true, "hello.JS")]
+    #[case(false, "hello.py")]
+    #[case(false, "hello")]
+    fn make_script_command(#[case] expected: bool, #[case] file_name: &str) -> Result<()> {
+        let plugin = NodePlugin::new_plugin("node");
+        let command = plugin.make_script_command(
+            &temp_dir().join(file_name),
+            Platform::Linux,
+            Shell::Bash,
+        )?;
+        assert_eq!(expected, command.is_some());
+        if let Some(command) = command {
+            assert!(command.to_string_lossy().starts_with("node"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::any::Any;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+use anyhow::{Error, bail};
+use isopy_lib::{Triple, VersionOps};
+
+const LTS_PREFIX: &str = "lts/";
+const LTS_LATEST: &str = "*";
+
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum NodeVersion {
+    // Concrete release such as v20.11.1
+    Release(Triple),
+
+    // Latest release of the LTS line with the given lowercase codename, or
+    // of any LTS line if no codename is given, such as lts/iron or lts/*
+    Lts(Option<String>),
+}
+
+impl NodeVersion {
+    pub(crate) fn matches(&self, version: &Triple, lts_codename: Option<&str>) -> bool {
+        match self {
+            Self::Release(triple) => triple == version,
+            Self::Lts(None) => lts_codename.is_some(),
+            Self::Lts(Some(codename)) => {
+                lts_codename.is_some_and(|c| c.eq_ignore_ascii_case(codename))
+            }
+        }
+    }
+}
+
+impl Display for NodeVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Release(triple) => write!(f, "v{triple}"),
+            Self::Lts(None) => write!(f, "{LTS_PREFIX}{LTS_LATEST}"),
+            Self::Lts(Some(codename)) => write!(f, "{LTS_PREFIX}{codename}"),
+        }
+    }
+}
+
+impl FromStr for NodeVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        if let Some(codename) = s.strip_prefix(LTS_PREFIX) {
+            return Ok(if codename == LTS_LATEST {
+                Self::Lts(None)
+            } else if !codename.is_empty() && codename.chars().all(|c| c.is_ascii_alphabetic()) {
+                Self::Lts(Some(codename.to_ascii_lowercase()))
+            } else {
+                bail!("Invalid Node.js LTS codename {codename} in version {s}")
+            });
+        }
+
+        let Some(rest) = s.strip_prefix('v') else {
+            bail!("Invalid Node.js version {s}: expected version such as v20.11.1 or lts/iron")
+        };
+
+        Ok(Self::Release(rest.parse()?))
+    }
+}
+
+impl VersionOps for NodeVersion {
+    fn as_str(&self) -> Cow<'_, String> {
+        Cow::Owned(format!("{self}"))
+    }
+
+    fn label(&self) -> Option<Cow<'_, String>> {
+        None
+    }
+
+    fn box_clone(&self) -> Box<dyn VersionOps> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use isopy_lib::Triple;
+    use rstest::rstest;
+
+    use crate::node_version::NodeVersion;
+
+    #[rstest]
+    #[case(NodeVersion::Release("20.11.1".parse().unwrap()), "v20.11.1", "v20.11.1")]
+    #[case(NodeVersion::Lts(Some(String::from("iron"))), "lts/iron", "lts/iron")]
+    #[case(NodeVersion::Lts(Some(String::from("iron"))), "lts/iron", "lts/Iron")]
+    #[case(NodeVersion::Lts(None), "lts/*", "lts/*")]
+    fn parse_valid(
+        #[case] expected: NodeVersion,
+        #[case] expected_str: &str,
+        #[case] input: &str,
+    ) -> Result<()> {
+        let version = input.parse::<NodeVersion>()?;
+        assert_eq!(expected, version);
+        assert_eq!(expected_str, version.to_string());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("20.11.1")]
+    #[case("v20")]
+    #[case("v20.11")]
+    #[case("v20.11.1.1")]
+    #[case("lts/")]
+    #[case("lts/iron-1")]
+    #[case("node20")]
+    fn parse_invalid(#[case] input: &str) {
+        assert!(input.parse::<NodeVersion>().is_err());
+    }
+
+    #[rstest]
+    #[case(true, "v20.11.1", "20.11.1", Some("Iron"))]
+    #[case(false, "v20.11.0", "20.11.1", Some("Iron"))]
+    #[case(true, "lts/iron", "20.11.1", Some("Iron"))]
+    #[case(false, "lts/hydrogen", "20.11.1", Some("Iron"))]
+    #[case(true, "lts/*", "20.11.1", Some("Iron"))]
+    #[case(false, "lts/*", "21.6.2", None)]
+    #[case(false, "lts/iron", "21.6.2", None)]
+    fn matches(
+        #[case] expected: bool,
+        #[case] input: &str,
+        #[case] release_version: &str,
+        #[case] lts_codename: Option<&str>,
+    ) -> Result<()> {
+        let version = input.parse::<NodeVersion>()?;
+        let release_version = release_version.parse::<Triple>()?;
+        assert_eq!(expected, version.matches(&release_version, lts_codename));
+        Ok(())
+    }
+}
@@ -19,6 +19,10 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
+use std::collections::HashSet;
+
+use anyhow::Result;
+use isopy_lib::PluginError;
 use itertools::Itertools;
 
 use crate::local_package_info::LocalPackageInfo;
@@ -28,31 +32,41 @@ fn sort_infos(infos: &mut [LocalPackageInfo]) {
     infos.reverse();
 }
 
-// On some platforms, most notably Windows, we will get more than one package
-// matching the given tags: these functions will choose the "best" so that
-// there is exactly one matching package for a given version and build label
+// Ranks packages sharing the same version, label and flavor: on Windows we
+// prefer the "shared" library over the default and "static" libraries and
+// full builds may come in several levels of optimization
+fn rank(info: &LocalPackageInfo) -> (u8, u8) {
+    let parts = info
+        .package
+        .metadata
+        .tags
+        .iter()
+        .flat_map(|t| t.split('+'))
+        .collect::<HashSet<_>>();
 
-#[cfg(not(target_os = "windows"))]
-pub(crate) fn choose_best(mut infos: Vec<LocalPackageInfo>) -> Vec<LocalPackageInfo> {
-    sort_infos(&mut infos);
+    let linkage = match (parts.contains("shared"), parts.contains("static")) {
+        (true, _) => 2,
+        (false, false) => 1,
+        (false, true) => 0,
+    };
 
-    for (key, group) in &infos
-        .iter()
-        .chunk_by(|i| i.package.metadata.version.clone())
-    {
-        assert_eq!(
-            1,
-            group.count(),
-            "More than one viable candidate for package {key}"
-        );
-    }
-    infos
+    let optimization = match (parts.contains("pgo"), parts.contains("lto")) {
+        (true, true) => 3,
+        (true, false) => 2,
+        (false, true) => 1,
+        (false, false) => 0,
+    };
+
+    (linkage, optimization)
 }
 
-// On Windows, we prefer the "shared" library over the "static" library and
-// choose the default otherwise
-#[cfg(target_os = "windows")]
-pub(crate) fn choose_best(mut infos: Vec<LocalPackageInfo>) -> Vec<LocalPackageInfo> {
+// We may get more than one package matching the given tags: this function
+// chooses the "best" so that there is exactly one matching package for a
+// given version, build label and flavor
+pub(crate) fn choose_best(
+    moniker: &str,
+    mut infos: Vec<LocalPackageInfo>,
+) -> Result<Vec<LocalPackageInfo>> {
     sort_infos(&mut infos);
 
     let mut best_infos = Vec::new();
@@ -60,51 +74,115 @@ pub(crate) fn choose_best(mut infos: Vec<LocalPackageInfo>) -> Vec<LocalPackageI
         .into_iter()
         .chunk_by(|i| i.package.metadata.version.clone())
     {
-        let infos = group.collect::<Vec<_>>();
-        let info_count = infos.len();
-        assert_ne!(0, info_count);
-
-        if info_count > 1 {
-            let mut temp_shared = None;
-            let mut temp_static = None;
-            let mut temp_default = None;
-
-            for info in infos {
-                let tags = &info.package.metadata.tags;
-                if tags.contains("shared") {
-                    assert!(temp_shared.is_none());
-                    temp_shared = Some(info);
-                } else if tags.contains("static") {
-                    assert!(temp_static.is_none());
-                    temp_static = Some(info);
-                } else {
-                    assert!(temp_default.is_none());
-                    temp_default = Some(info);
-                }
-            }
+        let mut infos = group.collect::<Vec<_>>();
+        infos.sort_by_key(rank);
+        let Some(best) = infos.pop() else {
+            continue;
+        };
 
-            assert!(
-                temp_shared.is_some() || temp_default.is_some() || temp_static.is_some(),
-                "No viable candidate for package {key}"
-            );
-
-            if let Some(info) = temp_shared {
-                best_infos.push(info);
-            } else if let Some(info) = temp_default {
-                best_infos.push(info);
-            } else if let Some(info) = temp_static {
-                best_infos.push(info);
-            } else {
-                unreachable!()
+        let mut candidates = infos
+            .iter()
+            .filter(|i| {
+                rank(i) == rank(&best) && i.package.metadata.name != best.package.metadata.name
+            })
+            .map(|i| i.package.metadata.name.clone())
+            .collect::<Vec<_>>();
+        if !candidates.is_empty() {
+            candidates.push(best.package.metadata.name);
+            candidates.sort_unstable();
+            return Err(PluginError::AmbiguousPackage {
+                package_id: format!("{moniker}:{key}"),
+                candidates,
             }
-        } else {
-            let info = infos
-                .into_iter()
-                .next()
-                .expect("Must have exactly one element");
-            best_infos.push(info);
+            .into());
         }
+
+        best_infos.push(best);
+    }
+
+    Ok(best_infos)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rstest::rstest;
+    use url::Url;
+
+    use crate::choose_best::choose_best;
+    use crate::local_package_info::LocalPackageInfo;
+    use crate::python_package::PythonPackage;
+
+    fn make_infos(names: &[&str]) -> Result<Vec<LocalPackageInfo>> {
+        names
+            .iter()
+            .map(|name| {
+                let url = format!("https://example.com/{name}").parse::<Url>()?;
+                Ok(LocalPackageInfo {
+                    package: PythonPackage::new(&url, name.parse()?),
+                    path: None,
+                })
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case(
+        vec!["cpython-3.13.1+20250115-x86_64-pc-windows-msvc-shared-install_only.tar.gz"],
+        vec![
+            "cpython-3.13.1+20250115-x86_64-pc-windows-msvc-static-install_only.tar.gz",
+            "cpython-3.13.1+20250115-x86_64-pc-windows-msvc-shared-install_only.tar.gz",
+        ]
+    )]
+    #[case(
+        vec!["cpython-3.13.1+20250115-x86_64-pc-windows-msvc-install_only.tar.gz"],
+        vec![
+            "cpython-3.13.1+20250115-x86_64-pc-windows-msvc-install_only.tar.gz",
+            "cpython-3.13.1+20250115-x86_64-pc-windows-msvc-static-install_only.tar.gz",
+        ]
+    )]
+    #[case(
+        vec![
+            "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-install_only.tar.gz",
+            "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-pgo+lto-full.tar.zst",
+            "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-pgo-full.tar.zst",
+            "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-lto-full.tar.zst",
+        ],
+        vec![
+            "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-lto-full.tar.zst",
+            "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-noopt-full.tar.zst",
+            "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-pgo-full.tar.zst",
+            "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-pgo+lto-full.tar.zst",
+            "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-install_only.tar.gz",
+        ]
+    )]
+    fn choose_best_basics(
+        #[case] expected_names: Vec<&str>,
+        #[case] names: Vec<&str>,
+    ) -> Result<()> {
+        let infos = choose_best("python", make_infos(&names)?)?;
+        assert_eq!(
+            expected_names,
+            infos
+                .iter()
+                .map(|i| i.package.metadata.name.as_str())
+                .collect::<Vec<_>>()
+        );
+        Ok(())
     }
 
-    best_infos
+    #[test]
+    fn choose_best_ambiguous() -> Result<()> {
+        let names = [
+            "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-install_only.tar.gz",
+            "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-install_only.tar.zst",
+        ];
+        let Err(e) = choose_best("python", make_infos(&names)?) else {
+            panic!("Must fail")
+        };
+        let message = e.to_string();
+        assert!(message.contains(names[0]));
+        assert!(message.contains(names[1]));
+        Ok(())
+    }
 }
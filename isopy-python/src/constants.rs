@@ -22,6 +22,8 @@
 use std::ffi::OsString;
 use std::sync::LazyLock;
 
+use isopy_lib::{Arch, Libc, Platform, Target};
+
 #[cfg(any(target_os = "linux", target_os = "macos"))]
 pub(crate) static PYTHON_BIN_FILE_NAME: LazyLock<OsString> =
     LazyLock::new(|| OsString::from("python3"));
@@ -30,19 +32,27 @@ pub(crate) static PYTHON_BIN_FILE_NAME: LazyLock<OsString> =
 pub(crate) static PYTHON_BIN_FILE_NAME: LazyLock<OsString> =
     LazyLock::new(|| OsString::from("python.exe"));
 
-pub(crate) static PYTHON_SCRIPT_EXT: LazyLock<OsString> = LazyLock::new(|| OsString::from("py"));
-
-#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
-pub(crate) const PLATFORM_TAGS: [&str; 5] = ["aarch64", "unknown", "linux", "gnu", "install_only"];
+pub(crate) const PYLOCK_REQUIREMENTS_FILE_NAME: &str = "isopy-pylock-requirements.txt";
 
-#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
-pub(crate) const PLATFORM_TAGS: [&str; 5] = ["x86_64", "unknown", "linux", "gnu", "install_only"];
+pub(crate) const PYCACHE_DIR_NAME: &str = "pycache";
 
-#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
-pub(crate) const PLATFORM_TAGS: [&str; 4] = ["aarch64", "apple", "darwin", "install_only"];
-
-#[cfg(all(target_os = "macos", target_arch = "x86_64"))]
-pub(crate) const PLATFORM_TAGS: [&str; 4] = ["x86_64", "apple", "darwin", "install_only"];
+pub(crate) static PYTHON_SCRIPT_EXT: LazyLock<OsString> = LazyLock::new(|| OsString::from("py"));
 
-#[cfg(target_os = "windows")]
-pub(crate) const PLATFORM_TAGS: [&str; 5] = ["x86_64", "pc", "windows", "msvc", "install_only"];
+// Tags identifying python-build-standalone builds for the given target
+pub(crate) fn platform_tags(target: Target) -> Vec<&'static str> {
+    let arch = match target.arch {
+        Arch::Aarch64 => "aarch64",
+        Arch::X86_64 => "x86_64",
+    };
+    match target.platform {
+        Platform::Linux => {
+            let libc = match target.libc {
+                Some(Libc::Musl) => "musl",
+                Some(Libc::Gnu) | None => "gnu",
+            };
+            vec![arch, "unknown", "linux", libc]
+        }
+        Platform::MacOS => vec![arch, "apple", "darwin"],
+        Platform::Windows => vec![arch, "pc", "windows", "msvc"],
+    }
+}
@@ -19,7 +19,9 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
-use isopy_lib::Plugin;
+use std::sync::Arc;
+
+use isopy_lib::{Plugin, PluginDescriptor, Stability};
 
 use crate::python_plugin::PythonPlugin;
 
@@ -27,3 +29,14 @@ use crate::python_plugin::PythonPlugin;
 pub fn new_plugin(moniker: &str) -> Plugin {
     PythonPlugin::new_plugin(moniker)
 }
+
+#[must_use]
+pub fn descriptor() -> PluginDescriptor {
+    PluginDescriptor {
+        name: String::from("python"),
+        description: String::from("Python Standalone Builds from GitHub"),
+        stability: Stability::Stable,
+        enable_env_var: None,
+        new_plugin: Arc::new(new_plugin),
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+use anyhow::{Error, bail};
+
+pub(crate) const FREETHREADED_TAG: &str = "freethreaded";
+
+// Build flavours published by python-build-standalone: the order of the
+// variants determines which flavour is preferred when several match
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub(crate) enum Flavor {
+    Debug,
+    Full,
+    Pgo,
+    PgoLto,
+    InstallOnlyStripped,
+    #[default]
+    InstallOnly,
+}
+
+impl Flavor {
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Full => "full",
+            Self::Pgo => "pgo",
+            Self::PgoLto => "pgo+lto",
+            Self::InstallOnlyStripped => "install_only_stripped",
+            Self::InstallOnly => "install_only",
+        }
+    }
+
+    // Full archives contain the build tree with the installation in
+    // python/install
+    pub(crate) const fn is_full_archive(self) -> bool {
+        matches!(self, Self::Debug | Self::Full | Self::Pgo | Self::PgoLto)
+    }
+
+    // Determines flavour and free-threadedness of a package from the tags in
+    // its file name, e.g. "freethreaded+pgo+lto" and "full": archives that
+    // are not install-only are full distributions and Windows builds are
+    // "pgo" without "lto"
+    pub(crate) fn from_tags(tags: &HashSet<String>) -> (Self, bool) {
+        let parts = tags
+            .iter()
+            .flat_map(|t| t.split('+'))
+            .collect::<HashSet<_>>();
+        let freethreaded = parts.contains(FREETHREADED_TAG);
+        let flavor = if parts.contains("install_only") {
+            Self::InstallOnly
+        } else if parts.contains("install_only_stripped") {
+            Self::InstallOnlyStripped
+        } else if parts.contains("debug") {
+            Self::Debug
+        } else if parts.contains("pgo") && parts.contains("lto") {
+            Self::PgoLto
+        } else if parts.contains("pgo") {
+            Self::Pgo
+        } else {
+            Self::Full
+        };
+        (flavor, freethreaded)
+    }
+
+    // Optimized full builds satisfy a request for any full build
+    pub(crate) fn satisfies(self, requested: Self) -> bool {
+        self == requested || (requested == Self::Full && matches!(self, Self::Pgo | Self::PgoLto))
+    }
+}
+
+impl Display for Flavor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Flavor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        Ok(match s {
+            "debug" => Self::Debug,
+            "full" => Self::Full,
+            "pgo" => Self::Pgo,
+            "pgo+lto" => Self::PgoLto,
+            "install_only_stripped" => Self::InstallOnlyStripped,
+            "install_only" => Self::InstallOnly,
+            _ => bail!(
+                "Unsupported Python build flavor {s}: expected one of install_only, install_only_stripped, full, debug, pgo, pgo+lto or freethreaded"
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use anyhow::Result;
+    use rstest::rstest;
+
+    use crate::flavor::Flavor;
+
+    #[rstest]
+    #[case((Flavor::InstallOnly, false), "install_only")]
+    #[case((Flavor::InstallOnlyStripped, false), "install_only_stripped")]
+    #[case((Flavor::InstallOnly, true), "freethreaded install_only")]
+    #[case((Flavor::PgoLto, false), "pgo+lto full")]
+    #[case((Flavor::Pgo, false), "shared pgo full")]
+    #[case((Flavor::PgoLto, true), "freethreaded+pgo+lto full")]
+    #[case((Flavor::Debug, false), "debug full")]
+    #[case((Flavor::Debug, true), "freethreaded+debug full")]
+    #[case((Flavor::Full, false), "noopt full")]
+    #[case((Flavor::Full, false), "")]
+    fn from_tags(#[case] expected_result: (Flavor, bool), #[case] input: &str) {
+        let tags = input
+            .split_whitespace()
+            .map(String::from)
+            .collect::<HashSet<_>>();
+        assert_eq!(expected_result, Flavor::from_tags(&tags));
+    }
+
+    #[rstest]
+    #[case(Flavor::InstallOnly)]
+    #[case(Flavor::InstallOnlyStripped)]
+    #[case(Flavor::Full)]
+    #[case(Flavor::Debug)]
+    #[case(Flavor::Pgo)]
+    #[case(Flavor::PgoLto)]
+    fn round_trip(#[case] flavor: Flavor) -> Result<()> {
+        assert_eq!(flavor, flavor.to_string().parse()?);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("optimized".parse::<Flavor>().is_err());
+    }
+
+    #[test]
+    fn satisfies() {
+        assert!(Flavor::PgoLto.satisfies(Flavor::Full));
+        assert!(Flavor::Pgo.satisfies(Flavor::Full));
+        assert!(!Flavor::Pgo.satisfies(Flavor::PgoLto));
+        assert!(!Flavor::PgoLto.satisfies(Flavor::Pgo));
+        assert!(Flavor::Full.satisfies(Flavor::Full));
+        assert!(!Flavor::Full.satisfies(Flavor::PgoLto));
+        assert!(!Flavor::Debug.satisfies(Flavor::Full));
+        assert!(!Flavor::InstallOnlyStripped.satisfies(Flavor::InstallOnly));
+    }
+}
@@ -24,6 +24,7 @@ mod choose_best;
 mod constants;
 mod discriminant;
 mod entrypoint;
+mod flavor;
 mod index;
 mod index_item;
 mod label;
@@ -32,9 +33,13 @@ mod metadata;
 mod package_cache;
 mod prerelease_info;
 mod prerelease_kind;
+mod project_version;
+mod pylock;
 mod python_package;
 mod python_package_manager;
 mod python_plugin;
 mod python_version;
+mod python_version_requirement;
+mod venv;
 
 pub use entrypoint::*;
@@ -26,6 +26,7 @@ use std::str::FromStr;
 use anyhow::{Error, anyhow, bail};
 use isopy_lib::ArchiveType;
 
+use crate::flavor::Flavor;
 use crate::python_version::PythonVersion;
 
 #[derive(Clone, Debug)]
@@ -50,7 +51,8 @@ impl FromStr for Metadata {
             bail!("Archive {s} is not a valid Python archive")
         }
 
-        let version = PythonVersion::from_tags(&mut tags)?;
+        let mut version = PythonVersion::from_tags(&mut tags)?;
+        (version.flavor, version.freethreaded) = Flavor::from_tags(&tags);
         if let Some(label) = version.label.as_ref() {
             tags.insert(String::from(label.as_str()));
         }
@@ -69,6 +71,7 @@ mod tests {
     use isopy_lib::ArchiveType;
     use rstest::rstest;
 
+    use crate::flavor::Flavor;
     use crate::metadata::Metadata;
 
     #[rstest]
@@ -122,6 +125,38 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[case(
+        Flavor::InstallOnly,
+        false,
+        "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-install_only.tar.gz"
+    )]
+    #[case(
+        Flavor::InstallOnly,
+        true,
+        "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-freethreaded-install_only.tar.gz"
+    )]
+    #[case(
+        Flavor::PgoLto,
+        true,
+        "cpython-3.13.1+20250115-x86_64-unknown-linux-gnu-freethreaded+pgo+lto-full.tar.zst"
+    )]
+    #[case(
+        Flavor::Debug,
+        false,
+        "cpython-3.13.1+20250115-x86_64-pc-windows-msvc-shared-debug-full.tar.zst"
+    )]
+    fn detects_flavor(
+        #[case] expected_flavor: Flavor,
+        #[case] expected_freethreaded: bool,
+        #[case] input: &str,
+    ) -> anyhow::Result<()> {
+        let m: Metadata = input.parse()?;
+        assert_eq!(expected_flavor, m.version.flavor);
+        assert_eq!(expected_freethreaded, m.version.freethreaded);
+        Ok(())
+    }
+
     #[rstest]
     #[case("cpython-3.10.17+20250409-aarch64-apple-darwin-install_only.unknown")]
     #[case("pypy-3.10.17+20250409-aarch64-apple-darwin-install_only.tar.gz")]
@@ -0,0 +1,213 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fs::read_to_string;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use isopy_lib::{ProjectVersion, ProjectVersionSpec, Version, VersionRequirement};
+use log::warn;
+use serde::Deserialize;
+
+use crate::python_version::PythonVersion;
+use crate::python_version_requirement::PythonVersionRequirement;
+
+const PYTHON_VERSION_FILE_NAME: &str = ".python-version";
+const PYPROJECT_FILE_NAME: &str = "pyproject.toml";
+const RUNTIME_FILE_NAME: &str = "runtime.txt";
+
+#[derive(Debug, Deserialize)]
+struct PyProject {
+    #[serde(rename = "project")]
+    project: Option<PyProjectProject>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PyProjectProject {
+    #[serde(rename = "requires-python")]
+    requires_python: Option<String>,
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match read_to_string(path) {
+        Ok(s) => Ok(Some(s)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// Version names as used by pyenv, e.g. "3.12.1" or "3.12": names such as
+// "system" or "pypy3.10" are not available from python-build-standalone
+fn parse_version_name(s: &str) -> Option<ProjectVersionSpec> {
+    if let Ok(version) = s.parse::<PythonVersion>() {
+        return Some(ProjectVersionSpec::Exact(Version::new(version)));
+    }
+
+    let (major, minor) = s.split_once('.')?;
+    if major.parse::<i32>().is_err() || minor.parse::<i32>().is_err() {
+        return None;
+    }
+
+    let requirement = format!("=={s}.*")
+        .parse::<PythonVersionRequirement>()
+        .ok()?;
+    Some(ProjectVersionSpec::Requirement(VersionRequirement::new(
+        requirement,
+    )))
+}
+
+// pyenv uses the first version listed
+fn read_python_version_file(s: &str) -> Option<&str> {
+    s.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+fn read_pyproject(s: &str) -> Result<Option<ProjectVersionSpec>> {
+    let Some(requires_python) = toml::from_str::<PyProject>(s)?
+        .project
+        .and_then(|p| p.requires_python)
+    else {
+        return Ok(None);
+    };
+
+    let requirement = requires_python
+        .parse::<PythonVersionRequirement>()
+        .with_context(|| format!("Invalid requires-python {requires_python}"))?;
+    Ok(Some(ProjectVersionSpec::Requirement(
+        VersionRequirement::new(requirement),
+    )))
+}
+
+// Heroku-style runtime.txt, e.g. "python-3.12.1"
+fn read_runtime(s: &str) -> Option<&str> {
+    s.trim().strip_prefix("python-")
+}
+
+fn detect_from_file(path: &Path, s: &str) -> Result<Option<ProjectVersionSpec>> {
+    let file_name = path.file_name().and_then(|s| s.to_str());
+    let name = match file_name {
+        Some(PYPROJECT_FILE_NAME) => return read_pyproject(s),
+        Some(PYTHON_VERSION_FILE_NAME) => read_python_version_file(s),
+        _ => read_runtime(s),
+    };
+
+    let Some(name) = name else {
+        return Ok(None);
+    };
+
+    let spec = parse_version_name(name);
+    if spec.is_none() {
+        warn!(
+            "Ignoring unsupported Python version {name} in {}",
+            path.display()
+        );
+    }
+    Ok(spec)
+}
+
+// Sources are tried in order of precedence until one yields a version
+pub(crate) fn detect_project_version(dir: &Path) -> Result<Option<ProjectVersion>> {
+    for file_name in [
+        PYTHON_VERSION_FILE_NAME,
+        PYPROJECT_FILE_NAME,
+        RUNTIME_FILE_NAME,
+    ] {
+        let path = dir.join(file_name);
+        let Some(s) = read_optional(&path)? else {
+            continue;
+        };
+
+        let spec = detect_from_file(&path, &s)
+            .with_context(|| format!("Failed to read Python version from {}", path.display()))?;
+        if let Some(spec) = spec {
+            return Ok(Some(ProjectVersion { path, spec }));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::write;
+    use std::path::Path;
+
+    use anyhow::Result;
+    use isopy_lib::{ProjectVersion, ProjectVersionSpec};
+    use rstest::rstest;
+    use tempfile::tempdir;
+
+    use crate::project_version::detect_project_version;
+
+    fn describe(project_version: ProjectVersion) -> (String, String) {
+        let file_name = project_version
+            .path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .map(String::from)
+            .unwrap_or_default();
+        let spec = match project_version.spec {
+            ProjectVersionSpec::Exact(version) => format!("exact {}", version.as_str()),
+            ProjectVersionSpec::Requirement(requirement) => requirement.to_string(),
+        };
+        (file_name, spec)
+    }
+
+    #[rstest]
+    #[case(Some((".python-version", "exact 3.12.1")), &[(".python-version", "3.12.1\n")])]
+    #[case(Some((".python-version", "==3.12.*")), &[(".python-version", "# comment\n3.12\n3.11\n")])]
+    #[case(Some((".python-version", "exact 3.13.0t")), &[(".python-version", "3.13.0t")])]
+    #[case(
+        Some(("pyproject.toml", ">=3.11,<3.13")),
+        &[(".python-version", "system\n"), ("pyproject.toml", "[project]\nname = \"x\"\nrequires-python = \">=3.11,<3.13\"\n")]
+    )]
+    #[case(Some(("runtime.txt", "exact 3.11.4")), &[("pyproject.toml", "[tool.black]\n"), ("runtime.txt", "python-3.11.4\n")])]
+    #[case(None, &[("runtime.txt", "pypy3.10\n")])]
+    #[case(None, &[])]
+    fn detect(
+        #[case] expected: Option<(&str, &str)>,
+        #[case] files: &[(&str, &str)],
+    ) -> Result<()> {
+        let dir = tempdir()?;
+        for (file_name, content) in files {
+            write(dir.path().join(file_name), content)?;
+        }
+        assert_eq!(
+            expected.map(|(a, b)| (String::from(a), String::from(b))),
+            detect_project_version(dir.path())?.map(describe)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn detect_invalid_requires_python() -> Result<()> {
+        let dir = tempdir()?;
+        write(
+            dir.path().join("pyproject.toml"),
+            "[project]\nrequires-python = \"3.12\"\n",
+        )?;
+        assert!(detect_project_version(dir.path()).is_err());
+        assert!(detect_project_version(Path::new("/nonexistent")).is_ok());
+        Ok(())
+    }
+}
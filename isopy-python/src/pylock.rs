@@ -0,0 +1,190 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use serde::Deserialize;
+
+// Hash algorithms accepted by pip's --hash option
+const PIP_HASH_ALGORITHMS: [&str; 3] = ["sha256", "sha384", "sha512"];
+
+#[derive(Debug, Deserialize)]
+struct PyLock {
+    #[serde(rename = "lock-version")]
+    lock_version: String,
+
+    #[serde(rename = "packages", default)]
+    packages: Vec<PyLockPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PyLockPackage {
+    #[serde(rename = "name")]
+    name: String,
+
+    #[serde(rename = "version")]
+    version: Option<String>,
+
+    #[serde(rename = "marker")]
+    marker: Option<String>,
+
+    #[serde(rename = "wheels", default)]
+    wheels: Vec<PyLockArtifact>,
+
+    #[serde(rename = "sdist")]
+    sdist: Option<PyLockArtifact>,
+
+    #[serde(rename = "archive")]
+    archive: Option<PyLockArtifact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PyLockArtifact {
+    #[serde(rename = "url")]
+    url: Option<String>,
+
+    #[serde(rename = "hashes", default)]
+    hashes: BTreeMap<String, String>,
+}
+
+// PEP 751 lock files are named pylock.toml or pylock.<name>.toml
+pub(crate) fn is_pylock(path: &Path) -> bool {
+    path.extension().is_some_and(|s| s == "toml")
+        && path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .is_some_and(|s| s == "pylock" || s.starts_with("pylock."))
+}
+
+// Converts a PEP 751 lock file into a pip requirements file in which every
+// package is pinned and carries the hashes of all of its locked artifacts
+pub(crate) fn pylock_to_requirements(s: &str) -> Result<String> {
+    let lock = toml::from_str::<PyLock>(s)?;
+    if lock.lock_version.split('.').next() != Some("1") {
+        bail!("Unsupported pylock.toml lock-version {}", lock.lock_version);
+    }
+
+    let mut requirements = String::new();
+    for package in &lock.packages {
+        let requirement = if let Some(archive) = &package.archive {
+            let Some(url) = &archive.url else {
+                bail!("Archive for package {} has no URL", package.name);
+            };
+            format!("{} @ {url}", package.name)
+        } else if let Some(version) = &package.version {
+            format!("{}=={version}", package.name)
+        } else {
+            bail!(
+                "Package {} in pylock.toml has no version: only wheel, sdist and archive packages are supported",
+                package.name
+            );
+        };
+
+        let hashes = package
+            .archive
+            .iter()
+            .chain(package.sdist.iter())
+            .chain(package.wheels.iter())
+            .flat_map(|a| a.hashes.iter())
+            .filter(|(algorithm, _)| PIP_HASH_ALGORITHMS.contains(&algorithm.as_str()))
+            .collect::<Vec<_>>();
+        if hashes.is_empty() {
+            bail!(
+                "Package {} in pylock.toml has no supported hashes",
+                package.name
+            );
+        }
+
+        requirements.push_str(&requirement);
+        if let Some(marker) = &package.marker {
+            write!(requirements, " ; {marker}")?;
+        }
+        for (algorithm, value) in hashes {
+            write!(requirements, " --hash={algorithm}:{value}")?;
+        }
+        requirements.push('\n');
+    }
+
+    Ok(requirements)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use anyhow::Result;
+    use rstest::rstest;
+
+    use crate::pylock::{is_pylock, pylock_to_requirements};
+
+    #[rstest]
+    #[case(true, "pylock.toml")]
+    #[case(true, "dir/pylock.dev.toml")]
+    #[case(false, "requirements.txt")]
+    #[case(false, "pyproject.toml")]
+    fn is_pylock_basics(#[case] expected_result: bool, #[case] input: &str) {
+        assert_eq!(expected_result, is_pylock(Path::new(input)));
+    }
+
+    #[test]
+    fn pylock_to_requirements_basics() -> Result<()> {
+        let s = r#"
+lock-version = "1.0"
+created-by = "test"
+
+[[packages]]
+name = "attrs"
+version = "25.1.0"
+sdist = { url = "https://example.com/attrs-25.1.0.tar.gz", hashes = { sha256 = "aaaa" } }
+wheels = [
+    { url = "https://example.com/attrs-25.1.0-py3-none-any.whl", hashes = { sha256 = "bbbb", md5 = "cccc" } },
+]
+
+[[packages]]
+name = "colorama"
+version = "0.4.6"
+marker = "sys_platform == 'win32'"
+wheels = [{ url = "https://example.com/colorama.whl", hashes = { sha512 = "dddd" } }]
+
+[[packages]]
+name = "local"
+archive = { url = "https://example.com/local-1.0.zip", hashes = { sha256 = "eeee" } }
+"#;
+        assert_eq!(
+            "attrs==25.1.0 --hash=sha256:aaaa --hash=sha256:bbbb\n\
+             colorama==0.4.6 ; sys_platform == 'win32' --hash=sha512:dddd\n\
+             local @ https://example.com/local-1.0.zip --hash=sha256:eeee\n",
+            pylock_to_requirements(s)?
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("lock-version = \"2.0\"\n")]
+    #[case("lock-version = \"1.0\"\n[[packages]]\nname = \"a\"\nversion = \"1\"\n")]
+    #[case("lock-version = \"1.0\"\n[[packages]]\nname = \"a\"\ndirectory = { path = \".\" }\n")]
+    fn pylock_to_requirements_rejects(#[case] input: &str) {
+        assert!(pylock_to_requirements(input).is_err());
+    }
+}
@@ -24,21 +24,21 @@ use std::collections::HashSet;
 use std::fs::{metadata, read_to_string};
 use std::path::Path;
 
-use anyhow::{Result, bail};
+use anyhow::{Error, Result};
 use async_trait::async_trait;
 use isopy_lib::{
     Accept, DownloadAssetOptionsBuilder, DownloadPackageOptions,
     DownloadPaginatedAssetOptionsBuilder, DownloadPaginatedAssetResponse, GetPackageOptions,
     InstallPackageOptions, ListPackagesOptions, ListTagsOptions, Package, PackageInfo,
-    PackageManagerContext, PackageManagerOps, SourceFilter, TagFilter, Tags, UpdateIndexOptions,
-    Version, error_for_github_rate_limit,
+    PackageManagerContext, PackageManagerOps, PluginError, SourceFilter, TagFilter, Tags, Target,
+    UnpackOptions, UnpackOptionsBuilder, UpdateIndexOptions, Version, error_for_github_rate_limit,
 };
 use serde_json::Value;
 use url::Url;
 
 use crate::checksum::get_checksum;
 use crate::choose_best::choose_best;
-use crate::constants::PLATFORM_TAGS;
+use crate::constants::platform_tags;
 use crate::index_item::IndexItem;
 use crate::local_package_info::LocalPackageInfo;
 use crate::package_cache::read_package_cache;
@@ -51,7 +51,6 @@ pub(crate) struct PythonPackageManager {
     ctx: PackageManagerContext,
     moniker: String,
     url: Url,
-    platform_tags: HashSet<String>,
 }
 
 impl PythonPackageManager {
@@ -60,11 +59,6 @@ impl PythonPackageManager {
             ctx,
             moniker: moniker.to_owned(),
             url: url.to_owned(),
-            platform_tags: PLATFORM_TAGS
-                .iter()
-                .copied()
-                .map(ToOwned::to_owned)
-                .collect::<HashSet<_>>(),
         }
     }
 
@@ -77,9 +71,10 @@ impl PythonPackageManager {
         let bin_dir = dir.join("bin");
         let link = bin_dir.join("python");
         if !link.exists() {
-            let original = bin_dir.join("python3");
+            // Relative so that the link survives moving the installation
+            let original = Path::new("python3");
             trace!("Creating link {} to {}", link.display(), original.display());
-            symlink(&original, &link)?;
+            symlink(original, &link)?;
             trace!("Created link {} to {}", link.display(), original.display());
         }
         Ok(())
@@ -101,6 +96,23 @@ impl PythonPackageManager {
         Ok(())
     }
 
+    fn package_not_found(&self, version: &PythonVersion, tag_filter: &TagFilter) -> Error {
+        PluginError::PackageNotFound {
+            package_id: format!("{}:{version}", self.moniker),
+            tags: tag_filter.tags.clone(),
+        }
+        .into()
+    }
+
+    fn make_unpack_options(info: &LocalPackageInfo, show_progress: bool) -> Result<UnpackOptions> {
+        let mut builder = UnpackOptionsBuilder::default();
+        builder.show_progress(show_progress);
+        if info.package.metadata.version.flavor.is_full_archive() {
+            builder.subdir("install");
+        }
+        Ok(builder.build()?)
+    }
+
     fn make_package_info(info: LocalPackageInfo) -> PackageInfo {
         PackageInfo::new(
             info.package.metadata.name.clone(),
@@ -170,10 +182,7 @@ impl PythonPackageManager {
         _cache_path: &Path,
         show_progress: bool,
     ) -> Result<Vec<PythonPackage>> {
-        fn get_packages(
-            response: &DownloadPaginatedAssetResponse,
-            platform_tags: &HashSet<String>,
-        ) -> Result<Vec<PythonPackage>> {
+        fn get_packages(response: &DownloadPaginatedAssetResponse) -> Result<Vec<PythonPackage>> {
             fn read_values_in_order(
                 response: &DownloadPaginatedAssetResponse,
             ) -> Result<Vec<Value>> {
@@ -196,37 +205,18 @@ impl PythonPackageManager {
                 Ok(values.into_iter().map(|(_, value)| value).collect())
             }
 
-            fn into_packages(
-                response: &DownloadPaginatedAssetResponse,
-                platform_tags: &HashSet<String>,
-            ) -> Result<Vec<PythonPackage>> {
-                let mut packages = Vec::new();
-                for value in read_values_in_order(response)? {
-                    packages.extend(
-                        PythonPackage::read_all(&IndexItem::new(&value))?
-                            .into_iter()
-                            .filter_map(|p| {
-                                if p.metadata.tags.is_superset(platform_tags) {
-                                    Some(p)
-                                } else {
-                                    None
-                                }
-                            }),
-                    );
-                }
-
-                Ok(packages)
+            let mut packages = Vec::new();
+            for value in read_values_in_order(response)? {
+                packages.extend(PythonPackage::read_all(&IndexItem::new(&value))?);
             }
 
-            into_packages(response, platform_tags)
+            Ok(packages)
         }
 
         let response = self.get_index_response(false, show_progress).await?;
         // Reading and parsing many large JSON pages is CPU- and I/O-bound;
         // push it off the tokio runtime thread so other tasks can progress.
-        let platform_tags = self.platform_tags.clone();
-        let packages =
-            tokio::task::spawn_blocking(move || get_packages(&response, &platform_tags)).await??;
+        let packages = tokio::task::spawn_blocking(move || get_packages(&response)).await??;
         //write_package_cache(cache_path, &packages)?;
         Ok(packages)
     }
@@ -260,12 +250,15 @@ impl PythonPackageManager {
         source_filter: SourceFilter,
         version: Option<&PythonVersion>,
         tag_filter: &TagFilter,
+        target: Target,
     ) -> Result<Vec<LocalPackageInfo>> {
         use isopy_lib::SourceFilter::{All, Local, Remote};
 
         let tags = tag_filter
             .tags
             .iter()
+            .map(String::as_str)
+            .chain(platform_tags(target))
             .map(ToOwned::to_owned)
             .collect::<HashSet<_>>();
         let mut infos = Vec::new();
@@ -287,13 +280,14 @@ impl PythonPackageManager {
         }
 
         // Ensure there is exactly one matching package for a given version and build label
-        Ok(choose_best(infos))
+        choose_best(&self.moniker, infos)
     }
 
     async fn read_package(
         &self,
         version: &PythonVersion,
         tag_filter: &TagFilter,
+        target: Target,
         show_progress: bool,
     ) -> Result<Option<LocalPackageInfo>> {
         Ok(self
@@ -302,6 +296,7 @@ impl PythonPackageManager {
                 SourceFilter::All,
                 Some(version),
                 tag_filter,
+                target,
             )?
             .into_iter()
             .next())
@@ -321,14 +316,14 @@ impl PackageManagerOps for PythonPackageManager {
             other_tags.extend(package.metadata.tags.iter().map(ToOwned::to_owned));
         }
 
-        other_tags.retain(|t| !self.platform_tags.contains(t));
+        let platform_tags = platform_tags(options.target);
+        other_tags.retain(|t| !platform_tags.contains(&t.as_str()));
         let mut other_tags = other_tags.into_iter().collect::<Vec<_>>();
         other_tags.sort();
         let other_tags = other_tags;
 
-        let mut platform_tags = self
-            .platform_tags
-            .iter()
+        let mut platform_tags = platform_tags
+            .into_iter()
             .map(ToOwned::to_owned)
             .collect::<Vec<_>>();
         platform_tags.sort();
@@ -349,6 +344,7 @@ impl PackageManagerOps for PythonPackageManager {
                 source_filter,
                 None,
                 tag_filter,
+                options.target,
             )?
             .into_iter()
             .map(Self::make_package_info)
@@ -363,7 +359,7 @@ impl PackageManagerOps for PythonPackageManager {
     ) -> Result<Option<PackageInfo>> {
         let version = isopy_lib::downcast_version!(version, PythonVersion);
         let package = self
-            .read_package(version, tag_filter, options.show_progress)
+            .read_package(version, tag_filter, options.target, options.show_progress)
             .await?;
         Ok(package.map(Self::make_package_info))
     }
@@ -376,14 +372,10 @@ impl PackageManagerOps for PythonPackageManager {
     ) -> Result<()> {
         let version = isopy_lib::downcast_version!(version, PythonVersion);
         let Some(info) = self
-            .read_package(version, tag_filter, options.show_progress)
+            .read_package(version, tag_filter, options.target, options.show_progress)
             .await?
         else {
-            bail!(
-                "No package with ID {moniker}:{version} and tags {tags:?} found in index",
-                moniker = self.moniker,
-                tags = tag_filter.tags
-            );
+            return Err(self.package_not_found(version, tag_filter));
         };
 
         let checksum = get_checksum(&self.ctx, &info.package, options.show_progress).await?;
@@ -405,28 +397,27 @@ impl PackageManagerOps for PythonPackageManager {
     ) -> Result<Package> {
         let version = isopy_lib::downcast_version!(version, PythonVersion);
         let Some(info) = self
-            .read_package(version, tag_filter, options.show_progress)
+            .read_package(version, tag_filter, options.target, options.show_progress)
             .await?
         else {
-            bail!(
-                "No package with ID {moniker}:{version} and tags {tags:?} found in index",
-                moniker = self.moniker,
-                tags = tag_filter.tags
-            );
+            return Err(self.package_not_found(version, tag_filter));
         };
 
         let Some(path) = &info.path else {
-            bail!(
-                "Package with ID {moniker}:{version} and tags {tags:?} not downloaded: use \"isopy download <PACKAGE-ID>\" or pass \"--download\" to download missing packages",
-                moniker = self.moniker,
-                tags = tag_filter.tags
-            );
+            return Err(PluginError::PackageNotDownloaded {
+                package_id: format!("{}:{version}", self.moniker),
+            }
+            .into());
         };
 
         info.package
             .metadata
             .archive_type
-            .unpack(path, dir, options)
+            .unpack(
+                path,
+                dir,
+                &Self::make_unpack_options(&info, options.show_progress)?,
+            )
             .await?;
 
         Self::on_after_install(dir)?;
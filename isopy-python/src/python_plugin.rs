@@ -24,13 +24,18 @@ use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
 use anyhow::Result;
-use isopy_lib::{DirUrl, EnvInfo, FileUrl, Platform, Shell, render_absolute_path};
-use isopy_lib::{PackageManager, PackageManagerContext, Plugin, PluginOps, Version};
+use isopy_lib::{
+    CreateVenvOptions, DirUrl, EnvInfo, FileUrl, MakeEnvInfoOptions, PackageManager,
+    PackageManagerContext, PathVar, PathVarPosition, Platform, Plugin, PluginOps, ProjectVersion,
+    Shell, Version, render_absolute_path,
+};
 use url::Url;
 
-use crate::constants::{PYTHON_BIN_FILE_NAME, PYTHON_SCRIPT_EXT};
+use crate::constants::{PYCACHE_DIR_NAME, PYTHON_BIN_FILE_NAME, PYTHON_SCRIPT_EXT};
+use crate::project_version::detect_project_version;
 use crate::python_package_manager::PythonPackageManager;
 use crate::python_version::PythonVersion;
+use crate::venv::{create_venv, venv_bin_dir};
 
 static INDEX_URL: LazyLock<FileUrl> = LazyLock::new(|| {
     "https://api.github.com/repos/astral-sh/python-build-standalone/releases"
@@ -65,7 +70,7 @@ impl PluginOps for PythonPlugin {
         Ok(Version::new(s.parse::<PythonVersion>()?))
     }
 
-    fn make_env_info(&self, dir: &Path) -> EnvInfo {
+    fn make_env_info(&self, dir: &Path, options: &MakeEnvInfoOptions) -> EnvInfo {
         #[cfg(any(target_os = "linux", target_os = "macos"))]
         fn make_path_dirs(dir: &Path) -> Vec<PathBuf> {
             vec![dir.join("bin")]
@@ -76,9 +81,84 @@ impl PluginOps for PythonPlugin {
             vec![dir.to_path_buf(), dir.join("Scripts")]
         }
 
-        let path_dirs = make_path_dirs(dir);
-        let vars = vec![];
-        EnvInfo { path_dirs, vars }
+        // Lets native builds find python3.pc and python3-embed.pc
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        fn make_path_vars(dir: &Path) -> Vec<PathVar> {
+            vec![PathVar {
+                name: String::from("PKG_CONFIG_PATH"),
+                position: PathVarPosition::Prepend,
+                dirs: vec![dir.join("lib").join("pkgconfig")],
+            }]
+        }
+
+        #[cfg(target_os = "windows")]
+        fn make_path_vars(_dir: &Path) -> Vec<PathVar> {
+            Vec::new()
+        }
+
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        fn make_user_path_dirs(user_base_dir: &Path) -> Vec<PathBuf> {
+            vec![user_base_dir.join("bin")]
+        }
+
+        // User scripts directory depends on the Python version on Windows
+        #[cfg(target_os = "windows")]
+        fn make_user_path_dirs(_user_base_dir: &Path) -> Vec<PathBuf> {
+            Vec::new()
+        }
+
+        // PYTHONHOME from the calling environment would point the
+        // interpreter at a different standard library
+        let mut path_dirs = Vec::new();
+        let mut vars = Vec::new();
+        let mut unset_vars = vec![String::from("PYTHONHOME")];
+        if let Some(venv_dir) = &options.venv_dir {
+            path_dirs.push(venv_bin_dir(venv_dir));
+            vars.push((
+                String::from("VIRTUAL_ENV"),
+                venv_dir.to_string_lossy().into_owned(),
+            ));
+        } else {
+            unset_vars.push(String::from("VIRTUAL_ENV"));
+        }
+
+        // The workspace provides a user site-packages directory belonging to
+        // the environment so that packages and bytecode are never written
+        // into the installation, which is read-only for shared installs:
+        // pip is told to use it since root can write to read-only directories
+        // and user installs are not allowed in virtual environments
+        if let Some(workspace_dir) = &options.workspace_dir {
+            path_dirs.extend(make_user_path_dirs(workspace_dir));
+            vars.push((
+                String::from("PYTHONUSERBASE"),
+                workspace_dir.to_string_lossy().into_owned(),
+            ));
+            vars.push((
+                String::from("PYTHONPYCACHEPREFIX"),
+                workspace_dir
+                    .join(PYCACHE_DIR_NAME)
+                    .to_string_lossy()
+                    .into_owned(),
+            ));
+            if options.venv_dir.is_none() {
+                vars.push((String::from("PIP_USER"), String::from("1")));
+            }
+        }
+
+        if options.isolated {
+            if options.workspace_dir.is_none() {
+                vars.push((String::from("PYTHONNOUSERSITE"), String::from("1")));
+            }
+            unset_vars.push(String::from("PYTHONPATH"));
+        }
+
+        path_dirs.extend(make_path_dirs(dir));
+        EnvInfo {
+            path_dirs,
+            vars,
+            unset_vars,
+            path_vars: make_path_vars(dir),
+        }
     }
 
     fn make_script_command(
@@ -89,7 +169,7 @@ impl PluginOps for PythonPlugin {
     ) -> Result<Option<OsString>> {
         fn make_command(script_path: &Path, shell: Shell) -> Result<OsString> {
             let delimiter: &str = match shell {
-                Shell::Bash => "'",
+                Shell::Bash | Shell::Fish | Shell::Nushell | Shell::PowerShell | Shell::Zsh => "'",
                 Shell::Cmd => "\"",
             };
 
@@ -121,4 +201,135 @@ impl PluginOps for PythonPlugin {
             INDEX_URL.as_url(),
         ))
     }
+
+    fn create_venv(&self, dir: &Path, venv_dir: &Path, options: &CreateVenvOptions) -> Result<()> {
+        create_venv(dir, venv_dir, options)
+    }
+
+    fn detect_project_version(&self, dir: &Path) -> Result<Option<ProjectVersion>> {
+        detect_project_version(dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use isopy_lib::MakeEnvInfoOptions;
+
+    use crate::python_plugin::PythonPlugin;
+    use crate::venv::venv_bin_dir;
+
+    #[test]
+    fn make_env_info_without_venv() {
+        let plugin = PythonPlugin::new_plugin("python");
+        let env_info = plugin.make_env_info(Path::new("python"), &MakeEnvInfoOptions::default());
+        assert!(!env_info.path_dirs.is_empty());
+        assert!(env_info.vars.is_empty());
+        assert_eq!(
+            vec![String::from("PYTHONHOME"), String::from("VIRTUAL_ENV")],
+            env_info.unset_vars
+        );
+    }
+
+    #[test]
+    fn make_env_info_with_venv() {
+        let plugin = PythonPlugin::new_plugin("python");
+        let venv_dir = PathBuf::from("project").join(".venv");
+        let env_info = plugin.make_env_info(
+            Path::new("python"),
+            &MakeEnvInfoOptions {
+                venv_dir: Some(venv_dir.clone()),
+                workspace_dir: None,
+                isolated: false,
+                ..Default::default()
+            },
+        );
+        assert_eq!(venv_bin_dir(&venv_dir), env_info.path_dirs[0]);
+        assert!(env_info.path_dirs.len() > 1);
+        assert_eq!(
+            vec![(
+                String::from("VIRTUAL_ENV"),
+                venv_dir.to_string_lossy().into_owned()
+            )],
+            env_info.vars
+        );
+        assert_eq!(vec![String::from("PYTHONHOME")], env_info.unset_vars);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn make_env_info_pkg_config_path() {
+        use isopy_lib::{PathVar, PathVarPosition};
+
+        let plugin = PythonPlugin::new_plugin("python");
+        let dir = Path::new("python");
+        let env_info = plugin.make_env_info(dir, &MakeEnvInfoOptions::default());
+        assert_eq!(
+            vec![PathVar {
+                name: String::from("PKG_CONFIG_PATH"),
+                position: PathVarPosition::Prepend,
+                dirs: vec![dir.join("lib").join("pkgconfig")],
+            }],
+            env_info.path_vars
+        );
+    }
+
+    #[test]
+    fn make_env_info_isolated() {
+        let plugin = PythonPlugin::new_plugin("python");
+        let env_info = plugin.make_env_info(
+            Path::new("python"),
+            &MakeEnvInfoOptions {
+                venv_dir: None,
+                workspace_dir: None,
+                isolated: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            vec![(String::from("PYTHONNOUSERSITE"), String::from("1"))],
+            env_info.vars
+        );
+        assert_eq!(
+            vec![
+                String::from("PYTHONHOME"),
+                String::from("VIRTUAL_ENV"),
+                String::from("PYTHONPATH")
+            ],
+            env_info.unset_vars
+        );
+    }
+
+    #[test]
+    fn make_env_info_workspace() {
+        let plugin = PythonPlugin::new_plugin("python");
+        let workspace_dir = PathBuf::from("workspaces").join("python");
+        let env_info = plugin.make_env_info(
+            Path::new("python"),
+            &MakeEnvInfoOptions {
+                venv_dir: None,
+                workspace_dir: Some(workspace_dir.clone()),
+                isolated: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            vec![
+                (
+                    String::from("PYTHONUSERBASE"),
+                    workspace_dir.to_string_lossy().into_owned()
+                ),
+                (
+                    String::from("PYTHONPYCACHEPREFIX"),
+                    workspace_dir.join("pycache").to_string_lossy().into_owned()
+                ),
+                (String::from("PIP_USER"), String::from("1"))
+            ],
+            env_info.vars
+        );
+
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        assert_eq!(workspace_dir.join("bin"), env_info.path_dirs[0]);
+    }
 }
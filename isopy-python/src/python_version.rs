@@ -31,6 +31,7 @@ use isopy_lib::Triple;
 use isopy_lib::VersionOps;
 
 use crate::discriminant::Discriminant;
+use crate::flavor::{FREETHREADED_TAG, Flavor};
 use crate::label::Label;
 use crate::prerelease_kind::PrereleaseKind;
 
@@ -39,6 +40,8 @@ pub(crate) struct PythonVersion {
     pub(crate) triple: Triple,
     pub(crate) discriminant: Discriminant,
     pub(crate) label: Option<Label>,
+    pub(crate) freethreaded: bool,
+    pub(crate) flavor: Flavor,
 }
 
 fn parse_triple_discriminant_helper(s: &str) -> Result<(Triple, Discriminant)> {
@@ -74,52 +77,42 @@ fn parse_triple_discriminant_helper(s: &str) -> Result<(Triple, Discriminant)> {
 
 impl PythonVersion {
     pub(crate) fn from_tags(tags: &mut HashSet<String>) -> Result<Self> {
-        let mut result = None;
         let mut triple_discriminant = None;
         let mut label = None;
         let mut tags_to_remove = Vec::new();
 
         for tag in tags.iter() {
-            if let Some((prefix, suffix)) = tag.split_once('+')
+            // Version and build label are either separate tags or combined
+            // as in "3.14.0a10+20250409"
+            let (temp_triple_discriminant, temp_label) = if let Some((prefix, suffix)) =
+                tag.split_once('+')
                 && let Ok(temp_triple_discriminant) = parse_triple_discriminant_helper(prefix)
-                && let Ok(temp_label) = suffix.parse()
+                && let Ok(temp_label) = suffix.parse::<Label>()
             {
-                assert!(result.is_none() && triple_discriminant.is_none() && label.is_none());
+                (Some(temp_triple_discriminant), Some(temp_label))
+            } else {
+                (parse_triple_discriminant_helper(tag).ok(), tag.parse().ok())
+            };
+
+            if let Some(temp_triple_discriminant) = temp_triple_discriminant {
+                if triple_discriminant.is_some() {
+                    bail!("Found more than one package version in tags {tags:?}")
+                }
+                triple_discriminant = Some(temp_triple_discriminant);
                 tags_to_remove.push(tag.clone());
-                result = Some(Self {
-                    triple: temp_triple_discriminant.0,
-                    discriminant: temp_triple_discriminant.1,
-                    label: Some(temp_label),
-                });
-                break;
             }
 
-            if let Ok(temp_triple_discriminant) = parse_triple_discriminant_helper(tag) {
-                assert!(result.is_none() && triple_discriminant.is_none());
-                tags_to_remove.push(tag.clone());
-                triple_discriminant = Some(temp_triple_discriminant);
+            if let Some(temp_label) = temp_label {
                 if label.is_some() {
-                    break;
+                    bail!("Found more than one package build label in tags {tags:?}")
                 }
-            }
-
-            if let Ok(temp_label) = tag.parse() {
-                assert!(result.is_none() && label.is_none());
-                tags_to_remove.push(tag.clone());
                 label = Some(temp_label);
-                if triple_discriminant.is_some() {
-                    break;
-                }
+                tags_to_remove.push(tag.clone());
             }
         }
 
         for tag in tags_to_remove {
-            assert!(tags.remove(&tag));
-        }
-
-        if let Some(result) = result {
-            assert!(triple_discriminant.is_none() && label.is_none());
-            return Ok(result);
+            tags.remove(&tag);
         }
 
         let Some(triple_discriminant) = triple_discriminant else {
@@ -134,6 +127,8 @@ impl PythonVersion {
             triple: triple_discriminant.0,
             discriminant: triple_discriminant.1,
             label: Some(label),
+            freethreaded: false,
+            flavor: Flavor::default(),
         })
     }
 
@@ -144,6 +139,9 @@ impl PythonVersion {
         if self.discriminant != other.discriminant {
             return false;
         }
+        if self.freethreaded != other.freethreaded || !self.flavor.satisfies(other.flavor) {
+            return false;
+        }
 
         if let Some(other_label) = other.label() {
             match &self.label {
@@ -175,21 +173,61 @@ impl FromStr for PythonVersion {
             None => (s, None),
         };
 
+        let (prefix, flavor_str) = match prefix.split_once('@') {
+            Some((prefix, suffix)) => (prefix, Some(suffix)),
+            None => (prefix, None),
+        };
+
+        let (prefix, mut freethreaded) = match prefix.strip_suffix('t') {
+            Some(prefix) => (prefix, true),
+            None => (prefix, false),
+        };
+
+        // Flavor is given as in python-build-standalone file names, e.g.
+        // "debug" or "freethreaded+pgo+lto"
+        let mut flavor = Flavor::default();
+        if let Some(flavor_str) = flavor_str {
+            let parts = flavor_str
+                .split('+')
+                .filter(|part| {
+                    if *part == FREETHREADED_TAG {
+                        freethreaded = true;
+                        false
+                    } else {
+                        true
+                    }
+                })
+                .collect::<Vec<_>>();
+            if !parts.is_empty() {
+                flavor = parts.join("+").parse()?;
+            }
+        }
+
         let (triple, discriminant) = parse_triple_discriminant_helper(prefix)?;
         Ok(Self {
             triple,
             discriminant,
             label,
+            freethreaded,
+            flavor,
         })
     }
 }
 
 impl VersionOps for PythonVersion {
     fn as_str(&self) -> Cow<'_, String> {
-        match &self.discriminant {
-            Discriminant::Prerelease(d) => Cow::Owned(format!("{}{}", self.triple, d)),
-            Discriminant::None => Cow::Owned(self.triple.to_string()),
+        let mut s = match &self.discriminant {
+            Discriminant::Prerelease(d) => format!("{}{}", self.triple, d),
+            Discriminant::None => self.triple.to_string(),
+        };
+        if self.freethreaded {
+            s.push('t');
+        }
+        if self.flavor != Flavor::default() {
+            s.push('@');
+            s.push_str(self.flavor.as_str());
         }
+        Cow::Owned(s)
     }
 
     fn label(&self) -> Option<Cow<'_, String>> {
@@ -213,6 +251,7 @@ mod tests {
     use rstest::rstest;
 
     use crate::discriminant::Discriminant;
+    use crate::flavor::Flavor;
     use crate::label::Label;
     use crate::prerelease_kind::PrereleaseKind;
     use crate::python_version::{PythonVersion, parse_triple_discriminant_helper};
@@ -273,6 +312,8 @@ mod tests {
     #[case("3.14.0x10")]
     #[case("3.14.0alpha1")]
     #[case("not-a-version")]
+    #[case("3.13.1@fast")]
+    #[case("3.13.1tt")]
     fn from_str_invalid(#[case] input: &str) {
         assert!(input.parse::<PythonVersion>().is_err());
     }
@@ -285,6 +326,11 @@ mod tests {
     #[case("1.2.3:20250414")]
     #[case("3.14.0rc2:20250414")]
     #[case("3.14.0a10:20250414T1530")]
+    #[case("3.13.1t")]
+    #[case("3.14.0rc1t:20250414")]
+    #[case("3.13.1@debug")]
+    #[case("3.13.1@install_only_stripped")]
+    #[case("3.13.1t@pgo+lto:20250414")]
     fn display_round_trip(#[case] input: &str) -> Result<()> {
         let parsed: PythonVersion = input.parse()?;
         assert_eq!(input, parsed.to_string());
@@ -359,4 +405,49 @@ mod tests {
         assert_eq!(expected_label, result.label);
         Ok(())
     }
+
+    #[rstest]
+    #[case(false, Flavor::InstallOnly, "3.13.1")]
+    #[case(true, Flavor::InstallOnly, "3.13.1t")]
+    #[case(true, Flavor::InstallOnly, "3.13.1@freethreaded")]
+    #[case(true, Flavor::Debug, "3.13.1@freethreaded+debug")]
+    #[case(false, Flavor::PgoLto, "3.13.1@pgo+lto")]
+    #[case(false, Flavor::Full, "3.13.1@full:20250414")]
+    fn from_str_flavor(
+        #[case] expected_freethreaded: bool,
+        #[case] expected_flavor: Flavor,
+        #[case] input: &str,
+    ) -> Result<()> {
+        let version = input.parse::<PythonVersion>()?;
+        assert_eq!(expected_freethreaded, version.freethreaded);
+        assert_eq!(expected_flavor, version.flavor);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(true, "3.13.1t@pgo+lto", "3.13.1t@full")]
+    #[case(true, "3.13.1t@full", "3.13.1t@full")]
+    #[case(false, "3.13.1t", "3.13.1")]
+    #[case(false, "3.13.1", "3.13.1t")]
+    #[case(false, "3.13.1@debug", "3.13.1")]
+    #[case(false, "3.13.1@full", "3.13.1@pgo+lto")]
+    fn matches_flavor(
+        #[case] expected_result: bool,
+        #[case] lhs: &str,
+        #[case] rhs: &str,
+    ) -> Result<()> {
+        let lhs = lhs.parse::<PythonVersion>()?;
+        let rhs = rhs.parse::<PythonVersion>()?;
+        assert_eq!(expected_result, lhs.matches(&rhs));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("3.13.1 3.13.2 20250115")]
+    #[case("3.13.1+20250115 3.13.2")]
+    #[case("3.13.1 20250115 20250116")]
+    fn from_tags_ambiguous(#[case] input: &str) {
+        let mut tags = input.split_whitespace().map(String::from).collect();
+        assert!(PythonVersion::from_tags(&mut tags).is_err());
+    }
 }
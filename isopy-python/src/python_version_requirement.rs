@@ -0,0 +1,291 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+use anyhow::{Error, anyhow, bail};
+use isopy_lib::{Triple, Version, VersionRequirementOps};
+
+use crate::discriminant::Discriminant;
+use crate::flavor::Flavor;
+use crate::python_version::PythonVersion;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operator {
+    Compatible,
+    Equal,
+    NotEqual,
+    LessEqual,
+    GreaterEqual,
+    Less,
+    Greater,
+    Arbitrary,
+}
+
+// Subset of PEP 440 version specifiers sufficient for "requires-python":
+// only final releases are considered
+#[derive(Debug, PartialEq)]
+struct Specifier {
+    operator: Operator,
+    release: Vec<i32>,
+    wildcard: bool,
+    // Text following the operator, compared verbatim by "==="
+    operand: String,
+    raw: String,
+}
+
+impl Specifier {
+    fn matches(&self, triple: &Triple) -> bool {
+        let version = [triple.major, triple.minor, triple.revision];
+        match self.operator {
+            Operator::Compatible => {
+                compare(&version, &self.release).is_ge()
+                    && is_prefix(&self.release[..self.release.len() - 1], &version)
+            }
+            Operator::Equal if self.wildcard => is_prefix(&self.release, &version),
+            Operator::Equal => compare(&version, &self.release).is_eq(),
+            Operator::NotEqual if self.wildcard => !is_prefix(&self.release, &version),
+            Operator::NotEqual => compare(&version, &self.release).is_ne(),
+            Operator::LessEqual => compare(&version, &self.release).is_le(),
+            Operator::GreaterEqual => compare(&version, &self.release).is_ge(),
+            Operator::Less => compare(&version, &self.release).is_lt(),
+            Operator::Greater => compare(&version, &self.release).is_gt(),
+            Operator::Arbitrary => triple.to_string() == self.operand,
+        }
+    }
+}
+
+// Compares release segments, padding the shorter with zeros
+fn compare(lhs: &[i32], rhs: &[i32]) -> Ordering {
+    (0..lhs.len().max(rhs.len()))
+        .map(|i| {
+            let lhs_value = lhs.get(i).copied().unwrap_or(0);
+            let rhs_value = rhs.get(i).copied().unwrap_or(0);
+            lhs_value.cmp(&rhs_value)
+        })
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+fn is_prefix(prefix: &[i32], version: &[i32]) -> bool {
+    compare(prefix, &version[..prefix.len().min(version.len())]).is_eq()
+}
+
+impl FromStr for Specifier {
+    type Err = Error;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        let s = s.trim();
+        let (operator, rest) = [
+            ("~=", Operator::Compatible),
+            ("===", Operator::Arbitrary),
+            ("==", Operator::Equal),
+            ("!=", Operator::NotEqual),
+            ("<=", Operator::LessEqual),
+            (">=", Operator::GreaterEqual),
+            ("<", Operator::Less),
+            (">", Operator::Greater),
+        ]
+        .into_iter()
+        .find_map(|(prefix, operator)| s.strip_prefix(prefix).map(|rest| (operator, rest)))
+        .ok_or_else(|| anyhow!("Invalid version specifier {s}"))?;
+
+        let rest = rest.trim();
+        if operator == Operator::Arbitrary {
+            return Ok(Self {
+                operator,
+                release: Vec::new(),
+                wildcard: false,
+                operand: String::from(rest),
+                raw: String::from(s),
+            });
+        }
+
+        let (version_str, wildcard) = match rest.strip_suffix(".*") {
+            Some(prefix) => (prefix, true),
+            None => (rest, false),
+        };
+
+        if wildcard && !matches!(operator, Operator::Equal | Operator::NotEqual) {
+            bail!("Wildcard not allowed in version specifier {s}")
+        }
+
+        let Ok(release) = version_str
+            .split('.')
+            .map(str::parse)
+            .collect::<StdResult<Vec<i32>, _>>()
+        else {
+            bail!("Unsupported version in version specifier {s}")
+        };
+
+        if operator == Operator::Compatible && release.len() < 2 {
+            bail!("Compatible release specifier {s} requires at least two components")
+        }
+
+        Ok(Self {
+            operator,
+            release,
+            wildcard,
+            operand: String::from(rest),
+            raw: String::from(s),
+        })
+    }
+}
+
+// Comma-separated version specifiers such as ">=3.11,<3.13", all of which
+// must match
+pub(crate) struct PythonVersionRequirement {
+    specifiers: Vec<Specifier>,
+}
+
+impl PythonVersionRequirement {
+    fn matches(&self, version: &PythonVersion) -> bool {
+        version.discriminant == Discriminant::None
+            && !version.freethreaded
+            && version.flavor == Flavor::default()
+            && self.specifiers.iter().all(|s| s.matches(&version.triple))
+    }
+}
+
+impl Display for PythonVersionRequirement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let s = self
+            .specifiers
+            .iter()
+            .map(|s| s.raw.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, "{s}")
+    }
+}
+
+impl FromStr for PythonVersionRequirement {
+    type Err = Error;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        let specifiers = s
+            .split(',')
+            .map(str::parse)
+            .collect::<StdResult<Vec<_>, _>>()?;
+        Ok(Self { specifiers })
+    }
+}
+
+impl VersionRequirementOps for PythonVersionRequirement {
+    // The build label is dropped so that the best build of the chosen version
+    // is selected at installation time
+    fn choose(&self, versions: &[Version]) -> Option<Version> {
+        versions
+            .iter()
+            .filter_map(|v| v.as_any().downcast_ref::<PythonVersion>())
+            .filter(|v| self.matches(v))
+            .map(|v| &v.triple)
+            .max()
+            .map(|triple| {
+                Version::new(PythonVersion {
+                    triple: triple.clone(),
+                    discriminant: Discriminant::None,
+                    label: None,
+                    freethreaded: false,
+                    flavor: Flavor::default(),
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use isopy_lib::{Version, VersionRequirementOps};
+    use rstest::rstest;
+
+    use crate::python_version::PythonVersion;
+    use crate::python_version_requirement::PythonVersionRequirement;
+
+    #[rstest]
+    #[case(true, ">=3.11", "3.11.0")]
+    #[case(false, ">=3.11", "3.10.14")]
+    #[case(true, ">=3.11,<3.13", "3.12.9")]
+    #[case(false, ">=3.11,<3.13", "3.13.0")]
+    #[case(true, "==3.12.*", "3.12.4")]
+    #[case(false, "==3.12.*", "3.13.0")]
+    #[case(true, "!=3.12.*", "3.13.0")]
+    #[case(true, "== 3.12", "3.12.0")]
+    #[case(false, "==3.12", "3.12.1")]
+    #[case(true, "~=3.10", "3.13.1")]
+    #[case(false, "~=3.10", "3.9.18")]
+    #[case(true, "~=3.10.2", "3.10.14")]
+    #[case(false, "~=3.10.2", "3.11.0")]
+    #[case(true, ">3.8, !=3.9.1, <=3.9.2", "3.9.2")]
+    #[case(false, ">3.8, !=3.9.1, <=3.9.2", "3.9.1")]
+    #[case(true, "===3.12.1", "3.12.1")]
+    fn matches(#[case] expected: bool, #[case] input: &str, #[case] version: &str) -> Result<()> {
+        let requirement = input.parse::<PythonVersionRequirement>()?;
+        assert_eq!(expected, requirement.matches(&version.parse()?));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(">=3.11,<3.13")]
+    #[case("==3.12.*")]
+    #[case("~=3.10")]
+    #[case("===3.12.1")]
+    fn display(#[case] input: &str) -> Result<()> {
+        assert_eq!(
+            input,
+            input.parse::<PythonVersionRequirement>()?.to_string()
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("3.12")]
+    #[case(">=3.12.*")]
+    #[case("~=3")]
+    #[case(">=3.12a1")]
+    fn parse_invalid(#[case] input: &str) {
+        assert!(input.parse::<PythonVersionRequirement>().is_err());
+    }
+
+    #[test]
+    fn choose() -> Result<()> {
+        let versions = [
+            "3.13.0rc1:20240909",
+            "3.12.5:20240814",
+            "3.12.4:20240726",
+            "3.12.4:20240713",
+            "3.12.9t:20250212",
+            "3.11.9:20240814",
+        ]
+        .into_iter()
+        .map(|s| Ok(Version::new(s.parse::<PythonVersion>()?)))
+        .collect::<Result<Vec<_>>>()?;
+        let requirement = ">=3.11,<3.13".parse::<PythonVersionRequirement>()?;
+        let version = requirement.choose(&versions).expect("must match");
+        assert_eq!("3.12.5", version.as_str().as_str());
+        assert!(version.label().is_none());
+        Ok(())
+    }
+}
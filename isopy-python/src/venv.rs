@@ -0,0 +1,118 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fs::{read_to_string, write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use isopy_lib::CreateVenvOptions;
+use log::info;
+
+use crate::constants::{PYLOCK_REQUIREMENTS_FILE_NAME, PYTHON_BIN_FILE_NAME};
+use crate::pylock::{is_pylock, pylock_to_requirements};
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub(crate) fn venv_bin_dir(venv_dir: &Path) -> PathBuf {
+    venv_dir.join("bin")
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) fn venv_bin_dir(venv_dir: &Path) -> PathBuf {
+    venv_dir.join("Scripts")
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn interpreter_path(dir: &Path) -> PathBuf {
+    dir.join("bin").join(&*PYTHON_BIN_FILE_NAME)
+}
+
+#[cfg(target_os = "windows")]
+fn interpreter_path(dir: &Path) -> PathBuf {
+    dir.join(&*PYTHON_BIN_FILE_NAME)
+}
+
+pub(crate) fn create_venv(dir: &Path, venv_dir: &Path, options: &CreateVenvOptions) -> Result<()> {
+    let mut command = Command::new(interpreter_path(dir));
+    command.arg("-m").arg("venv");
+    if options.system_site_packages {
+        command.arg("--system-site-packages");
+    }
+    command.arg(venv_dir);
+    run(&mut command)?;
+
+    let venv_python = venv_bin_dir(venv_dir).join(&*PYTHON_BIN_FILE_NAME);
+    for requirements_path in &options.requirements {
+        let mut command = Command::new(&venv_python);
+        command
+            .arg("-m")
+            .arg("pip")
+            .arg("install")
+            .arg("--requirement")
+            .arg(requirements_path);
+        run(&mut command)?;
+    }
+
+    if let Some(lock_path) = &options.lock {
+        install_lock(&venv_python, venv_dir, lock_path)?;
+    }
+
+    info!("Created virtual environment {}", venv_dir.display());
+    Ok(())
+}
+
+// Lock files are installed without dependency resolution: every package must
+// be pinned and hashed so that the environment is reproducible
+fn install_lock(venv_python: &Path, venv_dir: &Path, lock_path: &Path) -> Result<()> {
+    let requirements_path = if is_pylock(lock_path) {
+        let s = read_to_string(lock_path)
+            .with_context(|| format!("Failed to read lock file {}", lock_path.display()))?;
+        let requirements = pylock_to_requirements(&s)
+            .with_context(|| format!("Invalid lock file {}", lock_path.display()))?;
+        let requirements_path = venv_dir.join(PYLOCK_REQUIREMENTS_FILE_NAME);
+        write(&requirements_path, requirements)?;
+        requirements_path
+    } else {
+        lock_path.to_path_buf()
+    };
+
+    let mut command = Command::new(venv_python);
+    command
+        .arg("-m")
+        .arg("pip")
+        .arg("install")
+        .arg("--require-hashes")
+        .arg("--no-deps")
+        .arg("--requirement")
+        .arg(&requirements_path);
+    run(&mut command)
+}
+
+fn run(command: &mut Command) -> Result<()> {
+    let status = command.status()?;
+    if !status.success() {
+        bail!(
+            "Command {} failed with {status}",
+            command.get_program().display()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,231 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+use isopy_lib::{Arch, ArchiveType, Checksum, Libc, Platform, Target};
+use serde::Deserialize;
+use url::Url;
+
+// Components making up a minimal toolchain for the host: the directory name
+// of rust-std inside its archive includes the target triple
+const COMPONENT_NAMES: [&str; 3] = ["rustc", "cargo", "rust-std"];
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Manifest {
+    #[allow(clippy::struct_field_names)]
+    #[serde(rename = "manifest-version")]
+    pub(crate) manifest_version: String,
+
+    #[serde(rename = "date")]
+    pub(crate) date: String,
+
+    #[serde(rename = "pkg")]
+    pub(crate) pkg: HashMap<String, ManifestPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ManifestPackage {
+    #[serde(rename = "target")]
+    pub(crate) target: HashMap<String, ManifestTarget>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ManifestTarget {
+    #[serde(rename = "available")]
+    pub(crate) available: bool,
+
+    #[serde(rename = "url")]
+    pub(crate) url: Option<Url>,
+
+    #[serde(rename = "hash")]
+    pub(crate) hash: Option<String>,
+
+    #[serde(rename = "xz_url")]
+    pub(crate) xz_url: Option<Url>,
+
+    #[serde(rename = "xz_hash")]
+    pub(crate) xz_hash: Option<String>,
+}
+
+pub(crate) struct Component {
+    pub(crate) dir_name: String,
+    pub(crate) url: Url,
+    pub(crate) checksum: Checksum,
+    pub(crate) archive_type: ArchiveType,
+}
+
+impl Manifest {
+    pub(crate) fn parse(s: &str) -> Result<Self> {
+        let manifest = toml::from_str::<Self>(s)?;
+        if manifest.manifest_version != "2" {
+            bail!(
+                "Unsupported Rust channel manifest version {}",
+                manifest.manifest_version
+            )
+        }
+        Ok(manifest)
+    }
+
+    // Selects archives of toolchain components for given target triple,
+    // preferring .tar.xz over .tar.gz
+    pub(crate) fn components(&self, target_triple: &str) -> Result<Vec<Component>> {
+        COMPONENT_NAMES
+            .into_iter()
+            .map(|name| {
+                let Some(target) = self
+                    .pkg
+                    .get(name)
+                    .and_then(|p| p.target.get(target_triple))
+                    .filter(|t| t.available)
+                else {
+                    bail!(
+                        "Component {name} is not available for target {target_triple} in manifest dated {}",
+                        self.date
+                    )
+                };
+
+                let (url, hash, archive_type) = match (&target.xz_url, &target.xz_hash) {
+                    (Some(url), Some(hash)) => (url, hash, ArchiveType::TarXz),
+                    _ => match (&target.url, &target.hash) {
+                        (Some(url), Some(hash)) => (url, hash, ArchiveType::TarGz),
+                        _ => bail!("Component {name} has no archive for target {target_triple}"),
+                    },
+                };
+
+                let dir_name = if name == "rust-std" {
+                    format!("{name}-{target_triple}")
+                } else {
+                    String::from(name)
+                };
+
+                Ok(Component {
+                    dir_name,
+                    url: url.clone(),
+                    checksum: hash.parse()?,
+                    archive_type,
+                })
+            })
+            .collect()
+    }
+}
+
+pub(crate) const fn target_triple(target: Target) -> &'static str {
+    match (target.platform, target.arch, target.libc) {
+        (Platform::Linux, Arch::Aarch64, Some(Libc::Musl)) => "aarch64-unknown-linux-musl",
+        (Platform::Linux, Arch::Aarch64, _) => "aarch64-unknown-linux-gnu",
+        (Platform::Linux, Arch::X86_64, Some(Libc::Musl)) => "x86_64-unknown-linux-musl",
+        (Platform::Linux, Arch::X86_64, _) => "x86_64-unknown-linux-gnu",
+        (Platform::MacOS, Arch::Aarch64, _) => "aarch64-apple-darwin",
+        (Platform::MacOS, Arch::X86_64, _) => "x86_64-apple-darwin",
+        (Platform::Windows, Arch::Aarch64, _) => "aarch64-pc-windows-msvc",
+        (Platform::Windows, Arch::X86_64, _) => "x86_64-pc-windows-msvc",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use isopy_lib::ArchiveType;
+
+    use crate::manifest::Manifest;
+
+    const MANIFEST: &str = r#"
+manifest-version = "2"
+date = "2024-05-02"
+
+[pkg.cargo]
+version = "1.78.0 (54d8815d0 2024-03-26)"
+
+[pkg.cargo.target.x86_64-unknown-linux-gnu]
+available = true
+url = "https://static.rust-lang.org/dist/2024-05-02/cargo-1.78.0-x86_64-unknown-linux-gnu.tar.gz"
+hash = "0e2f2b3b2e5c5d6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f"
+xz_url = "https://static.rust-lang.org/dist/2024-05-02/cargo-1.78.0-x86_64-unknown-linux-gnu.tar.xz"
+xz_hash = "1e2f2b3b2e5c5d6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f"
+
+[pkg.cargo.target.riscv64gc-unknown-linux-gnu]
+available = false
+
+[pkg.rust-std]
+version = "1.78.0 (9b00956e5 2024-04-29)"
+
+[pkg.rust-std.target.x86_64-unknown-linux-gnu]
+available = true
+url = "https://static.rust-lang.org/dist/2024-05-02/rust-std-1.78.0-x86_64-unknown-linux-gnu.tar.gz"
+hash = "2e2f2b3b2e5c5d6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f"
+
+[pkg.rustc]
+version = "1.78.0 (9b00956e5 2024-04-29)"
+
+[pkg.rustc.target.x86_64-unknown-linux-gnu]
+available = true
+url = "https://static.rust-lang.org/dist/2024-05-02/rustc-1.78.0-x86_64-unknown-linux-gnu.tar.gz"
+hash = "3e2f2b3b2e5c5d6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f"
+xz_url = "https://static.rust-lang.org/dist/2024-05-02/rustc-1.78.0-x86_64-unknown-linux-gnu.tar.xz"
+xz_hash = "4e2f2b3b2e5c5d6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f"
+
+[renames.rls]
+to = "rls-preview"
+"#;
+
+    #[test]
+    fn components() -> Result<()> {
+        let manifest = Manifest::parse(MANIFEST)?;
+        let components = manifest.components("x86_64-unknown-linux-gnu")?;
+        assert_eq!(
+            vec!["rustc", "cargo", "rust-std-x86_64-unknown-linux-gnu"],
+            components
+                .iter()
+                .map(|c| c.dir_name.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert!(matches!(components[0].archive_type, ArchiveType::TarXz));
+        assert!(
+            components[0]
+                .url
+                .as_str()
+                .ends_with("rustc-1.78.0-x86_64-unknown-linux-gnu.tar.xz")
+        );
+        assert_eq!(
+            "4e2f2b3b2e5c5d6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f",
+            components[0].checksum.to_string()
+        );
+        assert!(matches!(components[2].archive_type, ArchiveType::TarGz));
+        Ok(())
+    }
+
+    #[test]
+    fn components_unavailable() -> Result<()> {
+        let manifest = Manifest::parse(MANIFEST)?;
+        assert!(manifest.components("riscv64gc-unknown-linux-gnu").is_err());
+        assert!(manifest.components("aarch64-apple-darwin").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_manifest_version() {
+        assert!(
+            Manifest::parse("manifest-version = \"1\"\ndate = \"2024-05-02\"\n[pkg]\n").is_err()
+        );
+    }
+}
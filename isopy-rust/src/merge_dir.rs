@@ -0,0 +1,103 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fs::{create_dir_all, read_dir, remove_dir, rename};
+use std::path::Path;
+
+use anyhow::{Result, bail};
+
+// Moves contents of source directory into destination directory, descending
+// into directories present in both, and removes source directory
+pub(crate) fn merge_dir(src: &Path, dst: &Path) -> Result<()> {
+    create_dir_all(dst)?;
+    for entry in read_dir(src)? {
+        let entry = entry?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() && dst_path.is_dir() {
+            merge_dir(&src_path, &dst_path)?;
+        } else if dst_path.exists() || dst_path.is_symlink() {
+            bail!(
+                "Cannot merge {} into {}: path already exists",
+                src_path.display(),
+                dst_path.display()
+            )
+        } else {
+            rename(&src_path, &dst_path)?;
+        }
+    }
+    remove_dir(src)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{create_dir_all, read_to_string, write};
+
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    use crate::merge_dir::merge_dir;
+
+    #[test]
+    fn merge() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let a = temp_dir.path().join("a");
+        let b = temp_dir.path().join("b");
+        let dst = temp_dir.path().join("dst");
+        create_dir_all(a.join("bin"))?;
+        create_dir_all(a.join("lib").join("rustlib"))?;
+        create_dir_all(b.join("bin"))?;
+        write(a.join("bin").join("rustc"), "rustc")?;
+        write(a.join("lib").join("rustlib").join("x"), "x")?;
+        write(b.join("bin").join("cargo"), "cargo")?;
+
+        merge_dir(&a, &dst)?;
+        merge_dir(&b, &dst)?;
+
+        assert!(!a.exists());
+        assert!(!b.exists());
+        assert_eq!("rustc", read_to_string(dst.join("bin").join("rustc"))?);
+        assert_eq!("cargo", read_to_string(dst.join("bin").join("cargo"))?);
+        assert_eq!(
+            "x",
+            read_to_string(dst.join("lib").join("rustlib").join("x"))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn merge_conflict() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let a = temp_dir.path().join("a");
+        let b = temp_dir.path().join("b");
+        let dst = temp_dir.path().join("dst");
+        create_dir_all(a.join("bin"))?;
+        create_dir_all(b.join("bin"))?;
+        write(a.join("bin").join("rustc"), "a")?;
+        write(b.join("bin").join("rustc"), "b")?;
+
+        merge_dir(&a, &dst)?;
+        assert!(merge_dir(&b, &dst).is_err());
+        assert_eq!("a", read_to_string(dst.join("bin").join("rustc"))?);
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use isopy_lib::{PackageOps, Version};
+use url::Url;
+
+use crate::manifest::Component;
+use crate::rust_version::RustVersion;
+
+pub struct RustPackage {
+    pub(crate) other_version: Version,
+    pub(crate) manifest_url: Url,
+    pub(crate) components: Vec<Component>,
+}
+
+impl RustPackage {
+    pub(crate) fn new(
+        version: &RustVersion,
+        manifest_url: &Url,
+        components: Vec<Component>,
+    ) -> Self {
+        let other_version = Version::new(version.clone());
+        Self {
+            other_version,
+            manifest_url: manifest_url.to_owned(),
+            components,
+        }
+    }
+}
+
+impl PackageOps for RustPackage {
+    fn version(&self) -> &Version {
+        &self.other_version
+    }
+
+    fn url(&self) -> &Url {
+        &self.manifest_url
+    }
+}
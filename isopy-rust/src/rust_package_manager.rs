@@ -0,0 +1,311 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fs::{create_dir_all, remove_file};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use isopy_lib::{
+    DownloadAssetOptionsBuilder, DownloadPackageOptions, GetPackageOptions, InstallPackageOptions,
+    ListPackagesOptions, ListTagsOptions, Package, PackageInfo, PackageManagerContext,
+    PackageManagerOps, PluginError, SourceFilter, TagFilter, Tags, Target, UnpackOptionsBuilder,
+    UpdateIndexOptions, Version,
+};
+use tokio::fs::read_to_string;
+use url::Url;
+
+use crate::manifest::{Component, Manifest, target_triple};
+use crate::merge_dir::merge_dir;
+use crate::rust_package::RustPackage;
+use crate::rust_version::RustVersion;
+
+const MANIFESTS_FILE_NAME: &str = "manifests.txt";
+const COMPONENT_MANIFEST_FILE_NAME: &str = "manifest.in";
+const STABLE_TAG: &str = "stable";
+
+pub struct RustPackageManager {
+    ctx: PackageManagerContext,
+    moniker: String,
+    server_url: Url,
+    dist_url: Url,
+}
+
+impl RustPackageManager {
+    pub(crate) fn new(
+        ctx: PackageManagerContext,
+        moniker: &str,
+        server_url: &Url,
+        dist_url: &Url,
+    ) -> Self {
+        Self {
+            ctx,
+            moniker: String::from(moniker),
+            server_url: server_url.clone(),
+            dist_url: dist_url.clone(),
+        }
+    }
+
+    fn package_id(&self, version: &RustVersion) -> String {
+        format!("{}:{version}", self.moniker)
+    }
+
+    // Versions of all dated manifests published on distribution server in
+    // descending order
+    async fn get_index(&self, update: bool, show_progress: bool) -> Result<Vec<RustVersion>> {
+        let options = DownloadAssetOptionsBuilder::default()
+            .update(update)
+            .show_progress(show_progress)
+            .build()?;
+        let url = self.server_url.join(MANIFESTS_FILE_NAME)?;
+        let response = self.ctx.download_asset(&url, &options).await?;
+        let s = read_to_string(response.path).await?;
+        let mut versions = s
+            .lines()
+            .filter_map(RustVersion::from_manifest_line)
+            .collect::<Vec<_>>();
+        versions.sort();
+        versions.dedup();
+        versions.reverse();
+        Ok(versions)
+    }
+
+    fn manifest_url(&self, version: &RustVersion) -> Result<Url> {
+        Ok(self.dist_url.join(&version.manifest_path())?)
+    }
+
+    async fn get_package_inner(
+        &self,
+        show_progress: bool,
+        version: &RustVersion,
+        target: Target,
+    ) -> Result<RustPackage> {
+        let manifest_url = self.manifest_url(version)?;
+        let options = DownloadAssetOptionsBuilder::default()
+            .update(false)
+            .show_progress(show_progress)
+            .build()?;
+        let response = self.ctx.download_asset(&manifest_url, &options).await?;
+        let manifest = Manifest::parse(&read_to_string(response.path).await?)?;
+        let components = manifest.components(target_triple(target))?;
+        Ok(RustPackage::new(version, &manifest_url, components))
+    }
+
+    // Path of rustc archive if all components of given package have been
+    // downloaded
+    fn check_components(&self, package: &RustPackage) -> Result<Option<PathBuf>> {
+        let mut rustc_path = None;
+        for component in &package.components {
+            let Some(path) = self.ctx.check_asset(&component.url)? else {
+                return Ok(None);
+            };
+            rustc_path.get_or_insert(path);
+        }
+        Ok(rustc_path)
+    }
+
+    // Determines local path of package without downloading its manifest
+    async fn check_package(
+        &self,
+        version: &RustVersion,
+        target: Target,
+    ) -> Result<Option<PathBuf>> {
+        let manifest_url = self.manifest_url(version)?;
+        let Some(manifest_path) = self.ctx.check_asset(&manifest_url)? else {
+            return Ok(None);
+        };
+        let manifest = Manifest::parse(&read_to_string(manifest_path).await?)?;
+        let Ok(components) = manifest.components(target_triple(target)) else {
+            return Ok(None);
+        };
+        self.check_components(&RustPackage::new(version, &manifest_url, components))
+    }
+
+    fn make_package_info(
+        &self,
+        version: &RustVersion,
+        target: Target,
+        path: Option<PathBuf>,
+    ) -> Result<PackageInfo> {
+        Ok(PackageInfo::new(
+            format!("rust-{version}-{}", target_triple(target)),
+            &self.manifest_url(version)?,
+            Version::new(version.clone()),
+            path,
+        ))
+    }
+}
+
+// Each archive contains a top-level directory holding installer metadata and
+// one directory per component: components are unpacked separately and merged
+// into a single sysroot
+async fn unpack_component(
+    component: &Component,
+    archive_path: &Path,
+    dir: &Path,
+    show_progress: bool,
+) -> Result<()> {
+    let staging_dir = dir.join(format!(".{}", component.dir_name));
+    component
+        .archive_type
+        .unpack(
+            archive_path,
+            &staging_dir,
+            &UnpackOptionsBuilder::default()
+                .show_progress(show_progress)
+                .subdir(&component.dir_name)
+                .build()?,
+        )
+        .await?;
+
+    let component_manifest_path = staging_dir.join(COMPONENT_MANIFEST_FILE_NAME);
+    if component_manifest_path.is_file() {
+        remove_file(&component_manifest_path)?;
+    }
+
+    merge_dir(&staging_dir, dir)
+}
+
+#[async_trait]
+impl PackageManagerOps for RustPackageManager {
+    async fn update_index(&self, options: &UpdateIndexOptions) -> Result<()> {
+        self.get_index(true, options.show_progress).await?;
+        Ok(())
+    }
+
+    async fn list_tags(&self, options: &ListTagsOptions) -> Result<Tags> {
+        Ok(Tags {
+            platform_tags: vec![String::from(target_triple(options.target))],
+            other_tags: vec![
+                String::from("beta"),
+                String::from("nightly"),
+                String::from(STABLE_TAG),
+            ],
+        })
+    }
+
+    async fn list_packages(
+        &self,
+        source_filter: SourceFilter,
+        tag_filter: &TagFilter,
+        options: &ListPackagesOptions,
+    ) -> Result<Vec<PackageInfo>> {
+        use isopy_lib::SourceFilter::{All, Local, Remote};
+
+        // Only stable releases are listed unless another channel is
+        // requested since there are thousands of dated nightly manifests
+        let channel = match tag_filter.tags.as_slice() {
+            [] => STABLE_TAG,
+            [channel] => channel.as_str(),
+            _ => return Ok(Vec::new()),
+        };
+
+        let mut packages = Vec::new();
+        for version in self.get_index(false, options.show_progress).await? {
+            if version.channel() != channel {
+                continue;
+            }
+
+            let path = self.check_package(&version, options.target).await?;
+            if matches!(
+                (source_filter, path.is_some()),
+                (All, _) | (Local, true) | (Remote, false)
+            ) {
+                packages.push(self.make_package_info(&version, options.target, path)?);
+            }
+        }
+
+        Ok(packages)
+    }
+
+    async fn get_package(
+        &self,
+        version: &Version,
+        _tag_filter: &TagFilter,
+        options: &GetPackageOptions,
+    ) -> Result<Option<PackageInfo>> {
+        let version = isopy_lib::downcast_version!(version, RustVersion);
+        let package = self
+            .get_package_inner(options.show_progress, version, options.target)
+            .await?;
+        let path = self.check_components(&package)?;
+        Ok(Some(self.make_package_info(
+            version,
+            options.target,
+            path,
+        )?))
+    }
+
+    async fn download_package(
+        &self,
+        version: &Version,
+        _tag_filter: &TagFilter,
+        options: &DownloadPackageOptions,
+    ) -> Result<()> {
+        let version = isopy_lib::downcast_version!(version, RustVersion);
+        let package = self
+            .get_package_inner(options.show_progress, version, options.target)
+            .await?;
+        for component in &package.components {
+            let download_options = DownloadAssetOptionsBuilder::default()
+                .update(false)
+                .checksum(Some(component.checksum.clone()))
+                .show_progress(options.show_progress)
+                .build()?;
+            _ = self
+                .ctx
+                .download_asset(&component.url, &download_options)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn install_package(
+        &self,
+        version: &Version,
+        _tag_filter: &TagFilter,
+        dir: &Path,
+        options: &InstallPackageOptions,
+    ) -> Result<Package> {
+        let version = isopy_lib::downcast_version!(version, RustVersion);
+        let package = self
+            .get_package_inner(options.show_progress, version, options.target)
+            .await?;
+
+        let mut archive_paths = Vec::new();
+        for component in &package.components {
+            let Some(path) = self.ctx.check_asset(&component.url)? else {
+                return Err(PluginError::PackageNotDownloaded {
+                    package_id: self.package_id(version),
+                }
+                .into());
+            };
+            archive_paths.push(path);
+        }
+
+        create_dir_all(dir)?;
+        for (component, archive_path) in package.components.iter().zip(archive_paths) {
+            unpack_component(component, &archive_path, dir, options.show_progress).await?;
+        }
+
+        Ok(Package::new(package))
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::ffi::OsString;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use anyhow::Result;
+use isopy_lib::{
+    EnvInfo, MakeEnvInfoOptions, PackageManager, PackageManagerContext, Platform, Plugin,
+    PluginOps, Shell, Version,
+};
+use url::Url;
+
+use crate::rust_package_manager::RustPackageManager;
+use crate::rust_version::RustVersion;
+
+static SERVER_URL: LazyLock<Url> = LazyLock::new(|| {
+    "https://static.rust-lang.org/"
+        .parse()
+        .expect("Invalid server URL")
+});
+
+static DIST_URL: LazyLock<Url> =
+    LazyLock::new(|| SERVER_URL.join("dist/").expect("Invalid distribution URL"));
+
+pub struct RustPlugin {
+    moniker: String,
+}
+
+impl RustPlugin {
+    pub(crate) fn new_plugin(moniker: &str) -> Plugin {
+        Plugin::new(Self {
+            moniker: String::from(moniker),
+        })
+    }
+}
+
+impl PluginOps for RustPlugin {
+    fn url(&self) -> &Url {
+        &DIST_URL
+    }
+
+    fn parse_version(&self, s: &str) -> Result<Version> {
+        Ok(Version::new(s.parse::<RustVersion>()?))
+    }
+
+    fn make_env_info(&self, dir: &Path, _options: &MakeEnvInfoOptions) -> EnvInfo {
+        EnvInfo {
+            path_dirs: vec![dir.join("bin")],
+            vars: vec![],
+            unset_vars: vec![],
+            path_vars: vec![],
+        }
+    }
+
+    fn make_script_command(
+        &self,
+        _script_path: &Path,
+        _platform: Platform,
+        _shell: Shell,
+    ) -> Result<Option<OsString>> {
+        Ok(None)
+    }
+
+    fn new_package_manager(&self, ctx: PackageManagerContext) -> PackageManager {
+        PackageManager::new(RustPackageManager::new(
+            ctx,
+            &self.moniker,
+            &SERVER_URL,
+            &DIST_URL,
+        ))
+    }
+}
@@ -0,0 +1,222 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::any::Any;
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+use anyhow::{Error, bail};
+use chrono::NaiveDate;
+use isopy_lib::{Triple, VersionOps};
+
+const BETA: &str = "beta";
+const NIGHTLY: &str = "nightly";
+const STABLE: &str = "stable";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum RustVersion {
+    Stable(Triple),
+    Beta(NaiveDate),
+    Nightly(NaiveDate),
+}
+
+impl RustVersion {
+    pub(crate) const fn channel(&self) -> &'static str {
+        match self {
+            Self::Stable(_) => STABLE,
+            Self::Beta(_) => BETA,
+            Self::Nightly(_) => NIGHTLY,
+        }
+    }
+
+    // Path of channel manifest relative to distribution server root
+    pub(crate) fn manifest_path(&self) -> String {
+        match self {
+            Self::Stable(triple) => format!("channel-rust-{triple}.toml"),
+            Self::Beta(date) | Self::Nightly(date) => format!(
+                "{}/channel-rust-{}.toml",
+                date.format(DATE_FORMAT),
+                self.channel()
+            ),
+        }
+    }
+
+    // Parses line from manifests.txt such as
+    // "static.rust-lang.org/dist/2024-05-02/channel-rust-1.78.0.toml":
+    // returns None for manifests of rolling channels and partial versions
+    pub(crate) fn from_manifest_line(s: &str) -> Option<Self> {
+        let mut parts = s.trim().rsplit('/');
+        let file_name = parts.next()?;
+        let date = parts.next()?;
+        let name = file_name
+            .strip_prefix("channel-rust-")?
+            .strip_suffix(".toml")?;
+        match name {
+            BETA => Some(Self::Beta(parse_date(date)?)),
+            NIGHTLY => Some(Self::Nightly(parse_date(date)?)),
+            _ => Some(Self::Stable(name.parse().ok()?)),
+        }
+    }
+}
+
+impl Display for RustVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Stable(triple) => write!(f, "{triple}"),
+            Self::Beta(date) | Self::Nightly(date) => {
+                write!(f, "{}-{}", self.channel(), date.format(DATE_FORMAT))
+            }
+        }
+    }
+}
+
+impl FromStr for RustVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        if let Some(date) = s.strip_prefix("nightly-") {
+            let Some(date) = parse_date(date) else {
+                bail!("Invalid Rust nightly version {s}: expected nightly-YYYY-MM-DD")
+            };
+            return Ok(Self::Nightly(date));
+        }
+
+        if let Some(date) = s.strip_prefix("beta-") {
+            let Some(date) = parse_date(date) else {
+                bail!("Invalid Rust beta version {s}: expected beta-YYYY-MM-DD")
+            };
+            return Ok(Self::Beta(date));
+        }
+
+        match s.parse() {
+            Ok(triple) => Ok(Self::Stable(triple)),
+            Err(_) => bail!(
+                "Invalid Rust version {s}: expected version such as 1.78.0 or nightly-2024-05-01"
+            ),
+        }
+    }
+}
+
+impl VersionOps for RustVersion {
+    fn as_str(&self) -> Cow<'_, String> {
+        Cow::Owned(format!("{self}"))
+    }
+
+    fn label(&self) -> Option<Cow<'_, String>> {
+        None
+    }
+
+    fn box_clone(&self) -> Box<dyn VersionOps> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn parse_date(s: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s, DATE_FORMAT).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rstest::rstest;
+
+    use crate::rust_version::RustVersion;
+
+    #[rstest]
+    #[case("1.78.0", "stable", "channel-rust-1.78.0.toml")]
+    #[case(
+        "nightly-2024-05-01",
+        "nightly",
+        "2024-05-01/channel-rust-nightly.toml"
+    )]
+    #[case("beta-2024-05-01", "beta", "2024-05-01/channel-rust-beta.toml")]
+    fn parse_valid(
+        #[case] input: &str,
+        #[case] expected_channel: &str,
+        #[case] expected_manifest_path: &str,
+    ) -> Result<()> {
+        let version = input.parse::<RustVersion>()?;
+        assert_eq!(input, version.to_string());
+        assert_eq!(expected_channel, version.channel());
+        assert_eq!(expected_manifest_path, version.manifest_path());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("1.78")]
+    #[case("stable")]
+    #[case("nightly")]
+    #[case("nightly-2024-13-01")]
+    #[case("beta-yesterday")]
+    fn parse_invalid(#[case] input: &str) {
+        assert!(input.parse::<RustVersion>().is_err());
+    }
+
+    #[rstest]
+    #[case(
+        Some("1.78.0"),
+        "static.rust-lang.org/dist/2024-05-02/channel-rust-1.78.0.toml"
+    )]
+    #[case(
+        Some("nightly-2024-05-01"),
+        "static.rust-lang.org/dist/2024-05-01/channel-rust-nightly.toml"
+    )]
+    #[case(
+        Some("beta-2024-05-01"),
+        "static.rust-lang.org/dist/2024-05-01/channel-rust-beta.toml"
+    )]
+    #[case(None, "static.rust-lang.org/dist/2024-05-02/channel-rust-1.78.toml")]
+    #[case(None, "static.rust-lang.org/dist/2024-05-02/channel-rust-stable.toml")]
+    #[case(
+        None,
+        "static.rust-lang.org/dist/2024-05-02/channel-rust-nightly.toml.sha256"
+    )]
+    fn from_manifest_line(#[case] expected: Option<&str>, #[case] input: &str) {
+        assert_eq!(
+            expected,
+            RustVersion::from_manifest_line(input)
+                .map(|v| v.to_string())
+                .as_deref()
+        );
+    }
+
+    #[test]
+    fn ordering() -> Result<()> {
+        let mut versions = ["1.78.0", "1.9.0", "1.77.2"]
+            .into_iter()
+            .map(str::parse::<RustVersion>)
+            .collect::<Result<Vec<_>>>()?;
+        versions.sort();
+        assert_eq!(
+            vec!["1.9.0", "1.77.2", "1.78.0"],
+            versions.iter().map(ToString::to_string).collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+}
@@ -21,22 +21,34 @@
 //
 use std::collections::HashMap;
 use std::ffi::OsString;
-use std::fs::File;
+use std::fs::{File, create_dir_all, remove_dir_all, rename};
 use std::path::{Path, PathBuf};
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::Utc;
 use isopy_lib::{
-    EnvInfo, GetPackageOptions, InstallPackageOptions, PackageInfo, Platform, Shell, TagFilter,
-    Version,
+    Checksum, CreateVenvOptionsBuilder, EnvInfo, GetPackageOptions, GetPackageOptionsBuilder,
+    InstallPackageOptions, ListPackagesOptionsBuilder, MakeEnvInfoOptions, Package, PackageInfo,
+    PackageManager, Platform, ProjectVersion, ProjectVersionSpec, SanitizeOptions, Shell,
+    SourceFilter, TagFilter, Target, Version, detect_libc, sanitize_with_options,
+};
+use log::info;
+use tempfile::Builder as TempFileBuilder;
+use url::Url;
+
+use crate::constants::{
+    DEFAULT_MONIKER_CONFIG_NAME, ENV_WORKSPACES_CONFIG_NAME, GO_TOOLCHAIN_CONFIG_NAME,
+    LIBC_CONFIG_NAME, PROJECT_CONFIG_FILE_NAME, PYTHON_MONIKER, SHARED_INSTALLS_CONFIG_NAME,
+    WORKSPACES_DIR_NAME,
 };
-
-use crate::constants::{DEFAULT_MONIKER_CONFIG_NAME, PROJECT_CONFIG_FILE_NAME};
 use crate::dir_info_ext::DirInfoExt;
 use crate::moniker::Moniker;
 use crate::package_id::PackageId;
 use crate::plugin_manager::PluginManager;
+use crate::plugin_registry::{builtin_plugin_descriptors, is_plugin_enabled};
+use crate::read_only::make_read_only;
 use crate::repo::{DirInfo, Link, LinkId, Repo};
-use crate::serialization::{Config, Env, EnvPackage, Project};
+use crate::serialization::{Config, Env, EnvPackage, EnvRequirements, EnvVenv, Project, Venv};
 use crate::shell::IsopyEnv;
 use crate::write::safe_write_file;
 use crate::yaml::read_yaml_file;
@@ -76,6 +88,10 @@ impl App {
         let config = self.get_config()?;
         Ok(match name {
             DEFAULT_MONIKER_CONFIG_NAME => config.default_moniker.map(|m| m.as_str().to_owned()),
+            SHARED_INSTALLS_CONFIG_NAME => config.shared_installs.map(|b| b.to_string()),
+            LIBC_CONFIG_NAME => config.libc.map(|l| l.to_string()),
+            ENV_WORKSPACES_CONFIG_NAME => config.env_workspaces.map(|b| b.to_string()),
+            GO_TOOLCHAIN_CONFIG_NAME => config.go_toolchain,
             _ => bail!("Unknown configuration value {name}"),
         })
     }
@@ -86,6 +102,18 @@ impl App {
             DEFAULT_MONIKER_CONFIG_NAME => {
                 config.default_moniker = Some(value.parse()?);
             }
+            SHARED_INSTALLS_CONFIG_NAME => {
+                config.shared_installs = Some(value.parse()?);
+            }
+            LIBC_CONFIG_NAME => {
+                config.libc = Some(value.parse()?);
+            }
+            ENV_WORKSPACES_CONFIG_NAME => {
+                config.env_workspaces = Some(value.parse()?);
+            }
+            GO_TOOLCHAIN_CONFIG_NAME => {
+                config.go_toolchain = Some(String::from(value));
+            }
             _ => bail!("Unknown configuration value {name}"),
         }
         let f = File::create(&self.config_value_path)?;
@@ -100,6 +128,10 @@ impl App {
         let mut config = self.get_config()?;
         match name {
             DEFAULT_MONIKER_CONFIG_NAME => config.default_moniker = None,
+            SHARED_INSTALLS_CONFIG_NAME => config.shared_installs = None,
+            LIBC_CONFIG_NAME => config.libc = None,
+            ENV_WORKSPACES_CONFIG_NAME => config.env_workspaces = None,
+            GO_TOOLCHAIN_CONFIG_NAME => config.go_toolchain = None,
             _ => bail!("Unknown configuration value {name}"),
         }
         let f = File::create(&self.config_value_path)?;
@@ -107,6 +139,16 @@ impl App {
         Ok(())
     }
 
+    // Target for packages installed on this machine: on Linux, the C library
+    // is taken from configuration or detected at runtime
+    pub(crate) fn host_target(&self) -> Result<Target> {
+        let mut target = Target::host();
+        if target.libc.is_some() {
+            target.libc = self.get_config()?.libc.or_else(detect_libc);
+        }
+        Ok(target)
+    }
+
     pub(crate) fn has_project_config_file(&self) -> bool {
         self.project_config_path.is_file()
     }
@@ -124,6 +166,104 @@ impl App {
         Ok(())
     }
 
+    // Infers package versions from files in the project directory understood
+    // by enabled plugins, e.g. go.mod
+    pub(crate) fn detect_project_versions(&self) -> Result<Vec<(Moniker, ProjectVersion)>> {
+        let mut project_versions = Vec::new();
+        for moniker in Moniker::iter_enabled() {
+            if let Some(project_version) = self
+                .plugin_manager
+                .detect_project_version(&moniker, &self.cwd)?
+            {
+                project_versions.push((moniker, project_version));
+            }
+        }
+        Ok(project_versions)
+    }
+
+    // Used by the shell prompt, which runs on every command: only built-in
+    // plugins are asked so that external plugins are never searched for, and
+    // detection stops at the first plugin that finds a version
+    pub(crate) fn can_infer_project_config(&self) -> Result<bool> {
+        for descriptor in builtin_plugin_descriptors() {
+            if is_plugin_enabled(descriptor)
+                && (descriptor.new_plugin)(&descriptor.name)
+                    .detect_project_version(&self.cwd)?
+                    .is_some()
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    // Builds project configuration from inferred package versions, resolving
+    // version requirements against the package index
+    pub(crate) async fn infer_project_config(&self) -> Result<Option<Project>> {
+        let project_versions = self.detect_project_versions()?;
+        if project_versions.is_empty() {
+            return Ok(None);
+        }
+
+        let mut package_ids = Vec::new();
+        for (moniker, project_version) in project_versions {
+            let version = self
+                .resolve_project_version(&moniker, &project_version)
+                .await?;
+            let package_id = PackageId::new(&moniker, &version);
+            info!(
+                "Inferred package {package_id} from {}",
+                project_version.path.display()
+            );
+            package_ids.push(package_id);
+        }
+
+        Ok(Some(Project {
+            package_ids,
+            venv: None,
+            isolated: false,
+        }))
+    }
+
+    async fn resolve_project_version(
+        &self,
+        moniker: &Moniker,
+        project_version: &ProjectVersion,
+    ) -> Result<Version> {
+        let requirement = match &project_version.spec {
+            ProjectVersionSpec::Exact(version) => return Ok(version.clone()),
+            ProjectVersionSpec::Requirement(requirement) => requirement,
+        };
+
+        let versions = self
+            .plugin_manager
+            .new_package_manager(moniker, &self.config_dir)
+            .list_packages(
+                SourceFilter::All,
+                &TagFilter::default(),
+                &ListPackagesOptionsBuilder::default()
+                    .show_progress(self.show_progress)
+                    .target(self.host_target()?)
+                    .build()?,
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to list {moniker} packages to resolve requirement {requirement}: try \"isopy update {moniker}\""
+                )
+            })?
+            .into_iter()
+            .map(|p| p.version)
+            .collect::<Vec<_>>();
+
+        requirement.choose(&versions).ok_or_else(|| {
+            anyhow!(
+                "No {moniker} package satisfies requirement {requirement} from {}",
+                project_version.path.display()
+            )
+        })
+    }
+
     pub(crate) async fn get_package(
         &self,
         moniker: &Moniker,
@@ -144,60 +284,91 @@ impl App {
     ) -> Result<()> {
         let project_dir = &self.cwd;
 
-        let (dir_info, mut packages) = if let Some(dir_info) = self.repo.get(project_dir)? {
-            let env = dir_info.read_env_config()?;
-            if env.project_dir != *project_dir {
-                bail!(
-                    "Environment directory {} does not correspond to project directory {}",
-                    dir_info.data_dir().display(),
-                    project_dir.display()
-                );
-            }
-
-            (dir_info, env.packages)
-        } else {
-            let Some(dir_info) = self.repo.init(project_dir)? else {
-                bail!(
-                    "Could not initialize environment for directory {}",
-                    project_dir.display()
-                );
+        let (dir_info, mut packages, isolated, created) =
+            if let Some(dir_info) = self.repo.get(project_dir)? {
+                let env = dir_info.read_env_config()?;
+                if env.project_dir != *project_dir {
+                    bail!(
+                        "Environment directory {} does not correspond to project directory {}",
+                        dir_info.data_dir().display(),
+                        project_dir.display()
+                    );
+                }
+
+                (dir_info, env.packages, env.isolated, false)
+            } else {
+                let Some(dir_info) = self.repo.init(project_dir)? else {
+                    bail!(
+                        "Could not initialize environment for directory {}",
+                        project_dir.display()
+                    );
+                };
+
+                (dir_info, Vec::new(), false, true)
             };
 
-            (dir_info, Vec::new())
-        };
-
-        if packages.iter().any(|p| &p.package_id.moniker == moniker) {
-            bail!("Environment already has a package for package manager {moniker} configured");
+        let package_id = PackageId::new(moniker, version);
+        if let Some(p) = packages
+            .iter()
+            .find(|p| p.package_id.conflicts_with(&package_id))
+        {
+            bail!(
+                "Environment already has package {} configured",
+                p.package_id
+            );
         }
 
         let package_manager = self
             .plugin_manager
             .new_package_manager(moniker, &self.config_dir);
 
-        let dir_name = Path::new(moniker.as_str());
-        let output_path = dir_info.data_dir().join(dir_name);
+        let dir_name = package_id.dir();
+        let output_path = dir_info.data_dir().join(&dir_name);
 
-        let package = match package_manager
-            .install_package(version, &TagFilter::default(), &output_path, options)
+        if output_path.exists() {
+            bail!("Output directory {} already exists", output_path.display());
+        }
+
+        // Install into a sibling staging directory and only move it into
+        // place once the plugin has finished so that a failed install leaves
+        // the environment untouched
+        let result = if self.get_config()?.shared_installs.unwrap_or(false) {
+            self.install_shared(moniker, &package_manager, version, &output_path, options)
+                .await
+        } else {
+            Self::install_staged(
+                &package_manager,
+                version,
+                dir_info.data_dir(),
+                &output_path,
+                options,
+            )
             .await
-        {
+            .map(|p| (p.version().clone(), p.url().clone()))
+        };
+
+        let (installed_version, url) = match result {
             Ok(p) => p,
             Err(e) => {
-                _ = self.remove_project_env(project_dir);
+                if created {
+                    _ = self.remove_project_env(project_dir);
+                }
                 bail!(e)
             }
         };
 
         packages.push(EnvPackage {
-            package_id: PackageId::new(moniker, package.version()),
-            dir: dir_name.to_path_buf(),
-            url: package.url().to_owned(),
+            package_id: PackageId::new(moniker, &installed_version),
+            dir: dir_name,
+            url,
+            venv: None,
         });
 
         dir_info.write_env_config(
             &Env {
                 project_dir: project_dir.clone(),
                 packages,
+                isolated,
             },
             true,
         )?;
@@ -205,6 +376,85 @@ impl App {
         Ok(())
     }
 
+    async fn install_staged(
+        package_manager: &PackageManager,
+        version: &Version,
+        data_dir: &Path,
+        output_path: &Path,
+        options: &InstallPackageOptions,
+    ) -> Result<Package> {
+        let staging_dir = TempFileBuilder::new()
+            .prefix(".isopy-staging-")
+            .tempdir_in(data_dir)?;
+        let staging_path = staging_dir.path().join("package");
+
+        let package = package_manager
+            .install_package(version, &TagFilter::default(), &staging_path, options)
+            .await?;
+
+        rename(&staging_path, output_path)?;
+        Ok(package)
+    }
+
+    // Shared installs are unpacked once per package ID and archive checksum
+    // and referenced from each environment's data directory by a symbolic
+    // link: Trash::compute removes them once no environment refers to them
+    async fn install_shared(
+        &self,
+        moniker: &Moniker,
+        package_manager: &PackageManager,
+        version: &Version,
+        output_path: &Path,
+        options: &InstallPackageOptions,
+    ) -> Result<(Version, Url)> {
+        let package_id = PackageId::new(moniker, version);
+        let Some(info) = package_manager
+            .get_package(
+                version,
+                &TagFilter::default(),
+                &GetPackageOptionsBuilder::default()
+                    .show_progress(options.show_progress)
+                    .target(options.target)
+                    .build()?,
+            )
+            .await?
+        else {
+            bail!("Package {package_id} not found");
+        };
+
+        let Some(archive_path) = &info.path else {
+            bail!(
+                "Package {package_id} not downloaded: use \"isopy download <PACKAGE-ID>\" or pass \"--download\" to download missing packages"
+            );
+        };
+
+        let checksum = Checksum::from_file(archive_path).await?.to_string();
+        let group_dir = self.repo.shared_dir().join(moniker.dir());
+        let shared_path = group_dir.join(format!(
+            "{version}-{checksum}",
+            version = sanitize_with_options(
+                &info.version.as_str(),
+                &SanitizeOptions::default().retain_dots(true)
+            ),
+            checksum = &checksum[..16]
+        ));
+
+        if !shared_path.is_dir() {
+            create_dir_all(&group_dir)?;
+            match Self::install_staged(package_manager, version, &group_dir, &shared_path, options)
+                .await
+            {
+                Ok(_) => make_read_only(&shared_path)?,
+                // Another isopy process installed the same package first
+                Err(_) if shared_path.is_dir() => {}
+                Err(e) => bail!(e),
+            }
+        }
+
+        symlink_dir(&shared_path, output_path)?;
+        Ok((info.version, info.url))
+    }
+
     pub(crate) fn find_link(&self, link_id: &LinkId) -> Result<Option<Link>> {
         // THIS IS A TEMPORARY HACK!
         // isopy-repo needs a method to get a DirInfo given a link ID or something
@@ -245,10 +495,126 @@ impl App {
         self.repo.get(link.project_dir())
     }
 
-    pub(crate) fn make_env_info(&self, data_dir: &Path, package: &EnvPackage) -> EnvInfo {
-        self.plugin_manager
+    pub(crate) fn make_env_info(
+        &self,
+        data_dir: &Path,
+        env: &Env,
+        package: &EnvPackage,
+    ) -> Result<EnvInfo> {
+        let moniker = &package.package_id.moniker;
+        let package_dir = data_dir.join(&package.dir);
+        let config = self.get_config()?;
+
+        // Shared installs are read-only so always get a workspace for state
+        // written by the package's own tooling
+        let workspace_dir = (config.env_workspaces.unwrap_or(false) || package_dir.is_symlink())
+            .then(|| data_dir.join(WORKSPACES_DIR_NAME).join(&package.dir));
+        Ok(self.plugin_manager.make_env_info(
+            moniker,
+            &package_dir,
+            &MakeEnvInfoOptions {
+                venv_dir: package.venv.as_ref().map(|v| v.dir.clone()),
+                workspace_dir,
+                isolated: env.isolated,
+                go_toolchain: config.go_toolchain,
+            },
+        ))
+    }
+
+    pub(crate) fn set_env_isolated(&self, isolated: bool) -> Result<()> {
+        let Some(dir_info) = self.repo.get(&self.cwd)? else {
+            if isolated {
+                bail!(
+                    "Project in directory {} has no environment",
+                    self.cwd.display()
+                );
+            }
+            return Ok(());
+        };
+
+        let mut env = dir_info.read_env_config()?;
+        if env.isolated != isolated {
+            env.isolated = isolated;
+            dir_info.write_env_config(&env, true)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn create_venv(&self, venv: &Venv) -> Result<()> {
+        let Some(dir_info) = self.repo.get(&self.cwd)? else {
+            bail!(
+                "Project in directory {} has no environment",
+                self.cwd.display()
+            );
+        };
+
+        let mut env = dir_info.read_env_config()?;
+        let Some(package) = env
+            .packages
+            .iter_mut()
+            .find(|p| p.package_id.moniker.as_str() == PYTHON_MONIKER)
+        else {
+            bail!("Virtual environment requires a Python package in the project configuration");
+        };
+
+        let venv_dir = self.cwd.join(&venv.path);
+        if venv_dir.exists() {
+            bail!(
+                "Virtual environment directory {} already exists",
+                venv_dir.display()
+            );
+        }
+
+        // Checksums are taken before installation so that the recorded state
+        // describes exactly the files that were installed
+        let mut requirements = Vec::new();
+        for path in &venv.requirements {
+            requirements.push(self.make_env_requirements(path).await?);
+        }
+        let lock = match &venv.lock {
+            Some(path) => Some(self.make_env_requirements(path).await?),
+            None => None,
+        };
+
+        let options = CreateVenvOptionsBuilder::default()
+            .requirements(
+                venv.requirements
+                    .iter()
+                    .map(|p| self.cwd.join(p))
+                    .collect::<Vec<_>>(),
+            )
+            .system_site_packages(venv.system_site_packages)
+            .lock(venv.lock.as_ref().map(|p| self.cwd.join(p)))
+            .build()?;
+        if let Err(e) = self
+            .plugin_manager
             .get_plugin(&package.package_id.moniker)
-            .make_env_info(&data_dir.join(&package.dir))
+            .create_venv(&dir_info.data_dir().join(&package.dir), &venv_dir, &options)
+        {
+            if venv_dir.exists() {
+                _ = remove_dir_all(&venv_dir);
+            }
+            bail!(e)
+        }
+
+        package.venv = Some(EnvVenv {
+            dir: venv_dir,
+            requirements,
+            lock,
+            installed_at: Utc::now(),
+        });
+        dir_info.write_env_config(&env, true)?;
+        Ok(())
+    }
+
+    async fn make_env_requirements(&self, path: &Path) -> Result<EnvRequirements> {
+        let checksum = Checksum::from_file(&self.cwd.join(path))
+            .await
+            .with_context(|| format!("Failed to read requirements file {}", path.display()))?;
+        Ok(EnvRequirements {
+            path: path.to_path_buf(),
+            sha256: checksum.to_string(),
+        })
     }
 
     pub(crate) fn make_script_command(
@@ -284,3 +650,15 @@ impl App {
         }
     }
 }
+
+#[cfg(unix)]
+fn symlink_dir(original: &Path, link: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(original, link)?;
+    Ok(())
+}
+
+#[cfg(windows)]
+fn symlink_dir(original: &Path, link: &Path) -> Result<()> {
+    std::os::windows::fs::symlink_dir(original, link)?;
+    Ok(())
+}
@@ -24,7 +24,7 @@ use std::result::Result;
 
 use clap::{ArgAction, Args as ClapArgs, Parser, Subcommand, ValueEnum};
 use clap_complete::Shell as ClapCompleteShell;
-use isopy_lib::{Platform as IsopyLibPlatform, Shell as IsopyLibShell, SourceFilter};
+use isopy_lib::{Platform as IsopyLibPlatform, Shell as IsopyLibShell, SourceFilter, Target};
 use log::LevelFilter;
 use path_absolutize::Absolutize;
 
@@ -98,6 +98,16 @@ pub(crate) struct Args {
 
 #[derive(Debug, Subcommand)]
 pub(crate) enum Command {
+    #[command(
+        name = "activate",
+        alias = "export",
+        about = "Print script to activate environment in current shell"
+    )]
+    Activate {
+        #[arg(help = "Shell script type", short = 's', long = "shell", value_enum)]
+        shell: Shell,
+    },
+
     #[command(
         name = "check",
         about = "Check integrity of metadata directory and optionally clean up"
@@ -139,6 +149,12 @@ pub(crate) enum Command {
             value_delimiter = ','
         )]
         tags: Option<Vec<String>>,
+
+        #[arg(
+            help = "Target platform (e.g. linux-aarch64-gnu, macos-x86_64, windows-x86_64) [default: host platform]",
+            long = "target"
+        )]
+        target: Option<Target>,
     },
 
     #[command(name = "env", about = "Install package into non-project environment")]
@@ -160,6 +176,25 @@ pub(crate) enum Command {
         _no_download: bool,
     },
 
+    #[command(
+        name = "hook",
+        about = "Print shell hook to activate environments on changing directory"
+    )]
+    Hook {
+        #[arg(help = "Shell", value_enum)]
+        shell: Shell,
+    },
+
+    // Called by the shell hook before each prompt
+    #[command(name = "hook-env", hide = true)]
+    HookEnv {
+        #[arg(help = "Shell", long = "shell", value_enum)]
+        shell: Shell,
+
+        #[arg(help = "Key of environment activated by hook", long = "key")]
+        key: Option<String>,
+    },
+
     #[command(name = "info", about = "Show information about current environment")]
     Info,
 
@@ -180,6 +215,13 @@ pub(crate) enum Command {
 
         #[arg(help = "Do not download package if missing", long = "no-download")]
         _no_download: bool,
+
+        #[arg(
+            help = "Write package versions inferred from project files to project configuration file",
+            long = "write",
+            default_value_t = false
+        )]
+        write: bool,
     },
 
     #[command(
@@ -228,6 +270,12 @@ pub(crate) enum Command {
         )]
         tags: Option<Vec<String>>,
 
+        #[arg(
+            help = "Target platform (e.g. linux-aarch64-gnu, macos-x86_64, windows-x86_64) [default: host platform]",
+            long = "target"
+        )]
+        target: Option<Target>,
+
         // Reference: https://jwodder.github.io/kbits/posts/clap-bool-negate/
         // --verbose/--no-verbose with default of "false"
         #[arg(
@@ -242,13 +290,18 @@ pub(crate) enum Command {
         _no_verbose: bool,
     },
 
+    #[command(name = "plugins", about = "List available plugins")]
+    Plugins,
+
     #[command(
         name = "project",
         about = "Generate or update project configuration file for current directory"
     )]
     Project {
-        #[arg(help = "Package ID")]
-        package_id: PackageId,
+        #[arg(
+            help = "Package ID: if omitted, writes configuration inferred from project files such as go.mod"
+        )]
+        package_id: Option<PackageId>,
     },
 
     #[command(name = "prompt", about = "Show brief information in shell prompt")]
@@ -309,6 +362,12 @@ pub(crate) enum Command {
     Tags {
         #[arg(help = "Package manager")]
         moniker: Option<Moniker>,
+
+        #[arg(
+            help = "Target platform (e.g. linux-aarch64-gnu, macos-x86_64, windows-x86_64) [default: host platform]",
+            long = "target"
+        )]
+        target: Option<Target>,
     },
 
     #[command(name = "update", about = "Update package indices")]
@@ -478,6 +537,18 @@ pub(crate) enum Shell {
 
     #[clap(name = "cmd")]
     Cmd,
+
+    #[clap(name = "fish")]
+    Fish,
+
+    #[clap(name = "nushell", alias = "nu")]
+    Nushell,
+
+    #[clap(name = "powershell", alias = "pwsh")]
+    Powershell,
+
+    #[clap(name = "zsh")]
+    Zsh,
 }
 
 impl Default for Shell {
@@ -497,6 +568,10 @@ impl From<Shell> for IsopyLibShell {
         match value {
             Shell::Bash => Self::Bash,
             Shell::Cmd => Self::Cmd,
+            Shell::Fish => Self::Fish,
+            Shell::Nushell => Self::Nushell,
+            Shell::Powershell => Self::PowerShell,
+            Shell::Zsh => Self::Zsh,
         }
     }
 }
@@ -0,0 +1,396 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::fmt::Write;
+
+use anyhow::{Result, anyhow, bail};
+use isopy_lib::{
+    EnvInfo, PathVarPosition, Shell, join_paths, path_separator, quote, render_absolute_path,
+};
+
+use crate::app::App;
+use crate::dir_info_ext::DirInfoExt;
+use crate::env::EnvKey;
+use crate::shell::IsopyEnv;
+use crate::status::{StatusResult, success, user_error};
+
+// Previous values are saved in shell variables with this prefix so that
+// "deactivate" can restore them
+const SAVED_VAR_PREFIX: &str = "_ISOPY_OLD_";
+
+pub(crate) fn do_activate(app: &App, shell: Shell) -> StatusResult {
+    if let Some(isopy_env) = IsopyEnv::get_vars()? {
+        user_error!(
+            "An isopy environment (metadirectory ID {}) is already active: run \"deactivate\" or exit the isopy shell first",
+            isopy_env.meta_id
+        );
+    }
+
+    if shell == Shell::Cmd {
+        user_error!("activation scripts are not supported for cmd: use \"isopy sh\" instead");
+    }
+
+    let Some(dir_info) = app.find_dir_info(None)? else {
+        user_error!(
+            "Could not find environment for directory {}: did you forget to run \"isopy init\" or \"isopy env\"?",
+            app.cwd.display()
+        );
+    };
+
+    let Some(env_info) = dir_info.make_env_info(app)? else {
+        user_error!("Could not get environment info");
+    };
+
+    let isopy_env = IsopyEnv::from_dir_info(&dir_info);
+    print!("{}", make_activate_script(shell, &isopy_env, &env_info)?);
+    success!();
+}
+
+// Generates a script to be evaluated by the shell which saves every
+// variable it changes and defines "deactivate" to restore them: cmd has no
+// functions so is not supported
+pub(crate) fn make_activate_script(
+    shell: Shell,
+    isopy_env: &IsopyEnv,
+    env_info: &EnvInfo,
+) -> Result<String> {
+    if shell == Shell::Cmd {
+        bail!("activation scripts are not supported for cmd");
+    }
+
+    let isopy_env_name = EnvKey::IsopyEnv.name();
+
+    let mut names = vec!["PATH", isopy_env_name];
+    names.extend(env_info.unset_vars.iter().map(String::as_str));
+    names.extend(env_info.vars.iter().map(|(k, _)| k.as_str()));
+    names.extend(env_info.path_vars.iter().map(|v| v.name.as_str()));
+    let mut seen = Vec::new();
+    names.retain(|name| {
+        if seen.contains(name) {
+            false
+        } else {
+            seen.push(*name);
+            true
+        }
+    });
+
+    let mut s = String::new();
+
+    _ = writeln!(s, "{}", begin_deactivate(shell));
+    for name in &names {
+        _ = writeln!(s, "    {}", restore_var(shell, name));
+    }
+    _ = writeln!(s, "{}", end_deactivate(shell));
+
+    for name in &names {
+        _ = writeln!(s, "{}", save_var(shell, name));
+    }
+
+    for name in &env_info.unset_vars {
+        _ = writeln!(s, "{}", unset_var(shell, name));
+    }
+
+    _ = writeln!(s, "{}", set_var(shell, isopy_env_name, &isopy_env.value()));
+
+    for (name, value) in &env_info.vars {
+        _ = writeln!(s, "{}", set_var(shell, name, value));
+    }
+
+    let mut path_vars = vec![("PATH", PathVarPosition::Prepend, &env_info.path_dirs)];
+    path_vars.extend(
+        env_info
+            .path_vars
+            .iter()
+            .map(|v| (v.name.as_str(), v.position, &v.dirs)),
+    );
+
+    for (name, position, dirs) in path_vars {
+        if dirs.is_empty() {
+            continue;
+        }
+
+        let mut rendered_dirs = Vec::new();
+        for dir in dirs {
+            let dir = render_absolute_path(shell, dir)?;
+            rendered_dirs
+                .push(String::from(dir.to_str().ok_or_else(|| {
+                    anyhow!("cannot convert path {}", dir.display())
+                })?));
+        }
+
+        _ = writeln!(s, "{}", add_paths(shell, name, position, &rendered_dirs)?);
+    }
+
+    if let Some(line) = rehash(shell) {
+        _ = writeln!(s, "{line}");
+    }
+
+    Ok(s)
+}
+
+const fn begin_deactivate(shell: Shell) -> &'static str {
+    match shell {
+        Shell::Bash | Shell::Cmd | Shell::Zsh => "deactivate() {",
+        Shell::Fish => "function deactivate",
+        Shell::Nushell => "def --env deactivate [] {",
+        Shell::PowerShell => "function global:deactivate {",
+    }
+}
+
+fn end_deactivate(shell: Shell) -> String {
+    let rehash = rehash(shell)
+        .map(|line| format!("    {line}\n"))
+        .unwrap_or_default();
+    match shell {
+        Shell::Bash | Shell::Cmd | Shell::Zsh => {
+            format!("{rehash}    unset -f deactivate\n}}")
+        }
+        Shell::Fish => format!("{rehash}    functions -e deactivate\nend"),
+        Shell::Nushell => String::from("}"),
+        Shell::PowerShell => String::from("    Remove-Item -Path function:deactivate\n}"),
+    }
+}
+
+// Shells that cache command locations must forget them when PATH changes
+const fn rehash(shell: Shell) -> Option<&'static str> {
+    match shell {
+        Shell::Bash | Shell::Zsh => Some("hash -r 2> /dev/null || true"),
+        Shell::Cmd | Shell::Fish | Shell::Nushell | Shell::PowerShell => None,
+    }
+}
+
+fn save_var(shell: Shell, name: &str) -> String {
+    let saved = format!("{SAVED_VAR_PREFIX}{name}");
+    match shell {
+        Shell::Bash | Shell::Cmd | Shell::Zsh => {
+            format!("if [ -n \"${{{name}+x}}\" ]; then {saved}=\"${name}\"; fi")
+        }
+        Shell::Fish => format!("if set -q {name}; set -g {saved} ${name}; end"),
+        Shell::Nushell => format!("if \"{name}\" in $env {{ $env.{saved} = $env.{name} }}"),
+        Shell::PowerShell => {
+            format!("if (Test-Path -Path env:{name}) {{ $global:{saved} = $env:{name} }}")
+        }
+    }
+}
+
+fn restore_var(shell: Shell, name: &str) -> String {
+    let saved = format!("{SAVED_VAR_PREFIX}{name}");
+    match shell {
+        Shell::Bash | Shell::Cmd | Shell::Zsh => format!(
+            "if [ -n \"${{{saved}+x}}\" ]; then export {name}=\"${saved}\"; unset {saved}; else unset {name}; fi"
+        ),
+        Shell::Fish => format!(
+            "if set -q {saved}; set -gx {name} ${saved}; set -e -g {saved}; else; set -e -g {name}; end"
+        ),
+        Shell::Nushell => format!(
+            "if \"{saved}\" in $env {{ $env.{name} = $env.{saved}; hide-env {saved} }} else {{ hide-env -i {name} }}"
+        ),
+        Shell::PowerShell => format!(
+            "if (Test-Path -Path variable:global:{saved}) {{ $env:{name} = $global:{saved}; Remove-Variable -Name {saved} -Scope Global }} else {{ Remove-Item -Path env:{name} -ErrorAction SilentlyContinue }}"
+        ),
+    }
+}
+
+fn unset_var(shell: Shell, name: &str) -> String {
+    match shell {
+        Shell::Bash | Shell::Cmd | Shell::Zsh => format!("unset {name}"),
+        Shell::Fish => format!("set -e -g {name}"),
+        Shell::Nushell => format!("hide-env -i {name}"),
+        Shell::PowerShell => {
+            format!("Remove-Item -Path env:{name} -ErrorAction SilentlyContinue")
+        }
+    }
+}
+
+fn set_var(shell: Shell, name: &str, value: &str) -> String {
+    let value = quote(shell, value);
+    match shell {
+        Shell::Bash | Shell::Cmd | Shell::Zsh => format!("export {name}={value}"),
+        Shell::Fish => format!("set -gx {name} {value}"),
+        Shell::Nushell => format!("$env.{name} = {value}"),
+        Shell::PowerShell => format!("$env:{name} = {value}"),
+    }
+}
+
+// Empty list elements are dropped since they can mean the current directory
+fn add_paths(
+    shell: Shell,
+    name: &str,
+    position: PathVarPosition,
+    dirs: &[String],
+) -> Result<String> {
+    let quoted_dirs = dirs
+        .iter()
+        .map(|d| quote(shell, d))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Ok(match shell {
+        Shell::Bash | Shell::Cmd | Shell::Zsh => {
+            let sep = path_separator(shell)
+                .to_str()
+                .ok_or_else(|| anyhow!("cannot convert path separator"))?;
+            let joined = join_paths(shell, dirs.iter());
+            let joined = quote(
+                shell,
+                joined
+                    .to_str()
+                    .ok_or_else(|| anyhow!("failed to generate {name} environment variable"))?,
+            );
+            match position {
+                PathVarPosition::Prepend => {
+                    format!("export {name}={joined}\"${{{name}:+{sep}${name}}}\"")
+                }
+                PathVarPosition::Append => {
+                    format!("export {name}=\"${{{name}:+${name}{sep}}}\"{joined}")
+                }
+            }
+        }
+        Shell::Fish => match position {
+            PathVarPosition::Prepend => format!("set -gx --path {name} {quoted_dirs} ${name}"),
+            PathVarPosition::Append => format!("set -gx --path {name} ${name} {quoted_dirs}"),
+        },
+        Shell::Nushell => {
+            // PATH is a list in Nushell while other variables must remain
+            // strings to be passed to external commands
+            let op = match position {
+                PathVarPosition::Prepend => "prepend",
+                PathVarPosition::Append => "append",
+            };
+            let join = if name == "PATH" {
+                ""
+            } else {
+                " | str join (char esep)"
+            };
+            format!(
+                "$env.{name} = ($env.{name}? | default [] | split row (char esep) | compact --empty | {op} [{quoted_dirs}]{join})"
+            )
+        }
+        Shell::PowerShell => {
+            let quoted_dirs = dirs
+                .iter()
+                .map(|d| quote(shell, d))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let items = match position {
+                PathVarPosition::Prepend => format!("@({quoted_dirs}) + @($env:{name})"),
+                PathVarPosition::Append => format!("@($env:{name}) + @({quoted_dirs})"),
+            };
+            format!(
+                "$env:{name} = ({items} | Where-Object {{ $_ }}) -join [IO.Path]::PathSeparator"
+            )
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use anyhow::Result;
+    use isopy_lib::{EnvInfo, PathVar, PathVarPosition, Shell};
+    use rstest::rstest;
+
+    use crate::commands::activate::make_activate_script;
+    use crate::shell::IsopyEnv;
+
+    fn make_isopy_env() -> Result<IsopyEnv> {
+        Ok(IsopyEnv {
+            link_id: "0123456789abcdef".parse()?,
+            meta_id: "41941524-f6da-41df-a06f-db5c55f32a3d".parse()?,
+        })
+    }
+
+    fn make_env_info(root: &str) -> EnvInfo {
+        EnvInfo {
+            path_dirs: vec![PathBuf::from(root).join("bin")],
+            vars: vec![(String::from("GREETING"), String::from("it's here"))],
+            unset_vars: vec![String::from("PYTHONHOME")],
+            path_vars: vec![PathVar {
+                name: String::from("PKG_CONFIG_PATH"),
+                position: PathVarPosition::Append,
+                dirs: vec![PathBuf::from(root).join("lib").join("pkgconfig")],
+            }],
+        }
+    }
+
+    #[rstest]
+    #[case(Shell::Bash, "deactivate() {", "export GREETING='it'\\''s here'")]
+    #[case(Shell::Zsh, "deactivate() {", "export GREETING='it'\\''s here'")]
+    #[case(Shell::Fish, "function deactivate", "set -gx GREETING 'it\\'s here'")]
+    #[case(
+        Shell::Nushell,
+        "def --env deactivate [] {",
+        "$env.GREETING = \"it's here\""
+    )]
+    #[case(
+        Shell::PowerShell,
+        "function global:deactivate {",
+        "$env:GREETING = 'it''s here'"
+    )]
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn make_activate_script_basics(
+        #[case] shell: Shell,
+        #[case] expected_first_line: &str,
+        #[case] expected_line: &str,
+    ) -> Result<()> {
+        let script = make_activate_script(shell, &make_isopy_env()?, &make_env_info("/env"))?;
+        assert_eq!(Some(expected_first_line), script.lines().next());
+        assert!(script.lines().any(|line| line == expected_line));
+        assert!(script.contains("_ISOPY_OLD_PYTHONHOME"));
+        Ok(())
+    }
+
+    #[test]
+    fn make_activate_script_cmd() -> Result<()> {
+        assert!(
+            make_activate_script(Shell::Cmd, &make_isopy_env()?, &make_env_info("/env")).is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn activate_and_deactivate_bash() -> Result<()> {
+        use std::process::Command;
+
+        let isopy_env = make_isopy_env()?;
+        let script = make_activate_script(Shell::Bash, &isopy_env, &make_env_info("/env"))?;
+        let show = r#"echo "$PATH|${PYTHONHOME-unset}|${GREETING-unset}|${PKG_CONFIG_PATH-unset}|${ISOPY_ENV-unset}""#;
+        let output = Command::new("bash")
+            .arg("-euc")
+            .arg(format!("eval \"$SCRIPT\"\n{show}\ndeactivate\n{show}\n"))
+            .env_clear()
+            .env("PATH", "/usr/bin:/bin")
+            .env("PYTHONHOME", "/elsewhere")
+            .env("SCRIPT", script)
+            .output()?;
+        assert!(output.status.success());
+        assert_eq!(
+            format!(
+                "/env/bin:/usr/bin:/bin|unset|it's here|/env/lib/pkgconfig|{}\n/usr/bin:/bin|/elsewhere|unset|unset|unset\n",
+                isopy_env.value()
+            ),
+            String::from_utf8(output.stdout)?
+        );
+        Ok(())
+    }
+}
@@ -23,7 +23,7 @@ use crate::app::App;
 use crate::print::{make_prop_table, print_link, print_metadir};
 use crate::repo::Trash;
 use crate::status::{StatusResult, success};
-use crate::table::table_divider;
+use crate::table::{table_columns, table_divider};
 
 pub(crate) fn do_check(app: &App, clean: bool) -> StatusResult {
     let mut trash = Trash::compute(&app.repo)?;
@@ -74,6 +74,27 @@ pub(crate) fn do_check(app: &App, clean: bool) -> StatusResult {
         }
     }
 
+    let unreferenced_shared_count = trash.unreferenced_shared.len();
+    if unreferenced_shared_count > 0 {
+        if clean {
+            table_divider!(
+                table,
+                "The following {unreferenced_shared_count} shared installations are unreferenced and will be removed:"
+            );
+        } else {
+            table_divider!(
+                table,
+                "The following {unreferenced_shared_count} shared installations are unreferenced and can be removed with the --clean option:"
+            );
+        }
+
+        for (idx, path) in trash.unreferenced_shared.iter().enumerate() {
+            let i = idx + 1;
+            table_divider!(table, "({i})");
+            table_columns!(table, "Shared directory", path.display());
+        }
+    }
+
     table.print();
 
     if clean {
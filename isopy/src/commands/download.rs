@@ -19,7 +19,7 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
-use isopy_lib::{DownloadPackageOptionsBuilder, TagFilter};
+use isopy_lib::{DownloadPackageOptionsBuilder, TagFilter, Target};
 use log::info;
 
 use crate::app::App;
@@ -30,9 +30,11 @@ pub(crate) async fn do_download(
     app: &App,
     package_id: &PackageId,
     tag_filter: &TagFilter,
+    target: Target,
 ) -> StatusResult {
     let options = DownloadPackageOptionsBuilder::default()
         .show_progress(app.show_progress)
+        .target(target)
         .build()?;
     app.plugin_manager
         .new_package_manager(&package_id.moniker, &app.config_dir)
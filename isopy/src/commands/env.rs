@@ -26,11 +26,14 @@ use crate::package_id::PackageId;
 use crate::status::{StatusResult, success};
 
 pub(crate) async fn do_env(app: &App, package_id: &PackageId, download: bool) -> StatusResult {
+    let target = app.host_target()?;
     let download_package_options = DownloadPackageOptionsBuilder::default()
         .show_progress(app.show_progress)
+        .target(target)
         .build()?;
     let install_package_options = InstallPackageOptionsBuilder::default()
         .show_progress(app.show_progress)
+        .target(target)
         .build()?;
 
     if download {
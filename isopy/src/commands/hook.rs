@@ -0,0 +1,306 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::env::current_exe;
+use std::fmt::Write;
+use std::fs::{metadata, read_dir, read_to_string, remove_file};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use anyhow::{Result, anyhow};
+use isopy_lib::{Shell, quote};
+
+use crate::app::App;
+use crate::commands::activate::make_activate_script;
+use crate::constants::HOOKS_DIR_NAME;
+use crate::dir_info_ext::DirInfoExt;
+use crate::repo::DirInfo;
+use crate::shell::IsopyEnv;
+use crate::status::{StatusResult, success, user_error};
+use crate::write::safe_write_file;
+
+// Shell variable, not exported to child processes, holding the key of the
+// environment activated by the hook
+const HOOK_KEY_VAR: &str = "_ISOPY_HOOK_KEY";
+
+pub(crate) fn do_hook(shell: Shell) -> StatusResult {
+    let exe = current_exe()?;
+    let exe = exe
+        .to_str()
+        .ok_or_else(|| anyhow!("cannot convert path {}", exe.display()))?;
+
+    let Some(script) = make_hook_script(shell, exe) else {
+        user_error!("shell hooks are only supported for bash, zsh and fish");
+    };
+
+    print!("{script}");
+    success!();
+}
+
+// Prints the commands needed to move from the environment previously
+// activated by the hook, if any, to the environment for the current
+// directory: prints nothing if they are the same
+pub(crate) fn do_hook_env(app: &App, shell: Shell, key: Option<&str>) -> StatusResult {
+    if !matches!(shell, Shell::Bash | Shell::Fish | Shell::Zsh) {
+        user_error!("shell hooks are only supported for bash, zsh and fish");
+    }
+
+    let key = key.filter(|k| !k.is_empty());
+    let active = IsopyEnv::get_vars()?.is_some();
+
+    // Environments activated by "isopy sh" or "isopy activate" are left
+    // alone
+    if active && key.is_none() {
+        success!();
+    }
+
+    let target = match app.find_dir_info(None)? {
+        Some(dir_info) => make_hook_key(&dir_info, &app.config_value_path)?.map(|k| (dir_info, k)),
+        None => None,
+    };
+
+    if target.as_ref().map(|(_, k)| k.as_str()) == key {
+        success!();
+    }
+
+    let target = match &target {
+        Some((dir_info, target_key)) => Some((
+            target_key.as_str(),
+            get_activate_script(app, shell, dir_info, target_key)?,
+        )),
+        None => None,
+    };
+
+    print!(
+        "{}",
+        make_hook_env_script(
+            shell,
+            key.is_some() && active,
+            target.as_ref().map(|(k, s)| (*k, s.as_str()))
+        )
+    );
+    success!();
+}
+
+fn make_hook_script(shell: Shell, exe: &str) -> Option<String> {
+    let exe = quote(shell, exe);
+    match shell {
+        Shell::Bash => Some(format!(
+            r#"_isopy_hook() {{
+    local previous_exit_status=$?
+    eval "$({exe} hook-env --shell bash --key "${{{HOOK_KEY_VAR}-}}")"
+    return $previous_exit_status
+}}
+if [[ ";${{PROMPT_COMMAND[*]:-}};" != *";_isopy_hook;"* ]]; then
+    PROMPT_COMMAND="_isopy_hook${{PROMPT_COMMAND:+;$PROMPT_COMMAND}}"
+fi
+"#
+        )),
+        Shell::Zsh => Some(format!(
+            r#"_isopy_hook() {{
+    eval "$({exe} hook-env --shell zsh --key "${{{HOOK_KEY_VAR}-}}")"
+}}
+typeset -ag precmd_functions
+if (( ! ${{precmd_functions[(I)_isopy_hook]}} )); then
+    precmd_functions=(_isopy_hook $precmd_functions)
+fi
+typeset -ag chpwd_functions
+if (( ! ${{chpwd_functions[(I)_isopy_hook]}} )); then
+    chpwd_functions=(_isopy_hook $chpwd_functions)
+fi
+"#
+        )),
+        Shell::Fish => Some(format!(
+            r#"function _isopy_hook --on-event fish_prompt --on-variable PWD
+    {exe} hook-env --shell fish --key "${HOOK_KEY_VAR}" | source
+end
+"#
+        )),
+        Shell::Cmd | Shell::Nushell | Shell::PowerShell => None,
+    }
+}
+
+fn make_hook_env_script(shell: Shell, deactivate: bool, target: Option<(&str, &str)>) -> String {
+    let mut s = String::new();
+
+    if deactivate {
+        _ = writeln!(s, "deactivate");
+    }
+
+    match target {
+        Some((key, activate_script)) => {
+            s.push_str(activate_script);
+            match shell {
+                Shell::Fish => _ = writeln!(s, "set -g {HOOK_KEY_VAR} {}", quote(shell, key)),
+                _ => _ = writeln!(s, "{HOOK_KEY_VAR}={}", quote(shell, key)),
+            }
+        }
+        None => match shell {
+            Shell::Fish => _ = writeln!(s, "set -e -g {HOOK_KEY_VAR}"),
+            _ => _ = writeln!(s, "unset {HOOK_KEY_VAR}"),
+        },
+    }
+
+    s
+}
+
+// Environment configuration is rewritten whenever packages are added to the
+// environment so its modification time identifies the environment's state:
+// configuration values such as env_workspaces also affect environment info
+fn make_hook_key(dir_info: &DirInfo, config_value_path: &Path) -> Result<Option<String>> {
+    fn mtime(path: &Path) -> Result<Option<u128>> {
+        let Ok(metadata) = metadata(path) else {
+            return Ok(None);
+        };
+        Ok(Some(
+            metadata.modified()?.duration_since(UNIX_EPOCH)?.as_nanos(),
+        ))
+    }
+
+    let Some(env_mtime) = mtime(&dir_info.env_config_path())? else {
+        return Ok(None);
+    };
+
+    let config_mtime = mtime(config_value_path)?.unwrap_or_default();
+    Ok(Some(format!(
+        "{}-{env_mtime}-{config_mtime}",
+        dir_info.link_id()
+    )))
+}
+
+// Generating environment info can be slow, e.g. for external plugins, so
+// activation scripts are cached by hook key
+fn get_activate_script(app: &App, shell: Shell, dir_info: &DirInfo, key: &str) -> Result<String> {
+    let hooks_dir = app.config_dir.join(HOOKS_DIR_NAME);
+    let extension = match shell {
+        Shell::Bash => "bash",
+        Shell::Cmd => "cmd",
+        Shell::Fish => "fish",
+        Shell::Nushell => "nu",
+        Shell::PowerShell => "ps1",
+        Shell::Zsh => "zsh",
+    };
+    let path = hooks_dir.join(format!("{key}.{extension}"));
+    if let Ok(script) = read_to_string(&path) {
+        return Ok(script);
+    }
+
+    let Some(env_info) = dir_info.make_env_info(app)? else {
+        return Ok(String::new());
+    };
+
+    let script = make_activate_script(shell, &IsopyEnv::from_dir_info(dir_info), &env_info)?;
+    remove_stale_scripts(&hooks_dir, &dir_info.link_id().to_string(), key)?;
+    safe_write_file(&path, &script, true)?;
+    Ok(script)
+}
+
+fn remove_stale_scripts(hooks_dir: &Path, link_id: &str, key: &str) -> Result<()> {
+    let Ok(entries) = read_dir(hooks_dir) else {
+        return Ok(());
+    };
+
+    let prefix = format!("{link_id}-");
+    for entry in entries {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else {
+            continue;
+        };
+
+        if file_name.starts_with(&prefix) && file_name.split('.').next() != Some(key) {
+            remove_file(entry.path())?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use isopy_lib::Shell;
+    use rstest::rstest;
+
+    use crate::commands::hook::make_hook_script;
+
+    #[rstest]
+    #[case(Shell::Bash, "PROMPT_COMMAND")]
+    #[case(Shell::Zsh, "precmd_functions")]
+    #[case(Shell::Fish, "--on-event fish_prompt")]
+    fn make_hook_script_basics(#[case] shell: Shell, #[case] expected: &str) {
+        let script = make_hook_script(shell, "/bin/isopy").expect("must be supported");
+        assert!(script.contains("'/bin/isopy' hook-env --shell"));
+        assert!(script.contains(expected));
+    }
+
+    #[rstest]
+    #[case(Shell::Cmd)]
+    #[case(Shell::Nushell)]
+    #[case(Shell::PowerShell)]
+    fn make_hook_script_unsupported(#[case] shell: Shell) {
+        assert!(make_hook_script(shell, "/bin/isopy").is_none());
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn hook_env_bash() -> anyhow::Result<()> {
+        use std::path::PathBuf;
+        use std::process::Command;
+
+        use isopy_lib::EnvInfo;
+
+        use crate::commands::activate::make_activate_script;
+        use crate::commands::hook::make_hook_env_script;
+        use crate::shell::IsopyEnv;
+
+        let isopy_env = IsopyEnv {
+            link_id: "0123456789abcdef".parse()?,
+            meta_id: "41941524-f6da-41df-a06f-db5c55f32a3d".parse()?,
+        };
+        let env_info = EnvInfo {
+            path_dirs: vec![PathBuf::from("/env/bin")],
+            vars: Vec::new(),
+            unset_vars: Vec::new(),
+            path_vars: Vec::new(),
+        };
+        let activate_script = make_activate_script(Shell::Bash, &isopy_env, &env_info)?;
+        let enter = make_hook_env_script(Shell::Bash, false, Some(("key", &activate_script)));
+        let leave = make_hook_env_script(Shell::Bash, true, None);
+
+        let show = r#"echo "$PATH|${_ISOPY_HOOK_KEY-unset}|${ISOPY_ENV+set}""#;
+        let output = Command::new("bash")
+            .arg("-euc")
+            .arg(format!(
+                "eval \"$ENTER\"\n{show}\neval \"$LEAVE\"\n{show}\n"
+            ))
+            .env_clear()
+            .env("PATH", "/usr/bin:/bin")
+            .env("ENTER", enter)
+            .env("LEAVE", leave)
+            .output()?;
+        assert!(output.status.success());
+        assert_eq!(
+            "/env/bin:/usr/bin:/bin|key|set\n/usr/bin:/bin|unset|\n",
+            String::from_utf8(output.stdout)?
+        );
+        Ok(())
+    }
+}
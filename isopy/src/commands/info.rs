@@ -19,6 +19,8 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
+use isopy_lib::detect_libc;
+
 use crate::app::App;
 use crate::constants::CONFIG_NAMES;
 use crate::env::{get_env_keys, read_env};
@@ -42,6 +44,12 @@ pub(crate) fn do_info(app: &App) -> StatusResult {
     table_title!(table, "Repository information");
     print_repo(&mut table, &app.repo);
 
+    table_title!(table, "Target");
+    table_columns!(table, "Host target", app.host_target()?);
+    if let Some(libc) = detect_libc() {
+        table_columns!(table, "Detected C library", libc);
+    }
+
     table_title!(table, "Configuration");
     table_columns!(table, "Configuration directory", app.config_dir.display());
     table_columns!(
@@ -19,15 +19,19 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
+use anyhow::Result;
 use isopy_lib::{
     DownloadPackageOptionsBuilder, GetPackageOptionsBuilder, InstallPackageOptionsBuilder,
     TagFilter,
 };
+use log::info;
 
 use crate::app::App;
+use crate::constants::PROJECT_CONFIG_FILE_NAME;
+use crate::serialization::Project;
 use crate::status::{StatusResult, success, user_error};
 
-pub async fn do_init(app: &App, download: bool) -> StatusResult {
+pub async fn do_init(app: &App, download: bool, write: bool) -> StatusResult {
     if app.repo.get(&app.cwd)?.is_some() {
         user_error!(
             "Project in directory {} already has an environment",
@@ -35,11 +39,31 @@ pub async fn do_init(app: &App, download: bool) -> StatusResult {
         );
     }
 
-    let project = app.read_project_config()?;
+    // Without a project configuration file, fall back to versions inferred
+    // from files such as go.mod
+    let project = if app.has_project_config_file() {
+        app.read_project_config()?
+    } else if let Some(project) = app.infer_project_config().await? {
+        if write {
+            app.write_project_config(&project, false)?;
+            info!(
+                "Wrote inferred project configuration to {}",
+                app.cwd.join(PROJECT_CONFIG_FILE_NAME).display()
+            );
+        }
+        project
+    } else {
+        user_error!(
+            "Project in directory {} has no {PROJECT_CONFIG_FILE_NAME} file and no package versions could be inferred from project files",
+            app.cwd.display()
+        );
+    };
+    let target = app.host_target()?;
 
     if download {
         let download_package_options = DownloadPackageOptionsBuilder::default()
             .show_progress(app.show_progress)
+            .target(target)
             .build()?;
 
         for package_id in &project.package_ids {
@@ -57,6 +81,7 @@ pub async fn do_init(app: &App, download: bool) -> StatusResult {
     } else {
         let get_package_options = GetPackageOptionsBuilder::default()
             .show_progress(app.show_progress)
+            .target(target)
             .build()?;
 
         let mut unavailable_package_ids = Vec::new();
@@ -81,8 +106,20 @@ pub async fn do_init(app: &App, download: bool) -> StatusResult {
         }
     }
 
+    // Packages and virtual environment are installed as a unit: since the
+    // project had no environment on entry, any failure removes it entirely
+    if let Err(e) = install_project(app, &project).await {
+        _ = app.remove_project_env(&app.cwd);
+        return Err(e);
+    }
+
+    success!();
+}
+
+async fn install_project(app: &App, project: &Project) -> Result<()> {
     let install_package_options = InstallPackageOptionsBuilder::default()
         .show_progress(app.show_progress)
+        .target(app.host_target()?)
         .build()?;
 
     for package_id in &project.package_ids {
@@ -94,5 +131,11 @@ pub async fn do_init(app: &App, download: bool) -> StatusResult {
         .await?;
     }
 
-    success!();
+    if let Some(venv) = &project.venv {
+        app.create_venv(venv).await?;
+    }
+
+    app.set_env_isolated(project.isolated)?;
+
+    Ok(())
 }
@@ -19,16 +19,19 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
+mod activate;
 mod check;
 mod completions;
 mod docs;
 mod download;
 mod env;
+mod hook;
 mod info;
 mod init;
 mod link;
 mod list;
 mod packages;
+mod plugins;
 mod project;
 mod prompt;
 mod remove;
@@ -40,16 +43,19 @@ mod tags;
 mod update;
 mod wrap;
 
+pub(crate) use activate::*;
 pub(crate) use check::*;
 pub(crate) use completions::*;
 pub(crate) use docs::*;
 pub(crate) use download::*;
 pub(crate) use env::*;
+pub(crate) use hook::*;
 pub(crate) use info::*;
 pub(crate) use init::*;
 pub(crate) use link::*;
 pub(crate) use list::*;
 pub(crate) use packages::*;
+pub(crate) use plugins::*;
 pub(crate) use project::*;
 pub(crate) use prompt::*;
 pub(crate) use remove::*;
@@ -26,6 +26,7 @@ use anyhow::bail;
 use colored::{ColoredString, Colorize};
 use isopy_lib::{
     ListPackagesOptions, ListPackagesOptionsBuilder, PackageInfo, Plugin, SourceFilter, TagFilter,
+    Target,
 };
 use url::{Host, Url};
 
@@ -40,6 +41,7 @@ pub(crate) async fn do_packages(
     moniker: Option<&Moniker>,
     source_filter: SourceFilter,
     tag_filter: &TagFilter,
+    target: Target,
     verbose: bool,
 ) -> StatusResult {
     async fn list_packages(
@@ -77,6 +79,7 @@ pub(crate) async fn do_packages(
     let mut table = make_list_table();
     let options = ListPackagesOptionsBuilder::default()
         .show_progress(app.show_progress)
+        .target(target)
         .build()?;
 
     match moniker {
@@ -0,0 +1,61 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use crate::plugin_registry::{is_plugin_enabled, plugin_descriptors};
+use crate::print::make_list_table;
+use crate::status::{StatusResult, success};
+use crate::table::{table_columns, table_headings};
+
+pub(crate) fn do_plugins() -> StatusResult {
+    let mut table = make_list_table();
+    table_headings!(
+        table,
+        "Moniker",
+        "Status",
+        "Stability",
+        "Enable with",
+        "Description"
+    );
+
+    for &descriptor in plugin_descriptors() {
+        let status = if is_plugin_enabled(descriptor) {
+            "enabled"
+        } else {
+            "disabled"
+        };
+        let enable_with = descriptor
+            .enable_env_var
+            .as_ref()
+            .map_or_else(|| String::from("(always enabled)"), |e| format!("{e}=true"));
+        table_columns!(
+            table,
+            descriptor.name,
+            status,
+            descriptor.stability,
+            enable_with,
+            descriptor.description
+        );
+    }
+
+    table.print();
+
+    success!();
+}
@@ -25,6 +25,7 @@ use anyhow::{Error, bail};
 use log::info;
 
 use crate::app::App;
+use crate::constants::PROJECT_CONFIG_FILE_NAME;
 use crate::package_id::PackageId;
 use crate::serialization::Project;
 use crate::status::{StatusResult, success, user_error};
@@ -36,23 +37,40 @@ fn is_io_error_kind(e: &Error, error_kind: ErrorKind) -> bool {
     }
 }
 
-pub fn do_project(app: &App, package_id: &PackageId) -> StatusResult {
-    let mut package_ids = match app.read_project_config() {
-        Ok(project) => project.package_ids,
-        Err(e) if is_io_error_kind(&e, ErrorKind::NotFound) => Vec::new(),
+pub async fn do_project(app: &App, package_id: Option<&PackageId>) -> StatusResult {
+    let Some(package_id) = package_id else {
+        return write_inferred_project(app).await;
+    };
+
+    // Packages inferred from files such as go.mod are retained when creating
+    // a new project configuration file unless overridden by this package
+    let mut project = match app.read_project_config() {
+        Ok(project) => project,
+        Err(e) if is_io_error_kind(&e, ErrorKind::NotFound) => {
+            let mut project = app.infer_project_config().await?.unwrap_or(Project {
+                package_ids: Vec::new(),
+                venv: None,
+                isolated: false,
+            });
+            project
+                .package_ids
+                .retain(|p| !p.conflicts_with(package_id));
+            project
+        }
         Err(e) => bail!(e),
     };
 
-    if package_ids.iter().any(|p| p.moniker == package_id.moniker) {
-        user_error!(
-            "Environment already has a package from package manager \"{}\"",
-            package_id.moniker.as_str()
-        );
+    if let Some(p) = project
+        .package_ids
+        .iter()
+        .find(|p| p.conflicts_with(package_id))
+    {
+        user_error!("Project already has package \"{p}\"");
     }
 
-    package_ids.push(package_id.clone());
+    project.package_ids.push(package_id.clone());
 
-    app.write_project_config(&Project { package_ids }, true)?;
+    app.write_project_config(&project, true)?;
     info!(
         "Added package \"{}\" to project at {}",
         package_id,
@@ -60,3 +78,26 @@ pub fn do_project(app: &App, package_id: &PackageId) -> StatusResult {
     );
     success!();
 }
+
+async fn write_inferred_project(app: &App) -> StatusResult {
+    if app.has_project_config_file() {
+        user_error!(
+            "Project in directory {} already has a {PROJECT_CONFIG_FILE_NAME} file",
+            app.cwd.display()
+        );
+    }
+
+    let Some(project) = app.infer_project_config().await? else {
+        user_error!(
+            "No package versions could be inferred from files in project directory {}",
+            app.cwd.display()
+        );
+    };
+
+    app.write_project_config(&project, false)?;
+    info!(
+        "Wrote inferred project configuration to {}",
+        app.cwd.join(PROJECT_CONFIG_FILE_NAME).display()
+    );
+    success!();
+}
@@ -28,9 +28,13 @@ pub(crate) fn do_prompt(app: &App, prompt_config: &PromptConfig) -> StatusResult
     let isopy_env = IsopyEnv::get_vars()?;
     let env = app.find_dir_info(isopy_env.as_ref())?;
 
-    let has_project_config_file = app.has_project_config_file();
+    // Versions inferred from files such as go.mod count as configuration: the
+    // prompt is shown on every command so an unreadable file is ignored here
+    // and reported by "isopy init" instead
+    let has_project_config =
+        app.has_project_config_file() || app.can_infer_project_config().unwrap_or(false);
 
-    let prompt_message = match (isopy_env.is_some(), env.is_some(), has_project_config_file) {
+    let prompt_message = match (isopy_env.is_some(), env.is_some(), has_project_config) {
         (true, true, _) => Some(prompt_config.shell_message.as_deref().unwrap_or("(isopy)")),
         (true, false, _) => Some(prompt_config.error_message.as_deref().unwrap_or("(!)")),
         (false, true, _) => Some(prompt_config.available_message.as_deref().unwrap_or("(*)")),
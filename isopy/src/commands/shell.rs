@@ -70,6 +70,23 @@ pub(crate) fn do_shell(app: App, verbose: bool) -> StatusResult {
                 info!("  {}", format!("{k} = {v}").yellow());
             }
         }
+
+        for path_var in &env_info.path_vars {
+            info!(
+                "{}",
+                format!("{} directories ({:?}):", path_var.name, path_var.position).bright_yellow()
+            );
+            for dir in &path_var.dirs {
+                info!("  {}", format!("{}", dir.display()).yellow());
+            }
+        }
+
+        if !env_info.unset_vars.is_empty() {
+            info!("{}", "Removed environment variables:".bright_yellow());
+            for k in &env_info.unset_vars {
+                info!("  {}", k.yellow());
+            }
+        }
     }
 
     let isopy_env = IsopyEnv::from_dir_info(&dir_info);
@@ -21,13 +21,13 @@
 //
 use anyhow::Result;
 use colored::Colorize;
-use isopy_lib::{ListTagsOptions, ListTagsOptionsBuilder};
+use isopy_lib::{ListTagsOptions, ListTagsOptionsBuilder, Target};
 
 use crate::app::App;
 use crate::moniker::Moniker;
 use crate::status::{StatusResult, success};
 
-pub(crate) async fn do_tags(app: &App, moniker: Option<&Moniker>) -> StatusResult {
+pub(crate) async fn do_tags(app: &App, moniker: Option<&Moniker>, target: Target) -> StatusResult {
     async fn list_tags(app: &App, moniker: &Moniker, options: &ListTagsOptions) -> Result<()> {
         let tags = app
             .plugin_manager
@@ -60,6 +60,7 @@ pub(crate) async fn do_tags(app: &App, moniker: Option<&Moniker>) -> StatusResul
 
     let options = ListTagsOptionsBuilder::default()
         .show_progress(app.show_progress)
+        .target(target)
         .build()?;
 
     match moniker {
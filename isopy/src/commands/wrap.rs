@@ -24,7 +24,10 @@ use std::fmt::Write;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Result, anyhow, bail};
-use isopy_lib::{Platform, Shell, env_var_substitution, join_paths, render_absolute_path};
+use isopy_lib::{
+    PathVar, PathVarPosition, Platform, Shell, env_var_substitution, join_paths, path_separator,
+    render_absolute_path,
+};
 use log::info;
 use path_absolutize::Absolutize;
 use serde::Serialize;
@@ -40,21 +43,23 @@ use crate::write::safe_write_file;
 
 const BASH_WRAPPER_TEMPLATE: &str = r#"#!/bin/bash
 set -euo pipefail
-{path_env} \
-{vars}exec {command} "$@"
+{unset_vars}{path_env} \
+{vars}{path_vars}exec {command} "$@"
 "#;
 
 const CMD_WRAPPER_TEMPLATE: &str = r"@echo off
 setlocal
-{path_env}
-{vars}
+{unset_vars}{path_env}
+{vars}{path_vars}
 {command} %*
 ";
 
 #[derive(Serialize)]
 struct TemplateContext {
+    unset_vars: String,
     path_env: String,
     vars: String,
+    path_vars: String,
     command: String,
 }
 
@@ -80,6 +85,9 @@ pub(crate) fn do_wrap(
     let wrapper_template = match shell {
         Shell::Bash => BASH_WRAPPER_TEMPLATE,
         Shell::Cmd => CMD_WRAPPER_TEMPLATE,
+        Shell::Fish | Shell::Nushell | Shell::PowerShell | Shell::Zsh => {
+            user_error!("wrapper scripts can only be generated for bash and cmd")
+        }
     };
 
     let mut template = TinyTemplate::new();
@@ -94,6 +102,10 @@ pub(crate) fn do_wrap(
 
     let vars = make_vars(&env_info.vars);
 
+    let unset_vars = make_unset_vars(shell, &env_info.unset_vars)?;
+
+    let path_vars = make_path_vars(shell, &env_info.path_vars)?;
+
     let wrapper_path = make_wrapper_path(app, wrapper_file_name)?;
 
     let command = make_script_command(app, &dir_info, script_path, platform, shell)?;
@@ -101,8 +113,10 @@ pub(crate) fn do_wrap(
     let s = template.render(
         "WRAPPER",
         &TemplateContext {
+            unset_vars,
             path_env,
             vars,
+            path_vars,
             command,
         },
     )?;
@@ -125,6 +139,9 @@ fn make_path_env(shell: Shell, paths: &[PathBuf]) -> Result<OsString> {
     match shell {
         Shell::Bash => s.push("PATH="),
         Shell::Cmd => s.push("set PATH="),
+        Shell::Fish | Shell::Nushell | Shell::PowerShell | Shell::Zsh => {
+            bail!("unsupported shell {shell:?}")
+        }
     }
 
     s.push(join_paths(shell, all_paths.iter()));
@@ -151,6 +168,68 @@ fn make_vars(vars: &[(String, String)]) -> String {
     inner(vars)
 }
 
+fn make_unset_vars(shell: Shell, vars: &[String]) -> Result<String> {
+    let mut s = String::new();
+    for k in vars {
+        match shell {
+            Shell::Bash => _ = writeln!(s, "unset {k}"),
+            Shell::Cmd => _ = writeln!(s, "set {k}="),
+            Shell::Fish | Shell::Nushell | Shell::PowerShell | Shell::Zsh => {
+                bail!("unsupported shell {shell:?}")
+            }
+        }
+    }
+    Ok(s)
+}
+
+// Inherited values may be unset: bash wrappers run with "set -u" and an
+// empty list element can mean the current directory
+fn make_path_vars(shell: Shell, path_vars: &[PathVar]) -> Result<String> {
+    let sep = path_separator(shell)
+        .to_str()
+        .ok_or_else(|| anyhow!("cannot convert path separator"))?;
+
+    let mut s = String::new();
+    for path_var in path_vars {
+        let mut dirs = Vec::new();
+        for dir in &path_var.dirs {
+            dirs.push(render_absolute_path(shell, dir)?);
+        }
+
+        let dirs =
+            String::from(join_paths(shell, dirs.iter()).to_str().ok_or_else(|| {
+                anyhow!("failed to generate {} environment variable", path_var.name)
+            })?);
+
+        let k = &path_var.name;
+        match (shell, path_var.position) {
+            (Shell::Bash, PathVarPosition::Prepend) => {
+                _ = writeln!(s, "{k}={dirs}${{{k}:+{sep}${k}}} \\");
+            }
+            (Shell::Bash, PathVarPosition::Append) => {
+                _ = writeln!(s, "{k}=${{{k}:+${k}{sep}}}{dirs} \\");
+            }
+            (Shell::Cmd, PathVarPosition::Prepend) => {
+                _ = writeln!(
+                    s,
+                    "if defined {k} (set \"{k}={dirs}{sep}%{k}%\") else (set \"{k}={dirs}\")"
+                );
+            }
+            (Shell::Cmd, PathVarPosition::Append) => {
+                _ = writeln!(
+                    s,
+                    "if defined {k} (set \"{k}=%{k}%{sep}{dirs}\") else (set \"{k}={dirs}\")"
+                );
+            }
+            (Shell::Fish | Shell::Nushell | Shell::PowerShell | Shell::Zsh, _) => {
+                bail!("unsupported shell {shell:?}")
+            }
+        }
+    }
+
+    Ok(s)
+}
+
 fn make_script_command(
     app: &App,
     dir_info: &DirInfo,
@@ -216,3 +295,50 @@ fn make_wrapper_path(app: &App, wrapper_file_name: &WrapperFileName) -> Result<P
 
     inner(app, wrapper_file_name)
 }
+
+#[cfg(test)]
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod tests {
+    use std::path::PathBuf;
+
+    use anyhow::Result;
+    use isopy_lib::{PathVar, PathVarPosition, Shell};
+    use rstest::rstest;
+
+    use super::make_path_vars;
+
+    #[rstest]
+    #[case(
+        "PKG_CONFIG_PATH=/a:/b${PKG_CONFIG_PATH:+:$PKG_CONFIG_PATH} \\\n",
+        Shell::Bash,
+        PathVarPosition::Prepend
+    )]
+    #[case(
+        "PKG_CONFIG_PATH=${PKG_CONFIG_PATH:+$PKG_CONFIG_PATH:}/a:/b \\\n",
+        Shell::Bash,
+        PathVarPosition::Append
+    )]
+    #[case(
+        "if defined PKG_CONFIG_PATH (set \"PKG_CONFIG_PATH=/a;/b;%PKG_CONFIG_PATH%\") else (set \"PKG_CONFIG_PATH=/a;/b\")\n",
+        Shell::Cmd,
+        PathVarPosition::Prepend
+    )]
+    #[case(
+        "if defined PKG_CONFIG_PATH (set \"PKG_CONFIG_PATH=%PKG_CONFIG_PATH%;/a;/b\") else (set \"PKG_CONFIG_PATH=/a;/b\")\n",
+        Shell::Cmd,
+        PathVarPosition::Append
+    )]
+    fn make_path_vars_basics(
+        #[case] expected: &str,
+        #[case] shell: Shell,
+        #[case] position: PathVarPosition,
+    ) -> Result<()> {
+        let path_vars = vec![PathVar {
+            name: String::from("PKG_CONFIG_PATH"),
+            position,
+            dirs: vec![PathBuf::from("/a"), PathBuf::from("/b")],
+        }];
+        assert_eq!(expected, make_path_vars(shell, &path_vars)?);
+        Ok(())
+    }
+}
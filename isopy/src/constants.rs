@@ -28,12 +28,27 @@ pub(crate) const PACKAGE_BUILD_VERSION: Option<&str> =
 
 pub(crate) const ENV_CONFIG_FILE_NAME: &str = "env.yaml";
 pub(crate) const PROJECT_CONFIG_FILE_NAME: &str = ".isopy.yaml";
+pub(crate) const DEFAULT_VENV_DIR: &str = ".venv";
 pub(crate) const CACHE_DIR_NAME: &str = "cache";
+pub(crate) const PLUGINS_DIR_NAME: &str = "plugins";
+pub(crate) const HOOKS_DIR_NAME: &str = "hooks";
 pub(crate) const DOWNLOAD_CACHE_FILE_NAME: &str = "downloads.yaml";
 pub(crate) const CONFIG_DIR_NAME: &str = "isopy";
 pub(crate) const ISOPY_USER_AGENT: &str = "isopy";
 pub(crate) const DEFAULT_MONIKER_CONFIG_NAME: &str = "default_moniker";
-pub(crate) const CONFIG_NAMES: [&str; 1] = [DEFAULT_MONIKER_CONFIG_NAME];
+pub(crate) const PYTHON_MONIKER: &str = "python";
+pub(crate) const SHARED_INSTALLS_CONFIG_NAME: &str = "shared_installs";
+pub(crate) const LIBC_CONFIG_NAME: &str = "libc";
+pub(crate) const ENV_WORKSPACES_CONFIG_NAME: &str = "env_workspaces";
+pub(crate) const GO_TOOLCHAIN_CONFIG_NAME: &str = "go_toolchain";
+pub(crate) const CONFIG_NAMES: [&str; 5] = [
+    DEFAULT_MONIKER_CONFIG_NAME,
+    SHARED_INSTALLS_CONFIG_NAME,
+    LIBC_CONFIG_NAME,
+    ENV_WORKSPACES_CONFIG_NAME,
+    GO_TOOLCHAIN_CONFIG_NAME,
+];
+pub(crate) const WORKSPACES_DIR_NAME: &str = "workspaces";
 
 #[cfg(any(target_os = "linux", target_os = "macos"))]
 pub(crate) const EXECUTABLE_MASK: u32 = 0o100;
@@ -33,6 +33,7 @@ use crate::write::safe_write_file;
 use crate::yaml::read_yaml_file;
 
 pub(crate) trait DirInfoExt {
+    fn env_config_path(&self) -> PathBuf;
     fn read_env_config(&self) -> Result<Env>;
     fn write_env_config(&self, env: &Env, overwrite: bool) -> Result<()>;
     fn make_env_info(&self, app: &App) -> Result<Option<EnvInfo>>;
@@ -46,6 +47,10 @@ pub(crate) trait DirInfoExt {
 }
 
 impl DirInfoExt for DirInfo {
+    fn env_config_path(&self) -> PathBuf {
+        make_env_config_path(self.data_dir())
+    }
+
     fn read_env_config(&self) -> Result<Env> {
         read_env_config(self.data_dir())
     }
@@ -70,6 +75,10 @@ impl DirInfoExt for DirInfo {
 }
 
 impl DirInfoExt for Manifest {
+    fn env_config_path(&self) -> PathBuf {
+        make_env_config_path(self.data_dir())
+    }
+
     fn read_env_config(&self) -> Result<Env> {
         read_env_config(self.data_dir())
     }
@@ -116,12 +125,28 @@ fn make_env_info(app: &App, data_dir: &Path) -> Result<Option<EnvInfo>> {
     let mut all_env_info = EnvInfo {
         path_dirs: Vec::new(),
         vars: Vec::new(),
+        unset_vars: Vec::new(),
+        path_vars: Vec::new(),
     };
 
     for package in &env.packages {
-        let env_info = app.make_env_info(data_dir, package);
+        let env_info = app.make_env_info(data_dir, &env, package)?;
         all_env_info.path_dirs.extend(env_info.path_dirs);
         all_env_info.vars.extend(env_info.vars);
+        all_env_info.unset_vars.extend(env_info.unset_vars);
+
+        // Keep directories in package order as for path_dirs
+        for path_var in env_info.path_vars {
+            if let Some(existing) = all_env_info
+                .path_vars
+                .iter_mut()
+                .find(|v| v.name == path_var.name && v.position == path_var.position)
+            {
+                existing.dirs.extend(path_var.dirs);
+            } else {
+                all_env_info.path_vars.push(path_var);
+            }
+        }
     }
 
     Ok(Some(all_env_info))
@@ -28,6 +28,7 @@ use std::sync::LazyLock;
 use anyhow::{Result, bail};
 
 use crate::bool_util::str_to_bool;
+use crate::plugin_registry::builtin_plugin_descriptors;
 
 pub const BOOL_TRUE_VALUE: &str = "true";
 pub const BOOL_FALSE_VALUE: &str = "false";
@@ -36,9 +37,10 @@ pub const BOOL_FALSE_VALUE: &str = "false";
 pub enum EnvKey {
     ConfigDir,
     LogLevel,
-    GoEnabled,
-    JavaEnabled,
     IsopyEnv,
+
+    // Env var named by a plugin descriptor that enables the plugin
+    PluginEnabled(&'static str),
 }
 
 impl EnvKey {
@@ -46,9 +48,8 @@ impl EnvKey {
         match self {
             Self::ConfigDir => "ISOPY_CONFIG_DIR",
             Self::LogLevel => "ISOPY_LOG_LEVEL",
-            Self::GoEnabled => "ISOPY_GO",
-            Self::JavaEnabled => "ISOPY_JAVA",
             Self::IsopyEnv => "ISOPY_ENV",
+            Self::PluginEnabled(name) => name,
         }
     }
 
@@ -78,13 +79,18 @@ enum EnvType {
 }
 
 static CLI_ENVS: LazyLock<Vec<(EnvKey, EnvType)>> = LazyLock::new(|| {
-    vec![
+    let mut envs = vec![
         (EnvKey::ConfigDir, EnvType::Ignore),
         (EnvKey::LogLevel, EnvType::Ignore),
-        (EnvKey::GoEnabled, EnvType::Bool),
-        (EnvKey::JavaEnabled, EnvType::Bool),
         (EnvKey::IsopyEnv, EnvType::Ignore),
-    ]
+    ];
+    envs.extend(
+        builtin_plugin_descriptors()
+            .iter()
+            .filter_map(|d| d.enable_env_var.as_deref())
+            .map(|name| (EnvKey::PluginEnabled(name), EnvType::Bool)),
+    );
+    envs
 });
 
 enum Op {
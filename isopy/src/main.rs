@@ -31,12 +31,15 @@ mod dir_info_ext;
 mod download;
 mod env;
 mod executable;
+mod libc_format;
 mod moniker;
 mod package_id;
 mod package_manager_helper;
 mod paginated_download;
 mod plugin_manager;
+mod plugin_registry;
 mod print;
+mod read_only;
 mod repo;
 mod run;
 mod serialization;
@@ -57,6 +60,7 @@ async fn main() -> anyhow::Result<()> {
     use anyhow::Error;
     use anyhow::bail;
     use colored::Colorize;
+    use isopy_lib::PluginError;
 
     use crate::run::run;
     use crate::status::Status::{Success, UserError};
@@ -77,6 +81,10 @@ async fn main() -> anyhow::Result<()> {
         eprintln!("{}", error.to_string().bright_red());
     }
 
+    fn show_hint(error: &PluginError) {
+        eprintln!("{}", error.hint().bright_yellow());
+    }
+
     let result = run().await;
     match result.as_ref() {
         Ok(Success(Some(message))) => {
@@ -88,6 +96,11 @@ async fn main() -> anyhow::Result<()> {
             show_user_error(message);
             exit(USER_ERROR);
         }
+        Err(e) if let Some(plugin_error) = e.downcast_ref::<PluginError>() => {
+            show_error(e);
+            show_hint(plugin_error);
+            exit(USER_ERROR);
+        }
         Err(e) => {
             show_error(e);
             match var("RUST_BACKTRACE") {
@@ -24,53 +24,60 @@ use std::path::Path;
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
-use anyhow::{Error, bail};
+use anyhow::{Error, Result, bail};
+use isopy_lib::PluginDescriptor;
 use serde::{Deserialize, Serialize};
-use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
 
-use crate::env::EnvKey;
+use crate::plugin_registry::{find_plugin_descriptor, is_plugin_enabled, plugin_descriptors};
 
-const GO: &str = "go";
-const JAVA: &str = "java";
-const PYTHON: &str = "python";
-
-#[derive(Clone, Debug, Deserialize, EnumIter, PartialEq, Serialize)]
-#[serde(rename_all = "lowercase")]
-pub(crate) enum Moniker {
-    Go,
-    Java,
-    Python,
-}
+// Name of a registered plugin such as "python": monikers are lowercase and
+// consist of ASCII letters, digits and hyphens starting with a letter
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Moniker(String);
 
 impl Moniker {
-    pub(crate) const fn as_str(&self) -> &str {
-        match self {
-            Self::Go => GO,
-            Self::Java => JAVA,
-            Self::Python => PYTHON,
+    // Validates moniker without requiring the plugin to be enabled so that
+    // configuration referring to disabled plugins can still be read
+    fn new_registered(s: &str) -> Result<Self> {
+        let s = s.to_ascii_lowercase();
+        if !s.starts_with(|c: char| c.is_ascii_lowercase())
+            || !s
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            bail!("Invalid package manager moniker {s}")
+        }
+
+        if find_plugin_descriptor(&s).is_none() {
+            bail!(
+                "No plugin is registered for package manager moniker {s}; run \"isopy plugins\" to list available plugins"
+            )
         }
+
+        Ok(Self(s))
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
     }
 
     pub(crate) fn dir(&self) -> &Path {
         Path::new(self.as_str())
     }
 
-    /// Env var that enables this moniker, or `None` if it's always enabled.
-    pub(crate) const fn enable_env_var(&self) -> Option<EnvKey> {
-        match self {
-            Self::Go => Some(EnvKey::GoEnabled),
-            Self::Java => Some(EnvKey::JavaEnabled),
-            Self::Python => None,
-        }
+    pub(crate) fn descriptor(&self) -> &'static PluginDescriptor {
+        find_plugin_descriptor(&self.0).expect("moniker must refer to registered plugin")
     }
 
     pub(crate) fn is_enabled(&self) -> bool {
-        self.enable_env_var().is_none_or(EnvKey::is_true)
+        is_plugin_enabled(self.descriptor())
     }
 
     pub(crate) fn iter_enabled() -> impl Iterator<Item = Self> {
-        Self::iter().filter(Self::is_enabled)
+        plugin_descriptors()
+            .iter()
+            .filter(|d| is_plugin_enabled(d))
+            .map(|d| Self(d.name.clone()))
     }
 }
 
@@ -84,20 +91,65 @@ impl FromStr for Moniker {
     type Err = Error;
 
     fn from_str(s: &str) -> StdResult<Self, Self::Err> {
-        for member in Self::iter() {
-            if member.as_str().eq_ignore_ascii_case(s) {
-                return if member.is_enabled() {
-                    Ok(member)
-                } else {
-                    let name = member.as_str();
-                    let env_key = member
-                        .enable_env_var()
-                        .expect("disabled moniker must have an enable env var");
-                    let env = env_key.name();
-                    bail!("{name} plugin is not enabled; set {env}=true to enable (experimental)")
-                };
-            }
+        let moniker = Self::new_registered(s)?;
+        if !moniker.is_enabled() {
+            let env = moniker
+                .descriptor()
+                .enable_env_var
+                .as_deref()
+                .expect("disabled moniker must have an enable env var");
+            bail!("{moniker} plugin is not enabled; set {env}=true to enable (experimental)")
         }
-        bail!("Invalid package manager moniker {s}")
+        Ok(moniker)
+    }
+}
+
+impl Serialize for Moniker {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: ::serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Moniker {
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+    where
+        D: ::serde::Deserializer<'de>,
+    {
+        Self::new_registered(&String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::moniker::Moniker;
+
+    #[rstest]
+    #[case("python", "python")]
+    #[case("python", "Python")]
+    fn parse_valid(#[case] expected: &str, #[case] input: &str) {
+        assert_eq!(expected, input.parse::<Moniker>().unwrap().as_str());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("1python")]
+    #[case("py thon")]
+    #[case("py_thon")]
+    #[case("unknown")]
+    fn parse_invalid(#[case] input: &str) {
+        assert!(input.parse::<Moniker>().is_err());
+    }
+
+    #[test]
+    fn deserialize_disabled() {
+        // Disabled plugins can still be referred to by configuration
+        let moniker = serde_yaml::from_str::<Moniker>("rust").unwrap();
+        assert_eq!("rust", moniker.as_str());
+        assert!(serde_yaml::from_str::<Moniker>("unknown").is_err());
     }
 }
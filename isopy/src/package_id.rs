@@ -20,6 +20,7 @@
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::PathBuf;
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
@@ -43,6 +44,21 @@ impl PackageId {
             version: version.clone(),
         }
     }
+
+    // An environment can contain only one package for each moniker and
+    // package name
+    pub(crate) fn conflicts_with(&self, other: &Self) -> bool {
+        self.moniker == other.moniker && self.version.package_name() == other.version.package_name()
+    }
+
+    // Directory relative to the environment's data directory: monikers never
+    // contain "@" so these cannot clash
+    pub(crate) fn dir(&self) -> PathBuf {
+        match self.version.package_name() {
+            Some(name) => PathBuf::from(format!("{}@{name}", self.moniker)),
+            None => self.moniker.dir().to_path_buf(),
+        }
+    }
 }
 
 impl FromStr for PackageId {
@@ -55,9 +71,8 @@ impl FromStr for PackageId {
 
         let moniker = moniker_str.parse::<Moniker>()?;
         let plugin_manager = PluginManager::new();
-        let plugin = plugin_manager.get_plugin(&moniker);
 
-        let Ok(version) = plugin.parse_version(version_str) else {
+        let Ok(version) = plugin_manager.parse_version(&moniker, version_str) else {
             bail!("Invalid version string {s} for plugin {moniker}");
         };
 
@@ -89,3 +104,41 @@ impl<'de> Deserialize<'de> for PackageId {
             .map_err(serde::de::Error::custom)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use anyhow::Result;
+    use rstest::rstest;
+
+    use crate::moniker::Moniker;
+    use crate::package_id::PackageId;
+    use crate::plugin_manager::PluginManager;
+
+    // Bypasses checks for enabled plugins
+    fn package_id(s: &str) -> Result<PackageId> {
+        let (moniker, version) = s.split_once(':').expect("must be valid package ID");
+        let moniker = serde_yaml::from_str::<Moniker>(moniker)?;
+        let version = PluginManager::new().parse_version(&moniker, version)?;
+        Ok(PackageId::new(&moniker, &version))
+    }
+
+    #[rstest]
+    #[case(true, "python:3.13.1", "python:3.12.0")]
+    #[case(false, "python:3.13.1", "binary:terraform@1.8.0")]
+    #[case(true, "binary:terraform@1.8.0", "binary:terraform@1.9.0")]
+    #[case(false, "binary:terraform@1.8.0", "binary:jq@1.7.1")]
+    fn conflicts_with(#[case] expected: bool, #[case] a: &str, #[case] b: &str) -> Result<()> {
+        assert_eq!(expected, package_id(a)?.conflicts_with(&package_id(b)?));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("python", "python:3.13.1")]
+    #[case("binary@terraform", "binary:terraform@1.8.0")]
+    fn dir(#[case] expected: &str, #[case] input: &str) -> Result<()> {
+        assert_eq!(PathBuf::from(expected), package_id(input)?.dir());
+        Ok(())
+    }
+}
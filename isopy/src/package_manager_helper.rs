@@ -41,16 +41,22 @@ use crate::paginated_download::{
     get_download_paginated_asset_response, get_download_paginated_asset_response_from_dir,
 };
 
+#[allow(clippy::struct_field_names)]
 pub(crate) struct PackageManagerHelper {
+    config_dir: PathBuf,
     base_dir: PathBuf,
     downloads_dir: PathBuf,
 }
 
 impl PackageManagerHelper {
-    pub(crate) fn new_context<P: Into<PathBuf>>(base_dir: P) -> PackageManagerContext {
+    pub(crate) fn new_context<P: Into<PathBuf>>(
+        config_dir: &Path,
+        base_dir: P,
+    ) -> PackageManagerContext {
         let base_dir = base_dir.into();
         let downloads_dir = base_dir.join("downloads");
         PackageManagerContext::new(Self {
+            config_dir: config_dir.to_path_buf(),
             base_dir,
             downloads_dir,
         })
@@ -63,6 +69,10 @@ impl PackageManagerContextOps for PackageManagerHelper {
         &self.base_dir
     }
 
+    fn config_dir(&self) -> &Path {
+        &self.config_dir
+    }
+
     fn check_asset(&self, url: &Url) -> Result<Option<PathBuf>> {
         check_cache::<FileCacheItem>(&self.base_dir, &self.downloads_dir, url)
     }
@@ -22,39 +22,78 @@
 use std::path::Path;
 use std::sync::OnceLock;
 
-use isopy_lib::{PackageManager, Plugin};
+use anyhow::Result;
+use isopy_lib::{
+    EnvInfo, MakeEnvInfoOptions, PackageManager, Plugin, PluginDescriptor, ProjectVersion,
+    Stability, Version,
+};
 use log::warn;
 
 use crate::constants::CACHE_DIR_NAME;
 use crate::moniker::Moniker;
 use crate::package_manager_helper::PackageManagerHelper;
+use crate::plugin_registry::plugin_descriptors;
 
+struct RegisteredPlugin {
+    descriptor: &'static PluginDescriptor,
+    plugin: OnceLock<Plugin>,
+    warned: OnceLock<()>,
+}
+
+impl RegisteredPlugin {
+    // Plugins are only created when first used since most commands need at
+    // most a few of them
+    fn plugin(&self) -> &Plugin {
+        self.plugin
+            .get_or_init(|| (self.descriptor.new_plugin)(&self.descriptor.name))
+    }
+}
+
+// Plugins are discovered when first used so that commands which don't use
+// them avoid searching for external plugins
 pub(crate) struct PluginManager {
-    go: Plugin,
-    java: Plugin,
-    python: Plugin,
-    go_warned: OnceLock<()>,
-    java_warned: OnceLock<()>,
+    plugins: OnceLock<Vec<RegisteredPlugin>>,
 }
 
 impl PluginManager {
-    pub(crate) fn new() -> Self {
+    pub(crate) const fn new() -> Self {
         Self {
-            go: isopy_go::new_plugin(Moniker::Go.as_str()),
-            java: isopy_java::new_plugin(Moniker::Java.as_str()),
-            python: isopy_python::new_plugin(Moniker::Python.as_str()),
-            go_warned: OnceLock::new(),
-            java_warned: OnceLock::new(),
+            plugins: OnceLock::new(),
         }
     }
 
     pub(crate) fn get_plugin(&self, moniker: &Moniker) -> &Plugin {
-        self.warn_if_experimental(moniker);
-        match moniker {
-            Moniker::Go => &self.go,
-            Moniker::Java => &self.java,
-            Moniker::Python => &self.python,
-        }
+        let registered_plugin = self.find(moniker);
+        Self::warn_if_experimental(registered_plugin);
+        registered_plugin.plugin()
+    }
+
+    // Detection runs for every enabled plugin, even those not used by the
+    // project, and from the shell prompt so doesn't warn about experimental
+    // plugins
+    pub(crate) fn detect_project_version(
+        &self,
+        moniker: &Moniker,
+        dir: &Path,
+    ) -> Result<Option<ProjectVersion>> {
+        self.find(moniker).plugin().detect_project_version(dir)
+    }
+
+    // Package IDs are parsed whenever a project or environment configuration
+    // file is read
+    pub(crate) fn parse_version(&self, moniker: &Moniker, s: &str) -> Result<Version> {
+        self.find(moniker).plugin().parse_version(s)
+    }
+
+    // Environment info is evaluated by shells via activation scripts and
+    // shell hooks so must not write warnings to standard output
+    pub(crate) fn make_env_info(
+        &self,
+        moniker: &Moniker,
+        dir: &Path,
+        options: &MakeEnvInfoOptions,
+    ) -> EnvInfo {
+        self.find(moniker).plugin().make_env_info(dir, options)
     }
 
     pub(crate) fn new_package_manager(
@@ -63,21 +102,37 @@ impl PluginManager {
         config_dir: &Path,
     ) -> PackageManager {
         let cache_dir = config_dir.join(CACHE_DIR_NAME).join(moniker.dir());
-        let ctx = PackageManagerHelper::new_context(&cache_dir);
+        let ctx = PackageManagerHelper::new_context(config_dir, &cache_dir);
         let plugin = self.get_plugin(moniker);
         plugin.new_package_manager(ctx)
     }
 
-    fn warn_if_experimental(&self, moniker: &Moniker) {
-        let cell = match moniker {
-            Moniker::Go => &self.go_warned,
-            Moniker::Java => &self.java_warned,
-            Moniker::Python => return,
-        };
-        cell.get_or_init(|| {
+    fn find(&self, moniker: &Moniker) -> &RegisteredPlugin {
+        self.plugins
+            .get_or_init(|| {
+                plugin_descriptors()
+                    .iter()
+                    .map(|&descriptor| RegisteredPlugin {
+                        descriptor,
+                        plugin: OnceLock::new(),
+                        warned: OnceLock::new(),
+                    })
+                    .collect()
+            })
+            .iter()
+            .find(|p| p.descriptor.name == moniker.as_str())
+            .expect("moniker must refer to registered plugin")
+    }
+
+    fn warn_if_experimental(registered_plugin: &RegisteredPlugin) {
+        if registered_plugin.descriptor.stability != Stability::Experimental {
+            return;
+        }
+
+        registered_plugin.warned.get_or_init(|| {
             warn!(
-                "{} plugin is experimental and may panic on unimplemented operations",
-                moniker.as_str()
+                "{} plugin is experimental and does not support all operations yet",
+                registered_plugin.descriptor.name
             );
         });
     }
@@ -0,0 +1,111 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::env::{split_paths, var_os};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, OnceLock};
+
+use isopy_lib::PluginDescriptor;
+use log::debug;
+
+use crate::constants::PLUGINS_DIR_NAME;
+use crate::env::EnvKey;
+
+// Plugins built into isopy: each plugin crate describes itself via its
+// descriptor function
+static BUILTIN_PLUGIN_DESCRIPTORS: LazyLock<Vec<PluginDescriptor>> = LazyLock::new(|| {
+    vec![
+        isopy_binary::descriptor(),
+        isopy_go::descriptor(),
+        isopy_java::descriptor(),
+        isopy_node::descriptor(),
+        isopy_python::descriptor(),
+        isopy_rust::descriptor(),
+    ]
+});
+
+static EXTERNAL_PLUGIN_DIRS: OnceLock<Vec<PathBuf>> = OnceLock::new();
+
+static EXTERNAL_PLUGIN_DESCRIPTORS: LazyLock<Vec<PluginDescriptor>> = LazyLock::new(|| {
+    isopy_external::find_plugins(EXTERNAL_PLUGIN_DIRS.get().cloned().unwrap_or_default())
+        .into_iter()
+        .filter(|d| {
+            let is_builtin = BUILTIN_PLUGIN_DESCRIPTORS.iter().any(|b| b.name == d.name);
+            if is_builtin {
+                debug!(
+                    "Ignoring {} since it has the same name as a built-in plugin",
+                    d.description
+                );
+            }
+            !is_builtin
+        })
+        .collect()
+});
+
+static PLUGIN_DESCRIPTORS: LazyLock<Vec<&'static PluginDescriptor>> = LazyLock::new(|| {
+    let mut descriptors = BUILTIN_PLUGIN_DESCRIPTORS
+        .iter()
+        .chain(EXTERNAL_PLUGIN_DESCRIPTORS.iter())
+        .collect::<Vec<_>>();
+    descriptors.sort_by(|a, b| a.name.cmp(&b.name));
+    descriptors
+});
+
+// Registers the plugins directory of the configuration directory and PATH as
+// locations of external plugins: must be called before parsing command-line
+// arguments so that their monikers are recognized. The directories are only
+// searched once plugins are needed since commands such as hook-env run on
+// every shell prompt
+pub(crate) fn register_external_plugins(config_dir: Option<&Path>) {
+    let mut dirs = Vec::new();
+    if let Some(config_dir) = config_dir {
+        dirs.push(config_dir.join(PLUGINS_DIR_NAME));
+    }
+    if let Some(path) = var_os("PATH") {
+        dirs.extend(split_paths(&path));
+    }
+    _ = EXTERNAL_PLUGIN_DIRS.set(dirs);
+}
+
+// Returns built-in plugins, which are the only plugins that can be disabled
+pub(crate) fn builtin_plugin_descriptors() -> &'static [PluginDescriptor] {
+    &BUILTIN_PLUGIN_DESCRIPTORS
+}
+
+// Returns built-in and external plugins sorted by name
+pub(crate) fn plugin_descriptors() -> &'static [&'static PluginDescriptor] {
+    &PLUGIN_DESCRIPTORS
+}
+
+pub(crate) fn find_plugin_descriptor(name: &str) -> Option<&'static PluginDescriptor> {
+    let descriptors = plugin_descriptors();
+    descriptors
+        .binary_search_by(|d| d.name.as_str().cmp(name))
+        .ok()
+        .map(|i| descriptors[i])
+}
+
+pub(crate) fn is_plugin_enabled(descriptor: &'static PluginDescriptor) -> bool {
+    descriptor
+        .enable_env_var
+        .as_deref()
+        .is_none_or(|name| EnvKey::PluginEnabled(name).is_true())
+}
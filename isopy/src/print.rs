@@ -25,7 +25,7 @@ use colored::Color;
 use crate::app::App;
 use crate::dir_info_ext::DirInfoExt;
 use crate::repo::{DirInfo, Link, Manifest, Repo};
-use crate::serialization::Env;
+use crate::serialization::{Env, EnvVenv};
 use crate::table::{Table, TableSettings, table_columns, table_divider, table_line, table_title};
 
 pub(crate) fn print_link(table: &mut Table, link: &Link, idx: Option<usize>) {
@@ -46,6 +46,27 @@ pub(crate) fn print_repo(table: &mut Table, repo: &Repo) {
     table_columns!(table, "Shared directory", repo.shared_dir().display());
 }
 
+fn print_env_venv(table: &mut Table, venv: &EnvVenv) {
+    table_line!(table, "venv: {}", venv.dir.display());
+    for requirements in &venv.requirements {
+        table_line!(
+            table,
+            "requirements: {} (sha256 {})",
+            requirements.path.display(),
+            requirements.sha256
+        );
+    }
+    if let Some(lock) = &venv.lock {
+        table_line!(
+            table,
+            "lock: {} (sha256 {})",
+            lock.path.display(),
+            lock.sha256
+        );
+    }
+    table_line!(table, "venv installed at: {}", venv.installed_at);
+}
+
 pub(crate) fn print_metadir(
     table: &mut Table,
     manifest: &Manifest,
@@ -67,6 +88,9 @@ pub(crate) fn print_metadir(
             table_columns!(table, "Package", &package.package_id);
             table_line!(table, "dir: {}", package.dir.display());
             table_line!(table, "url: {}", package.url);
+            if let Some(venv) = &package.venv {
+                print_env_venv(table, venv);
+            }
         }
     } else {
         table_columns!(
@@ -77,16 +101,24 @@ pub(crate) fn print_metadir(
     }
 }
 
-pub(crate) fn print_dir_info(app: &App, table: &mut Table, dir_info: &DirInfo, env: Option<&Env>) {
+pub(crate) fn print_dir_info(
+    app: &App,
+    table: &mut Table,
+    dir_info: &DirInfo,
+    env: Option<&Env>,
+) -> Result<()> {
     if let Some(env) = env {
         table_columns!(table, "Project directory", env.project_dir.display());
 
         for package in &env.packages {
-            let env_info = app.make_env_info(dir_info.data_dir(), package);
+            let env_info = app.make_env_info(dir_info.data_dir(), env, package)?;
 
             table_columns!(table, "Package", &package.package_id);
             table_line!(table, "dir: {}", package.dir.display());
             table_line!(table, "url: {}", package.url);
+            if let Some(venv) = &package.venv {
+                print_env_venv(table, venv);
+            }
 
             for p in env_info.path_dirs {
                 table_line!(table, "{}", p.display());
@@ -95,6 +127,16 @@ pub(crate) fn print_dir_info(app: &App, table: &mut Table, dir_info: &DirInfo, e
             for (k, v) in env_info.vars {
                 table_line!(table, "{k} = {v}");
             }
+
+            for path_var in env_info.path_vars {
+                for dir in path_var.dirs {
+                    table_line!(table, "{} += {}", path_var.name, dir.display());
+                }
+            }
+
+            for k in env_info.unset_vars {
+                table_line!(table, "unset {k}");
+            }
         }
     }
 
@@ -111,6 +153,7 @@ pub(crate) fn print_dir_info(app: &App, table: &mut Table, dir_info: &DirInfo, e
     table_columns!(table, "Link created at", dir_info.link_created_at());
     table_columns!(table, "Link ID", dir_info.link_id());
     table_columns!(table, "Project directory", dir_info.project_dir().display());
+    Ok(())
 }
 
 pub(crate) fn print_dir_info_and_env(
@@ -120,8 +163,7 @@ pub(crate) fn print_dir_info_and_env(
 ) -> Result<()> {
     table_title!(table, "Environment information");
     let env = Some(dir_info.read_env_config()?);
-    print_dir_info(app, table, dir_info, env.as_ref());
-    Ok(())
+    print_dir_info(app, table, dir_info, env.as_ref())
 }
 
 pub(crate) fn make_list_table() -> Table {
@@ -0,0 +1,227 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::path::Path;
+
+use anyhow::Result;
+
+// Clears write permissions on all files and directories in a shared install
+// so that environments cannot modify it
+pub fn make_read_only(dir: &Path) -> Result<()> {
+    set_write_mode(dir, false)
+}
+
+// Restores write permissions so that a shared install can be removed
+pub fn make_writable(dir: &Path) -> Result<()> {
+    set_write_mode(dir, true)
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn set_write_mode(path: &Path, writable: bool) -> Result<()> {
+    use std::fs::{read_dir, set_permissions, symlink_metadata};
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = symlink_metadata(path)?;
+    if metadata.file_type().is_symlink() {
+        return Ok(());
+    }
+
+    if metadata.is_dir() {
+        for entry in read_dir(path)? {
+            set_write_mode(&entry?.path(), writable)?;
+        }
+    }
+
+    let mut permissions = metadata.permissions();
+    let mode = permissions.mode();
+    permissions.set_mode(if writable {
+        mode | 0o200
+    } else {
+        mode & !0o222
+    });
+    set_permissions(path, permissions)?;
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+#[allow(clippy::missing_const_for_fn)]
+#[allow(clippy::unnecessary_wraps)]
+fn set_write_mode(_path: &Path, _writable: bool) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(all(test, any(target_os = "linux", target_os = "macos")))]
+mod tests {
+    use std::fs::{copy, create_dir_all, metadata, read_dir, remove_dir_all, write};
+    use std::os::unix::fs::{PermissionsExt, symlink};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    use anyhow::Result;
+    use isopy_lib::MakeEnvInfoOptions;
+    use tempfile::TempDir;
+
+    use crate::read_only::{make_read_only, make_writable};
+
+    fn list_tree(dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        for entry in read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() && !path.is_symlink() {
+                paths.extend(list_tree(&path)?);
+            }
+            paths.push(path);
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    #[test]
+    fn read_only_tree() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dir = temp_dir.path().join("shared");
+        create_dir_all(dir.join("lib"))?;
+        write(dir.join("lib").join("file"), "")?;
+
+        make_read_only(&dir)?;
+        for path in [dir.clone(), dir.join("lib"), dir.join("lib").join("file")] {
+            assert_eq!(0, metadata(&path)?.permissions().mode() & 0o222);
+        }
+
+        make_writable(&dir)?;
+        remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    // Builds a minimal Python installation from the host's Python, if any,
+    // with only pip in its site-packages directory
+    fn make_python_install(dir: &Path) -> Result<Option<PathBuf>> {
+        let Ok(output) = Command::new("python3")
+            .args([
+                "-c",
+                "import sys; print(sys.base_prefix); print('python%d.%d' % sys.version_info[:2])",
+            ])
+            .output()
+        else {
+            return Ok(None);
+        };
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        let output = String::from_utf8(output.stdout)?;
+        let mut lines = output.lines();
+        let (Some(prefix), Some(name)) = (lines.next(), lines.next()) else {
+            return Ok(None);
+        };
+        let host_lib_dir = Path::new(prefix).join("lib").join(name);
+        if !host_lib_dir.join("site-packages").join("pip").is_dir() {
+            return Ok(None);
+        }
+
+        // The interpreter is copied so that its prefix is the new directory
+        let python_path = dir.join("bin").join(name);
+        create_dir_all(dir.join("bin"))?;
+        copy(Path::new(prefix).join("bin").join(name), &python_path)?;
+
+        let lib_dir = dir.join("lib").join(name);
+        create_dir_all(lib_dir.join("site-packages"))?;
+        for entry in read_dir(&host_lib_dir)? {
+            let entry = entry?;
+            if entry.file_name() != "site-packages" {
+                symlink(entry.path(), lib_dir.join(entry.file_name()))?;
+            }
+        }
+        symlink(
+            host_lib_dir.join("site-packages").join("pip"),
+            lib_dir.join("site-packages").join("pip"),
+        )?;
+
+        Ok(Some(python_path))
+    }
+
+    #[test]
+    fn pip_install_into_shared_install() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let shared_dir = temp_dir.path().join("shared");
+        let workspace_dir = temp_dir.path().join("workspace");
+
+        // Skipped unless the host has a Python installation with pip
+        let Some(python_path) = make_python_install(&shared_dir)? else {
+            return Ok(());
+        };
+
+        let wheel_path = temp_dir.path().join("hello-1.0-py3-none-any.whl");
+        let status = Command::new(&python_path)
+            .arg("-c")
+            .arg(
+                r#"import sys, zipfile
+with zipfile.ZipFile(sys.argv[1], "w") as z:
+    z.writestr("hello.py", "")
+    z.writestr("hello-1.0.dist-info/METADATA", "Metadata-Version: 2.1\nName: hello\nVersion: 1.0\n")
+    z.writestr("hello-1.0.dist-info/WHEEL", "Wheel-Version: 1.0\nRoot-Is-Purelib: true\nTag: py3-none-any\n")
+    z.writestr("hello-1.0.dist-info/RECORD", "")
+"#,
+            )
+            .arg(&wheel_path)
+            .status()?;
+        assert!(status.success());
+
+        make_read_only(&shared_dir)?;
+        let before = list_tree(&shared_dir)?;
+
+        let plugin = (isopy_python::descriptor().new_plugin)("python");
+        let env_info = plugin.make_env_info(
+            &shared_dir,
+            &MakeEnvInfoOptions {
+                workspace_dir: Some(workspace_dir.clone()),
+                ..Default::default()
+            },
+        );
+        let output = Command::new(&python_path)
+            .args([
+                "-m",
+                "pip",
+                "install",
+                "--no-index",
+                "--disable-pip-version-check",
+            ])
+            .arg(&wheel_path)
+            .env_remove("PYTHONPATH")
+            .envs(env_info.vars)
+            .output()?;
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        assert_eq!(before, list_tree(&shared_dir)?);
+        assert!(
+            list_tree(&workspace_dir)?
+                .iter()
+                .any(|p| p.ends_with("site-packages/hello.py"))
+        );
+
+        make_writable(&shared_dir)?;
+        Ok(())
+    }
+}
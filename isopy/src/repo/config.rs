@@ -59,6 +59,14 @@ impl RepoConfig {
             self
         })
     }
+
+    pub fn read_only_repo(self) -> Result<Repo> {
+        Ok(Repo::new_read_only(if self.config_path.is_file() {
+            read_yaml_file::<Self>(&self.config_path)?
+        } else {
+            self
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -80,6 +88,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn read_only() -> Result<()> {
+        let base_dir = TempDir::with_prefix("isopy-repo-test")?;
+        let project_dir = base_dir.path().join("project");
+        let repo = RepoConfig::default(base_dir.path(), None)
+            .repo()?
+            .expect("must acquire lock");
+        assert!(repo.init(&project_dir)?.is_some());
+
+        // Doesn't wait for the lock held by the other instance
+        let read_only_repo = RepoConfig::default(base_dir.path(), None).read_only_repo()?;
+        assert!(read_only_repo.get(&project_dir)?.is_some());
+        assert!(read_only_repo.init(&base_dir.path().join("other")).is_err());
+        Ok(())
+    }
+
     #[test]
     fn prefix() -> Result<()> {
         let base_dir = TempDir::with_prefix("isopy-repo-test")?;
@@ -41,7 +41,7 @@ const MANIFEST_FILE_NAME: &str = "manifest.yaml";
 #[derive(Debug)]
 pub struct Repo {
     config: RepoConfig,
-    _lock_file: LockFile,
+    lock_file: Option<LockFile>,
 }
 
 impl Repo {
@@ -51,13 +51,23 @@ impl Repo {
         Ok(if lock_file.try_lock_with_pid()? {
             Some(Self {
                 config,
-                _lock_file: lock_file,
+                lock_file: Some(lock_file),
             })
         } else {
             None
         })
     }
 
+    // Opens the repository without taking the lock: other instances of isopy
+    // may be modifying it so the repository cannot be modified
+    #[must_use]
+    pub const fn new_read_only(config: RepoConfig) -> Self {
+        Self {
+            config,
+            lock_file: None,
+        }
+    }
+
     #[must_use]
     pub fn lock_path(&self) -> &Path {
         &self.config.lock_path
@@ -115,7 +125,32 @@ impl Repo {
         Ok(manifests)
     }
 
+    // Shared installs live two levels down as <shared_dir>/<group>/<key> while
+    // hidden entries are staging directories for installs in progress
+    pub fn list_shared(&self) -> Result<Vec<PathBuf>> {
+        let mut shared = Vec::new();
+
+        if self.config.shared_dir.is_dir() {
+            for group in read_dir(&self.config.shared_dir)? {
+                let group = group?;
+                if !group.path().is_dir() || is_hidden(&group.path()) {
+                    continue;
+                }
+
+                for entry in read_dir(group.path())? {
+                    let entry = entry?;
+                    if entry.path().is_dir() && !is_hidden(&entry.path()) {
+                        shared.push(entry.path());
+                    }
+                }
+            }
+        }
+
+        Ok(shared)
+    }
+
     pub fn init(&self, project_dir: &Path) -> Result<Option<DirInfo>> {
+        self.check_writable()?;
         let link_id = Self::make_link_id(project_dir)?;
         let link_path = self.make_link_path(&link_id);
         if link_path.is_file() {
@@ -150,6 +185,7 @@ impl Repo {
     }
 
     pub fn remove(&self, project_dir: &Path) -> Result<bool> {
+        self.check_writable()?;
         Ok(if let Some(dir_info) = self.get(project_dir)? {
             remove_file(dir_info.link_path())?;
             Trash::compute(self)?.empty()?;
@@ -212,6 +248,7 @@ impl Repo {
     }
 
     pub fn link(&self, meta_id: &MetaId, project_dir: &Path) -> Result<Option<DirInfo>> {
+        self.check_writable()?;
         let manifest = self.read_manifest(meta_id)?;
 
         let link_id = Self::make_link_id(project_dir)?;
@@ -237,6 +274,7 @@ impl Repo {
 
     #[allow(unused)]
     pub fn purge(&self) -> Result<()> {
+        self.check_writable()?;
         if self.config.shared_dir.is_dir() {
             remove_dir_all(&self.config.shared_dir)?;
         }
@@ -255,6 +293,13 @@ impl Repo {
         Ok(())
     }
 
+    fn check_writable(&self) -> Result<()> {
+        if self.lock_file.is_none() {
+            bail!("Repository was opened read-only")
+        }
+        Ok(())
+    }
+
     fn make_link_id(project_dir: &Path) -> Result<LinkId> {
         LinkId::try_from(project_dir)
     }
@@ -267,3 +312,9 @@ impl Repo {
         self.config.container_dir.join(format!("{meta_id}"))
     }
 }
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|s| s.to_str())
+        .is_some_and(|s| s.starts_with('.'))
+}
@@ -21,10 +21,12 @@
 //
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::fs::{remove_dir_all, remove_file};
+use std::fs::{read_dir, read_link, remove_dir_all, remove_file};
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 
+use crate::read_only::make_writable;
 use crate::repo::Repo;
 use crate::repo::link::Link;
 use crate::repo::manifest::Manifest;
@@ -33,6 +35,7 @@ use crate::repo::manifest::Manifest;
 pub struct Trash {
     pub unreferenced_manifests: Vec<Manifest>,
     pub invalid_links: Vec<Link>,
+    pub unreferenced_shared: Vec<PathBuf>,
 }
 
 struct ManifestStatus {
@@ -91,21 +94,47 @@ impl Trash {
             .filter(|x| !x.is_valid)
             .map(|x| x.link)
             .collect::<Vec<_>>();
+
+        // Environments reference shared installs through symbolic links in
+        // their data directories: count these only for manifests that survive
+        let mut shared_ref_counts = repo
+            .list_shared()?
+            .into_iter()
+            .map(|p| (p, 0usize))
+            .collect::<HashMap<_, _>>();
+        for status in manifest_map.values().filter(|x| x.is_referenced) {
+            for target in shared_targets(status.manifest.data_dir())? {
+                if let Some(count) = shared_ref_counts.get_mut(&target) {
+                    *count += 1;
+                }
+            }
+        }
+
         let unreferenced_manifests = manifest_map
             .into_values()
             .filter(|x| !x.is_referenced)
             .map(|x| x.manifest)
             .collect::<Vec<_>>();
+        let mut unreferenced_shared = shared_ref_counts
+            .into_iter()
+            .filter(|(_, count)| *count == 0)
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>();
+        unreferenced_shared.sort();
 
         Ok(Self {
             unreferenced_manifests,
             invalid_links,
+            unreferenced_shared,
         })
     }
 
     #[must_use]
     pub const fn is_empty(&self) -> bool {
-        self.invalid_links.len() + self.unreferenced_manifests.len() == 0
+        self.invalid_links.len()
+            + self.unreferenced_manifests.len()
+            + self.unreferenced_shared.len()
+            == 0
     }
 
     pub fn empty(&mut self) -> Result<()> {
@@ -117,6 +146,91 @@ impl Trash {
             remove_dir_all(m.data_dir())?;
         }
 
+        for p in self.unreferenced_shared.drain(..) {
+            make_writable(&p)?;
+            remove_dir_all(p)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn shared_targets(data_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut targets = Vec::new();
+
+    if data_dir.is_dir() {
+        for entry in read_dir(data_dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_symlink() {
+                targets.push(read_link(entry.path())?);
+            }
+        }
+    }
+
+    Ok(targets)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::fs::{create_dir_all, remove_file};
+    use std::os::unix::fs::symlink;
+    use std::path::Path;
+
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    use crate::repo::{Repo, RepoConfig, Trash};
+
+    fn reference_shared(repo: &Repo, project_dir: &Path, shared_path: &Path) -> Result<()> {
+        create_dir_all(project_dir)?;
+        let dir_info = repo.init(project_dir)?.expect("must initialize");
+        symlink(shared_path, dir_info.data_dir().join("python"))?;
+        Ok(())
+    }
+
+    #[test]
+    fn shared_installs_are_reference_counted() -> Result<()> {
+        let base_dir = TempDir::with_prefix("isopy-repo-test")?;
+        let repo = RepoConfig::default(base_dir.path(), None)
+            .repo()?
+            .expect("must lock");
+
+        let used = repo
+            .shared_dir()
+            .join("python")
+            .join("3.12.1-0123456789abcdef");
+        let unused = repo
+            .shared_dir()
+            .join("python")
+            .join("3.11.7-fedcba9876543210");
+        let staging = repo.shared_dir().join("python").join(".isopy-staging-1234");
+        create_dir_all(&used)?;
+        create_dir_all(&unused)?;
+        create_dir_all(&staging)?;
+
+        let project_dir0 = base_dir.path().join("project0");
+        let project_dir1 = base_dir.path().join("project1");
+        reference_shared(&repo, &project_dir0, &used)?;
+        reference_shared(&repo, &project_dir1, &used)?;
+
+        let trash = Trash::compute(&repo)?;
+        assert_eq!(vec![unused.clone()], trash.unreferenced_shared);
+
+        remove_file(repo.get(&project_dir0)?.expect("must exist").link_path())?;
+        let trash = Trash::compute(&repo)?;
+        assert_eq!(vec![unused.clone()], trash.unreferenced_shared);
+
+        remove_file(repo.get(&project_dir1)?.expect("must exist").link_path())?;
+        let mut trash = Trash::compute(&repo)?;
+        assert_eq!(
+            vec![unused.clone(), used.clone()],
+            trash.unreferenced_shared
+        );
+
+        trash.empty()?;
+        assert!(!used.exists());
+        assert!(!unused.exists());
+        assert!(staging.exists());
         Ok(())
     }
 }
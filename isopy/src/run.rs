@@ -19,19 +19,23 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
-use std::env::current_dir;
+use std::borrow::Cow;
+use std::env::{args_os, current_dir};
+use std::ffi::OsString;
 use std::path::PathBuf;
 
 use anyhow::{Result, bail};
 use clap::Parser;
 use isopy_lib::TagFilter;
 use log::{LevelFilter, set_max_level};
+use path_absolutize::Absolutize;
 
 use crate::app::App;
 use crate::args::{Args, Command, PackageFilter};
 use crate::constants::{CONFIG_DIR_NAME, DEFAULT_MONIKER_CONFIG_NAME};
-use crate::env::set_up_env;
+use crate::env::{EnvKey, set_up_env};
 use crate::moniker::Moniker;
+use crate::plugin_registry::register_external_plugins;
 use crate::repo::RepoConfig;
 use crate::status::StatusResult;
 use crate::terminal::reset_terminal;
@@ -41,6 +45,7 @@ fn set_up() -> Result<()> {
     reset_terminal();
     init_ui(true)?;
     set_max_level(LevelFilter::Trace);
+    register_external_plugins(pre_parse_config_dir().as_deref());
     set_up_env()?;
     Ok(())
 }
@@ -70,6 +75,35 @@ fn get_moniker(app: &App, moniker: Option<&Moniker>) -> Result<Option<Moniker>>
     })
 }
 
+// Determines the configuration directory before command-line arguments are
+// parsed so that external plugins installed there can be registered
+fn pre_parse_config_dir() -> Option<PathBuf> {
+    let mut args = args_os().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+
+        let value = if arg == "-d" || arg == "--config-dir" {
+            args.next()
+        } else {
+            arg.to_str()
+                .and_then(|s| s.strip_prefix("--config-dir="))
+                .map(OsString::from)
+        };
+
+        if let Some(value) = value {
+            return PathBuf::from(value).absolutize().ok().map(Cow::into_owned);
+        }
+    }
+
+    EnvKey::ConfigDir
+        .get()
+        .ok()
+        .map(PathBuf::from)
+        .or_else(default_config_dir)
+}
+
 pub(crate) async fn run() -> StatusResult {
     set_up()?;
 
@@ -81,12 +115,19 @@ pub(crate) async fn run() -> StatusResult {
         bail!("Could not infer isopy cache directory location: please specify using --dir option")
     };
 
-    let repo = match RepoConfig::default(&config_dir, None).repo() {
-        Ok(Some(r)) => r,
-        Ok(None) => {
-            bail!("Could not acquire lock on repository: is another instance of isopy running?")
+    // Commands run from the shell prompt only read the repository so must not
+    // fail or block other commands while another instance of isopy is running
+    let repo_config = RepoConfig::default(&config_dir, None);
+    let repo = if matches!(args.command, Command::HookEnv { .. } | Command::Prompt(_)) {
+        repo_config.read_only_repo()?
+    } else {
+        match repo_config.repo() {
+            Ok(Some(r)) => r,
+            Ok(None) => {
+                bail!("Could not acquire lock on repository: is another instance of isopy running?")
+            }
+            Err(e) => bail!(e),
         }
-        Err(e) => bail!(e),
     };
 
     let cwd = match args.cwd {
@@ -100,55 +141,73 @@ pub(crate) async fn run() -> StatusResult {
 
 async fn run_command(app: App, command: Command) -> StatusResult {
     use crate::args::Command::{
-        Check, Completions, Docs, Download, Env, Info, Init, Link, List, Packages, Project, Prompt,
-        Remove, Run, Scratch, SetConfig, Shell, Tags, Update, Wrap,
+        Activate, Check, Completions, Docs, Download, Env, Hook, HookEnv, Info, Init, Link, List,
+        Packages, Plugins, Project, Prompt, Remove, Run, Scratch, SetConfig, Shell, Tags, Update,
+        Wrap,
     };
     use crate::commands::{
-        do_check, do_completions, do_docs, do_download, do_env, do_info, do_init, do_link, do_list,
-        do_packages, do_project, do_prompt, do_remove, do_run, do_scratch, do_set_config, do_shell,
-        do_tags, do_update, do_wrap,
+        do_activate, do_check, do_completions, do_docs, do_download, do_env, do_hook, do_hook_env,
+        do_info, do_init, do_link, do_list, do_packages, do_plugins, do_project, do_prompt,
+        do_remove, do_run, do_scratch, do_set_config, do_shell, do_tags, do_update, do_wrap,
     };
 
     match command {
+        Activate { shell } => do_activate(&app, shell.into()),
         Check { clean, .. } => do_check(&app, clean),
         Completions { shell } => do_completions(shell),
         Docs => do_docs(&app),
-        Download { package_id, tags } => {
-            do_download(&app, &package_id, &TagFilter::new(tags)).await
+        Download {
+            package_id,
+            tags,
+            target,
+        } => {
+            let target = target.map_or_else(|| app.host_target(), Ok)?;
+            do_download(&app, &package_id, &TagFilter::new(tags), target).await
         }
         Env {
             package_id,
             download,
             ..
         } => do_env(&app, &package_id, download).await,
+        Hook { shell } => do_hook(shell.into()),
+        HookEnv { shell, key } => do_hook_env(&app, shell.into(), key.as_deref()),
         Info => do_info(&app),
-        Init { download, .. } => do_init(&app, download).await,
+        Init {
+            download, write, ..
+        } => do_init(&app, download, write).await,
         Link { dir_id } => do_link(&app, &dir_id),
         List { verbose, .. } => do_list(&app, verbose),
         Packages {
             moniker,
             filter,
             tags,
+            target,
             verbose,
             ..
         } => {
+            let target = target.map_or_else(|| app.host_target(), Ok)?;
             do_packages(
                 &app,
                 get_moniker(&app, moniker.as_ref())?.as_ref(),
                 PackageFilter::to_source_filter(filter),
                 &TagFilter::new(tags),
+                target,
                 verbose,
             )
             .await
         }
-        Project { package_id } => do_project(&app, &package_id),
+        Plugins => do_plugins(),
+        Project { package_id } => do_project(&app, package_id.as_ref()).await,
         Prompt(prompt_config) => do_prompt(&app, &prompt_config),
         Remove { project_dir } => do_remove(&app, &project_dir).await,
         Run { program, args } => do_run(app, &program, &args),
         Scratch => do_scratch(&app).await,
         SetConfig { name, value } => do_set_config(&app, &name, value.as_ref()),
         Shell { verbose, .. } => do_shell(app, verbose),
-        Tags { moniker } => do_tags(&app, get_moniker(&app, moniker.as_ref())?.as_ref()).await,
+        Tags { moniker, target } => {
+            let target = target.map_or_else(|| app.host_target(), Ok)?;
+            do_tags(&app, get_moniker(&app, moniker.as_ref())?.as_ref(), target).await
+        }
         Update { moniker } => do_update(&app, get_moniker(&app, moniker.as_ref())?.as_ref()).await,
         Wrap {
             wrapper_file_name,
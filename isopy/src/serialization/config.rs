@@ -19,8 +19,10 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
+use isopy_lib::Libc;
 use serde::{Deserialize, Serialize};
 
+use crate::libc_format;
 use crate::moniker::Moniker;
 
 #[derive(Debug, Default, Deserialize, Serialize)]
@@ -28,4 +30,16 @@ use crate::moniker::Moniker;
 pub(crate) struct Config {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) default_moniker: Option<Moniker>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) shared_installs: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "libc_format")]
+    pub(crate) libc: Option<Libc>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) env_workspaces: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) go_toolchain: Option<String>,
 }
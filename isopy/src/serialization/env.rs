@@ -19,6 +19,7 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
+use std::ops::Not;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
@@ -32,4 +33,7 @@ pub(crate) struct Env {
 
     #[serde(rename = "packages")]
     pub(crate) packages: Vec<EnvPackage>,
+
+    #[serde(rename = "isolated", default, skip_serializing_if = "Not::not")]
+    pub(crate) isolated: bool,
 }
@@ -25,6 +25,7 @@ use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::package_id::PackageId;
+use crate::serialization::EnvVenv;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub(crate) struct EnvPackage {
@@ -36,4 +37,7 @@ pub(crate) struct EnvPackage {
 
     #[serde(rename = "url")]
     pub(crate) url: Url,
+
+    #[serde(rename = "venv", default, skip_serializing_if = "Option::is_none")]
+    pub(crate) venv: Option<EnvVenv>,
 }
@@ -0,0 +1,55 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::date_time_format;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct EnvVenv {
+    #[serde(rename = "dir")]
+    pub(crate) dir: PathBuf,
+
+    #[serde(
+        rename = "requirements",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub(crate) requirements: Vec<EnvRequirements>,
+
+    #[serde(rename = "lock", default, skip_serializing_if = "Option::is_none")]
+    pub(crate) lock: Option<EnvRequirements>,
+
+    #[serde(rename = "installed_at", with = "date_time_format")]
+    pub(crate) installed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub(crate) struct EnvRequirements {
+    #[serde(rename = "path")]
+    pub(crate) path: PathBuf,
+
+    #[serde(rename = "sha256")]
+    pub(crate) sha256: String,
+}
@@ -24,17 +24,21 @@ mod directory;
 mod download;
 mod env;
 mod env_package;
+mod env_venv;
 mod file;
 mod manifest;
 mod paginated_file;
 mod project;
+mod venv;
 
 pub(crate) use config::*;
 pub(crate) use directory::*;
 pub(crate) use download::*;
 pub(crate) use env::*;
 pub(crate) use env_package::*;
+pub(crate) use env_venv::*;
 pub(crate) use file::*;
 pub(crate) use manifest::*;
 pub(crate) use paginated_file::*;
 pub(crate) use project::*;
+pub(crate) use venv::*;
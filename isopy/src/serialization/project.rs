@@ -19,12 +19,47 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
+use std::ops::Not;
+
 use serde::{Deserialize, Serialize};
 
 use crate::package_id::PackageId;
+use crate::serialization::Venv;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub(crate) struct Project {
     #[serde(rename = "package_ids")]
     pub(crate) package_ids: Vec<PackageId>,
+
+    #[serde(rename = "venv", default, skip_serializing_if = "Option::is_none")]
+    pub(crate) venv: Option<Venv>,
+
+    #[serde(rename = "isolated", default, skip_serializing_if = "Not::not")]
+    pub(crate) isolated: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::serialization::Project;
+
+    #[test]
+    fn isolated_absent() -> Result<()> {
+        let project = serde_yaml::from_str::<Project>("package_ids: []\n")?;
+        assert!(!project.isolated);
+        assert_eq!("package_ids: []\n", serde_yaml::to_string(&project)?);
+        Ok(())
+    }
+
+    #[test]
+    fn isolated() -> Result<()> {
+        let project = serde_yaml::from_str::<Project>("package_ids: []\nisolated: true\n")?;
+        assert!(project.isolated);
+        assert_eq!(
+            "package_ids: []\nisolated: true\n",
+            serde_yaml::to_string(&project)?
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,96 @@
+// Copyright (c) 2023 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use std::ops::Not;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::DEFAULT_VENV_DIR;
+
+fn default_path() -> PathBuf {
+    PathBuf::from(DEFAULT_VENV_DIR)
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Venv {
+    #[serde(rename = "path", default = "default_path")]
+    pub(crate) path: PathBuf,
+
+    #[serde(
+        rename = "requirements",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub(crate) requirements: Vec<PathBuf>,
+
+    #[serde(
+        rename = "system_site_packages",
+        default,
+        skip_serializing_if = "Not::not"
+    )]
+    pub(crate) system_site_packages: bool,
+
+    #[serde(rename = "lock", default, skip_serializing_if = "Option::is_none")]
+    pub(crate) lock: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use anyhow::Result;
+
+    use crate::serialization::Project;
+
+    #[test]
+    fn venv_defaults() -> Result<()> {
+        let project = serde_yaml::from_str::<Project>("package_ids: []\nvenv: {}\n")?;
+        let venv = project.venv.expect("venv must be present");
+        assert_eq!(PathBuf::from(".venv"), venv.path);
+        assert!(venv.requirements.is_empty());
+        assert!(!venv.system_site_packages);
+        assert!(venv.lock.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn venv_absent() -> Result<()> {
+        let project = serde_yaml::from_str::<Project>("package_ids: []\n")?;
+        assert!(project.venv.is_none());
+        assert_eq!("package_ids: []\n", serde_yaml::to_string(&project)?);
+        Ok(())
+    }
+
+    #[test]
+    fn venv_all_fields() -> Result<()> {
+        let project = serde_yaml::from_str::<Project>(
+            "package_ids: []\nvenv:\n  path: env\n  requirements:\n  - requirements.txt\n  system_site_packages: true\n  lock: pylock.toml\n",
+        )?;
+        let venv = project.venv.expect("venv must be present");
+        assert_eq!(PathBuf::from("env"), venv.path);
+        assert_eq!(vec![PathBuf::from("requirements.txt")], venv.requirements);
+        assert!(venv.system_site_packages);
+        assert_eq!(Some(PathBuf::from("pylock.toml")), venv.lock);
+        Ok(())
+    }
+}
@@ -19,13 +19,13 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
-use std::env::{join_paths, set_var, split_paths, var_os};
+use std::env::{join_paths, remove_var, set_var, split_paths, var_os};
 use std::ffi::OsString;
 use std::path::PathBuf;
 use std::process::ExitStatus;
 
 use anyhow::Result;
-use isopy_lib::EnvInfo;
+use isopy_lib::{EnvInfo, PathVarPosition};
 
 use crate::shell::IsopyEnv;
 
@@ -55,14 +55,25 @@ impl Command {
     }
 
     pub(crate) fn exec(&self, isopy_env: &IsopyEnv, env_info: &EnvInfo) -> Result<ExitStatus> {
-        prepend_paths(&env_info.path_dirs)?;
+        prepend_paths("PATH", &env_info.path_dirs)?;
 
         isopy_env.set_vars();
 
+        for key in &env_info.unset_vars {
+            unsafe { remove_var(key) };
+        }
+
         for (key, value) in &env_info.vars {
             unsafe { set_var(key, value) };
         }
 
+        for path_var in &env_info.path_vars {
+            match path_var.position {
+                PathVarPosition::Prepend => prepend_paths(&path_var.name, &path_var.dirs)?,
+                PathVarPosition::Append => append_paths(&path_var.name, &path_var.dirs)?,
+            }
+        }
+
         self.exec_impl()
     }
 
@@ -127,14 +138,27 @@ impl Command {
     }
 }
 
-fn prepend_paths(paths: &[PathBuf]) -> Result<()> {
+fn prepend_paths(key: &str, paths: &[PathBuf]) -> Result<()> {
     let mut new_paths = paths.to_owned();
-    if let Some(path) = var_os("PATH") {
-        new_paths.extend(split_paths(&path));
+    if let Some(path) = var_os(key) {
+        new_paths.extend(split_paths(&path).filter(|p| !p.as_os_str().is_empty()));
+    }
+
+    let p = join_paths(new_paths)?;
+    unsafe { set_var(key, p) };
+
+    Ok(())
+}
+
+fn append_paths(key: &str, paths: &[PathBuf]) -> Result<()> {
+    let mut new_paths = Vec::new();
+    if let Some(path) = var_os(key) {
+        new_paths.extend(split_paths(&path).filter(|p| !p.as_os_str().is_empty()));
     }
+    new_paths.extend_from_slice(paths);
 
     let p = join_paths(new_paths)?;
-    unsafe { set_var("PATH", p) };
+    unsafe { set_var(key, p) };
 
     Ok(())
 }
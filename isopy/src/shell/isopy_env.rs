@@ -58,6 +58,10 @@ impl IsopyEnv {
     }
 
     pub(crate) fn set_vars(&self) {
-        EnvKey::IsopyEnv.set(&format!("{}-{}", self.meta_id, self.link_id));
+        EnvKey::IsopyEnv.set(&self.value());
+    }
+
+    pub(crate) fn value(&self) -> String {
+        format!("{}-{}", self.meta_id, self.link_id)
     }
 }
@@ -19,6 +19,8 @@
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
 // WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 //
+use std::fs::{create_dir_all, read_to_string, write};
+
 use assert_cmd::Command;
 use predicates::prelude::*;
 use tempfile::TempDir;
@@ -136,6 +138,73 @@ fn prompt_with_before_after() {
         .stdout(predicate::str::is_empty());
 }
 
+#[test]
+fn activate_without_env() {
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .env_remove("ISOPY_ENV")
+        .args(["activate", "--shell", "bash"])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Could not find environment"));
+}
+
+#[test]
+fn hook() {
+    isopy()
+        .args(["hook", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("_isopy_hook"));
+
+    isopy()
+        .args(["hook", "powershell"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "only supported for bash, zsh and fish",
+        ));
+}
+
+#[test]
+fn hook_env_without_env() {
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .env_remove("ISOPY_ENV")
+        .args(["hook-env", "--shell", "bash", "--key", ""])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn hook_env_while_locked() {
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    // Simulate another instance of isopy holding the repository lock
+    let mut lock_file = fslock::LockFile::open(&config_dir.path().join(".lock")).unwrap();
+    assert!(lock_file.try_lock_with_pid().unwrap());
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .env_remove("ISOPY_ENV")
+        .args(["hook-env", "--shell", "bash", "--key", ""])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .args(["info"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Could not acquire lock"));
+}
+
 #[test]
 fn set_config_invalid_name() {
     let config_dir = TempDir::new().unwrap();
@@ -169,6 +238,66 @@ fn set_config_and_clear() {
         .success();
 }
 
+#[test]
+fn set_config_shared_installs() {
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .arg("set-config")
+        .arg("shared_installs")
+        .arg("true")
+        .assert()
+        .success();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .arg("set-config")
+        .arg("shared_installs")
+        .arg("not-a-bool")
+        .assert()
+        .failure();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .arg("set-config")
+        .arg("shared_installs")
+        .assert()
+        .success();
+}
+
+#[test]
+fn set_config_libc() {
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .arg("set-config")
+        .arg("libc")
+        .arg("musl")
+        .assert()
+        .success();
+
+    let info = isopy_with_dirs(&config_dir, &cwd)
+        .arg("info")
+        .assert()
+        .success();
+    if cfg!(target_os = "linux") {
+        info.stdout(predicate::str::contains("-musl"));
+    }
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .arg("set-config")
+        .arg("libc")
+        .arg("uclibc")
+        .assert()
+        .failure();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .arg("set-config")
+        .arg("libc")
+        .assert()
+        .success();
+}
+
 #[test]
 fn invalid_subcommand() {
     isopy()
@@ -189,3 +318,214 @@ fn check_with_clean_on_empty_repo() {
         .assert()
         .success();
 }
+
+#[test]
+fn packages_invalid_target() {
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .args(["packages", "python", "--target", "solaris-sparc"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--target"));
+}
+
+#[test]
+fn node_disabled_by_default() {
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .env_remove("ISOPY_NODE")
+        .args(["packages", "node"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ISOPY_NODE"));
+}
+
+#[test]
+fn plugins() {
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .env_remove("ISOPY_GO")
+        .arg("plugins")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("python"))
+        .stdout(predicate::str::contains("ISOPY_GO=true"))
+        .stdout(predicate::str::is_match("go +disabled").unwrap());
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .env("ISOPY_GO", "true")
+        .arg("plugins")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match("go +enabled").unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn external_plugin() {
+    use std::fs::{Permissions, set_permissions};
+    use std::os::unix::fs::PermissionsExt;
+
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    let plugins_dir = config_dir.path().join("plugins");
+    create_dir_all(&plugins_dir).unwrap();
+    let path = plugins_dir.join("isopy-plugin-hello");
+    write(&path, "#!/bin/sh\nexit 1\n").unwrap();
+    set_permissions(&path, Permissions::from_mode(0o755)).unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .arg("plugins")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match("hello +enabled +external").unwrap());
+}
+
+#[test]
+fn project_inferred_from_go_mod() {
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .env("ISOPY_GO", "true")
+        .arg("project")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "No package versions could be inferred",
+        ));
+
+    write(
+        cwd.path().join("go.mod"),
+        "module example.com/hello\n\ngo 1.22\n\ntoolchain go1.22.3\n",
+    )
+    .unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .env("ISOPY_GO", "true")
+        .arg("prompt")
+        .assert()
+        .success()
+        .stdout("(config)");
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .env("ISOPY_GO", "true")
+        .arg("project")
+        .assert()
+        .success();
+
+    let project_config = read_to_string(cwd.path().join(".isopy.yaml")).unwrap();
+    assert!(project_config.contains("go:go1.22.3"));
+}
+
+// An external plugin with the same name as a built-in plugin is reported
+// when external plugins are searched for
+#[cfg(unix)]
+#[test]
+fn prompt_skips_external_plugin_discovery() {
+    use std::fs::{Permissions, set_permissions};
+    use std::os::unix::fs::PermissionsExt;
+
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    let plugins_dir = config_dir.path().join("plugins");
+    create_dir_all(&plugins_dir).unwrap();
+    let path = plugins_dir.join("isopy-plugin-go");
+    write(&path, "#!/bin/sh\nexit 1\n").unwrap();
+    set_permissions(&path, Permissions::from_mode(0o755)).unwrap();
+
+    write(
+        cwd.path().join("go.mod"),
+        "module example.com/hello\n\ngo 1.22\n\ntoolchain go1.22.3\n",
+    )
+    .unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .env("ISOPY_GO", "true")
+        .args(["--level", "debug", "plugins"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Ignoring External plugin"));
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .env("ISOPY_GO", "true")
+        .args(["--level", "debug", "prompt"])
+        .assert()
+        .success()
+        .stdout("(config)");
+}
+
+#[test]
+fn project_inferred_from_python_version() {
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    write(cwd.path().join(".python-version"), "3.12.1\n").unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .arg("prompt")
+        .assert()
+        .success()
+        .stdout("(config)");
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .arg("project")
+        .assert()
+        .success();
+
+    let project_config = read_to_string(cwd.path().join(".isopy.yaml")).unwrap();
+    assert!(project_config.contains("python:3.12.1"));
+}
+
+#[test]
+fn unknown_moniker() {
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .args(["packages", "cobol"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("isopy plugins"));
+}
+
+#[test]
+fn binary_disabled_by_default() {
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .env_remove("ISOPY_BINARY")
+        .args(["packages", "binary"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ISOPY_BINARY"));
+}
+
+#[test]
+fn binary_invalid_manifest() {
+    let config_dir = TempDir::new().unwrap();
+    let cwd = TempDir::new().unwrap();
+    let manifests_dir = config_dir.path().join("manifests");
+    create_dir_all(&manifests_dir).unwrap();
+    write(
+        manifests_dir.join("tool.yaml"),
+        "url: https://example.com/{version}\n",
+    )
+    .unwrap();
+
+    isopy_with_dirs(&config_dir, &cwd)
+        .env("ISOPY_BINARY", "true")
+        .args(["packages", "binary"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("tool.yaml"));
+}